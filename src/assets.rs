@@ -3,6 +3,10 @@ use dioxus::prelude::*;
 pub const MAIN_SCRIPT: Asset = asset!("/assets/dist/main.js");
 pub const MAIN_STYLE: Asset = asset!("/assets/dist/main.css");
 
+// Embed the bundled stylesheet and script for standalone HTML pages
+pub const MAIN_STYLE_SOURCE: &str = include_str!("../assets/dist/main.css");
+pub const MAIN_SCRIPT_SOURCE: &str = include_str!("../assets/dist/main.js");
+
 // Embed header image as base64 at compile time
 const HEADER_IMAGE_BYTES: &[u8] = include_bytes!("../assets/arto-header.png");
 
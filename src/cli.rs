@@ -0,0 +1,520 @@
+use crate::theme::Theme;
+use crate::window::CreateMainWindowConfigParams;
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Usage shown by `--help` and on invalid arguments
+pub const USAGE: &str = indoc::indoc! {"
+    Usage: arto [OPTIONS] [PATH...]
+           arto render [--output <PATH>] [--theme <THEME>] <PATH>
+
+    Open Markdown files and directories. Each file opens in a tab and a
+    directory becomes the sidebar root. A PATH of - reads Markdown from
+    standard input (e.g. `some-command | arto -`).
+
+    Paths may point into a document:
+      README.md#installation    Scroll to a heading
+      src/main.rs:120           Select a line (also :120-140 and :120:5)
+
+    Options:
+          --new-window          Open in a new window instead of the focused one
+          --theme <THEME>       Window theme: auto, light or dark
+          --no-sidebar          Open the window with the sidebar closed
+          --standalone          Do not hand over to an already running Arto
+      -h, --help                Print help
+      -V, --version             Print version
+
+    Render (no window is opened):
+      Render a Markdown file or a directory of Markdown files to standalone
+      HTML pages. Links to Markdown files point at their pages, and the exit
+      status is 1 when a local link or image is broken.
+
+      -o, --output <PATH>       Output file or directory (a single file is
+                                written to standard output by default)
+          --theme <THEME>       Page theme: light (default), dark or auto
+"};
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CliError {
+    #[error("unknown option '{0}'")]
+    UnknownOption(String),
+    #[error("option '{0}' requires a value")]
+    MissingValue(String),
+    #[error("invalid theme '{0}' (expected auto, light or dark)")]
+    InvalidTheme(String),
+    #[error("render requires exactly one PATH")]
+    RenderPath,
+    #[error("rendering a directory requires --output")]
+    RenderOutput,
+}
+
+/// What the command line asks the application to do
+#[derive(Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    /// Open files and directories (possibly none)
+    Open(CliArgs),
+    /// Render Markdown to HTML without opening a window
+    Render(RenderArgs),
+    /// Print usage and exit
+    Help,
+    /// Print the version and exit
+    Version,
+}
+
+/// A file or directory given on the command line
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenTarget {
+    pub path: PathBuf,
+    /// Heading id or line fragment (`L120`, `L120-L140`) to scroll to
+    pub anchor: Option<String>,
+}
+
+/// Markdown piped to standard input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StdinDocument {
+    pub markdown: String,
+    /// Directory relative links and images are resolved against (the working directory)
+    pub base_dir: PathBuf,
+}
+
+/// Files to open and window options given on the command line
+///
+/// Serialized to forward an invocation to the running instance.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CliArgs {
+    pub targets: Vec<OpenTarget>,
+    /// Markdown read from standard input (`-`)
+    pub stdin: Option<StdinDocument>,
+    /// Standard input was requested but has not been read yet
+    #[serde(skip)]
+    pub read_stdin: bool,
+    /// Open the targets in a new window instead of the focused one
+    pub new_window: bool,
+    /// Theme of the window (overrides preferences)
+    pub theme: Option<Theme>,
+    /// Open the window with the sidebar closed (overrides preferences)
+    pub no_sidebar: bool,
+    /// Run as a separate process instead of forwarding to a running instance
+    #[serde(skip)]
+    pub standalone: bool,
+}
+
+impl CliArgs {
+    /// Check whether there is anything to open or apply
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+            && self.stdin.is_none()
+            && !self.read_stdin
+            && !self.new_window
+            && self.theme.is_none()
+            && !self.no_sidebar
+    }
+
+    /// Read the Markdown requested with `-`, resolving its links against `cwd`
+    pub fn read_stdin_from(&mut self, mut reader: impl Read, cwd: &Path) -> std::io::Result<()> {
+        if !std::mem::take(&mut self.read_stdin) {
+            return Ok(());
+        }
+        let mut markdown = String::new();
+        reader.read_to_string(&mut markdown)?;
+        self.stdin = Some(StdinDocument {
+            markdown,
+            base_dir: cwd.to_path_buf(),
+        });
+        Ok(())
+    }
+
+    /// Apply the window options to the parameters of a new window
+    pub fn apply_to(&self, params: &mut CreateMainWindowConfigParams) {
+        if let Some(theme) = self.theme {
+            params.theme = theme;
+        }
+        if self.no_sidebar {
+            params.sidebar_open = false;
+        }
+    }
+
+    /// Remove targets that do not exist, returning their paths
+    pub fn take_missing_targets(&mut self) -> Vec<PathBuf> {
+        let (existing, missing) = std::mem::take(&mut self.targets)
+            .into_iter()
+            .partition(|target| target.path.exists());
+        self.targets = existing;
+        missing.into_iter().map(|target| target.path).collect()
+    }
+}
+
+/// Arguments of the headless `render` subcommand
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderArgs {
+    /// Markdown file or directory to render
+    pub input: PathBuf,
+    /// Output file or directory (standard output if `None`)
+    pub output: Option<PathBuf>,
+    pub theme: Theme,
+}
+
+/// Parse command-line arguments (without the program name)
+///
+/// Relative paths are resolved against `cwd`.
+pub fn parse_args<I, S>(args: I, cwd: &Path) -> Result<CliCommand, CliError>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    let mut cli_args = CliArgs::default();
+    let mut args = args.into_iter().map(Into::into).peekable();
+    let mut only_paths = false;
+
+    // A subcommand is only recognized first (use `./render` to open such a file)
+    if args.peek().is_some_and(|arg| arg == "render") {
+        args.next();
+        return parse_render_args(args, cwd);
+    }
+
+    while let Some(arg) = args.next() {
+        if arg == "-" && !only_paths {
+            cli_args.read_stdin = true;
+            continue;
+        }
+        if only_paths || !arg.starts_with('-') {
+            cli_args.targets.push(parse_target(&arg, cwd));
+            continue;
+        }
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        match name.as_str() {
+            "--" => only_paths = true,
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "-V" | "--version" => return Ok(CliCommand::Version),
+            "--new-window" => cli_args.new_window = true,
+            "--no-sidebar" => cli_args.no_sidebar = true,
+            "--standalone" => cli_args.standalone = true,
+            "--theme" => {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| CliError::MissingValue(name.clone()))?;
+                cli_args.theme = Some(parse_theme(&value)?);
+            }
+            // macOS passes a process serial number when launched from Finder
+            _ if arg.starts_with("-psn_") => {}
+            _ => return Err(CliError::UnknownOption(arg)),
+        }
+    }
+    Ok(CliCommand::Open(cli_args))
+}
+
+fn parse_render_args(
+    mut args: impl Iterator<Item = String>,
+    cwd: &Path,
+) -> Result<CliCommand, CliError> {
+    let mut paths = Vec::new();
+    let mut output = None;
+    let mut theme = Theme::Light;
+    let mut only_paths = false;
+
+    while let Some(arg) = args.next() {
+        if only_paths || !arg.starts_with('-') || arg == "-" {
+            paths.push(cwd.join(arg));
+            continue;
+        }
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        let mut value = || {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError::MissingValue(name.clone()))
+        };
+        match name.as_str() {
+            "--" => only_paths = true,
+            "-h" | "--help" => return Ok(CliCommand::Help),
+            "-o" | "--output" => output = Some(cwd.join(value()?)),
+            "--theme" => theme = parse_theme(&value()?)?,
+            _ => return Err(CliError::UnknownOption(arg)),
+        }
+    }
+
+    let [input]: [PathBuf; 1] = paths.try_into().map_err(|_| CliError::RenderPath)?;
+    if input.is_dir() && output.is_none() {
+        return Err(CliError::RenderOutput);
+    }
+    Ok(CliCommand::Render(RenderArgs {
+        input,
+        output,
+        theme,
+    }))
+}
+
+fn parse_theme(value: &str) -> Result<Theme, CliError> {
+    match value.to_ascii_lowercase().as_str() {
+        "auto" => Ok(Theme::Auto),
+        "light" => Ok(Theme::Light),
+        "dark" => Ok(Theme::Dark),
+        _ => Err(CliError::InvalidTheme(value.to_string())),
+    }
+}
+
+/// Parse a path argument, splitting a heading or line suffix off existing files
+///
+/// A path that exists as written is never split, so file names containing
+/// `#` or `:` still open.
+fn parse_target(arg: &str, cwd: &Path) -> OpenTarget {
+    let resolve = |path: &str| {
+        let path = cwd.join(path);
+        path.canonicalize().unwrap_or(path)
+    };
+
+    let path = resolve(arg);
+    if path.exists() {
+        return OpenTarget { path, anchor: None };
+    }
+    anchor_candidates(arg)
+        .into_iter()
+        .map(|(file, anchor)| (resolve(file), anchor))
+        .find(|(file, _)| file.is_file())
+        .map(|(path, anchor)| OpenTarget {
+            path,
+            anchor: Some(anchor),
+        })
+        .unwrap_or(OpenTarget { path, anchor: None })
+}
+
+/// List the ways an argument can be split into a file and an anchor
+fn anchor_candidates(arg: &str) -> Vec<(&str, String)> {
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let mut candidates = Vec::new();
+
+    if let Some((file, fragment)) = arg.rsplit_once('#') {
+        if !fragment.is_empty() {
+            candidates.push((file, fragment.to_string()));
+        }
+    }
+    if let Some((file, suffix)) = arg.rsplit_once(':') {
+        if is_number(suffix) {
+            // `file:line:column` as printed by compilers and linters
+            if let Some((inner_file, line)) = file.rsplit_once(':') {
+                if is_number(line) {
+                    candidates.push((inner_file, format!("L{line}")));
+                }
+            }
+            candidates.push((file, format!("L{suffix}")));
+        } else if let Some((start, end)) = suffix.split_once('-') {
+            if is_number(start) && is_number(end) {
+                candidates.push((file, format!("L{start}-L{end}")));
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn open(args: &[&str], cwd: &Path) -> CliArgs {
+        match parse_args(args.iter().copied(), cwd) {
+            Ok(CliCommand::Open(args)) => args,
+            other => panic!("unexpected parse result: {other:?}"),
+        }
+    }
+
+    fn setup() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        std::fs::create_dir(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("README.md"), "# Readme").unwrap();
+        std::fs::write(dir.join("main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.join("notes#1.md"), "# Notes").unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_parse_args_paths() {
+        let temp_dir = setup();
+        let dir = temp_dir.path().canonicalize().unwrap();
+        let args = open(&["README.md", "docs", "notes#1.md"], &dir);
+
+        assert_eq!(
+            args.targets,
+            vec![
+                OpenTarget {
+                    path: dir.join("README.md"),
+                    anchor: None
+                },
+                OpenTarget {
+                    path: dir.join("docs"),
+                    anchor: None
+                },
+                OpenTarget {
+                    path: dir.join("notes#1.md"),
+                    anchor: None
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_anchors() {
+        let temp_dir = setup();
+        let dir = temp_dir.path().canonicalize().unwrap();
+        let anchors: Vec<_> = open(
+            &[
+                "README.md#installation",
+                "main.rs:120",
+                "main.rs:120-140",
+                "main.rs:120:5",
+            ],
+            &dir,
+        )
+        .targets
+        .into_iter()
+        .map(|target| (target.path, target.anchor))
+        .collect();
+
+        assert_eq!(
+            anchors,
+            vec![
+                (dir.join("README.md"), Some("installation".to_string())),
+                (dir.join("main.rs"), Some("L120".to_string())),
+                (dir.join("main.rs"), Some("L120-L140".to_string())),
+                (dir.join("main.rs"), Some("L120".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_args_options() {
+        let temp_dir = setup();
+        let args = open(
+            &[
+                "--new-window",
+                "--theme",
+                "dark",
+                "--no-sidebar",
+                "-psn_0_12345",
+            ],
+            temp_dir.path(),
+        );
+        assert!(args.new_window);
+        assert!(args.no_sidebar);
+        assert_eq!(args.theme, Some(Theme::Dark));
+        assert!(args.targets.is_empty());
+
+        let args = open(&["--theme=Light", "--", "--new-window"], temp_dir.path());
+        assert_eq!(args.theme, Some(Theme::Light));
+        assert!(!args.standalone);
+        assert!(!args.new_window);
+        assert_eq!(args.targets.len(), 1);
+
+        assert!(open(&[], temp_dir.path()).is_empty());
+        let args = open(&["--standalone"], temp_dir.path());
+        assert!(args.standalone);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        let cwd = Path::new("/");
+        assert_eq!(parse_args(["--help"], cwd), Ok(CliCommand::Help));
+        assert_eq!(parse_args(["-V"], cwd), Ok(CliCommand::Version));
+        assert_eq!(
+            parse_args(["--verbose"], cwd),
+            Err(CliError::UnknownOption("--verbose".to_string()))
+        );
+        assert_eq!(
+            parse_args(["--theme"], cwd),
+            Err(CliError::MissingValue("--theme".to_string()))
+        );
+        assert_eq!(
+            parse_args(["--theme", "blue"], cwd),
+            Err(CliError::InvalidTheme("blue".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_stdin() {
+        let temp_dir = setup();
+        let dir = temp_dir.path().canonicalize().unwrap();
+        let mut args = open(&["-", "README.md"], &dir);
+        assert!(args.read_stdin);
+        assert!(!args.is_empty());
+        assert_eq!(args.targets.len(), 1);
+
+        args.read_stdin_from("# Piped".as_bytes(), &dir).unwrap();
+        assert!(!args.read_stdin);
+        assert_eq!(
+            args.stdin,
+            Some(StdinDocument {
+                markdown: "# Piped".to_string(),
+                base_dir: dir.clone(),
+            })
+        );
+
+        // Reading again is a no-op, and a `-` after `--` is a file name
+        args.read_stdin_from("ignored".as_bytes(), &dir).unwrap();
+        assert_eq!(args.stdin.unwrap().markdown, "# Piped");
+        let args = open(&["--", "-"], &dir);
+        assert!(!args.read_stdin);
+        assert_eq!(args.targets[0].path, dir.join("-"));
+    }
+
+    #[test]
+    fn test_parse_render_args() {
+        let temp_dir = setup();
+        let dir = temp_dir.path();
+        assert_eq!(
+            parse_args(["render", "README.md"], dir),
+            Ok(CliCommand::Render(RenderArgs {
+                input: dir.join("README.md"),
+                output: None,
+                theme: Theme::Light,
+            }))
+        );
+        assert_eq!(
+            parse_args(["render", "--theme=dark", "docs", "-o", "site"], dir),
+            Ok(CliCommand::Render(RenderArgs {
+                input: dir.join("docs"),
+                output: Some(dir.join("site")),
+                theme: Theme::Dark,
+            }))
+        );
+
+        assert_eq!(parse_args(["render"], dir), Err(CliError::RenderPath));
+        assert_eq!(
+            parse_args(["render", "README.md", "main.rs"], dir),
+            Err(CliError::RenderPath)
+        );
+        assert_eq!(
+            parse_args(["render", "docs"], dir),
+            Err(CliError::RenderOutput)
+        );
+        assert_eq!(
+            parse_args(["render", "README.md", "--new-window"], dir),
+            Err(CliError::UnknownOption("--new-window".to_string()))
+        );
+        // Only the first argument is a subcommand
+        assert_eq!(open(&["README.md", "render"], dir).targets.len(), 2);
+    }
+
+    #[test]
+    fn test_take_missing_targets() {
+        let temp_dir = setup();
+        let dir = temp_dir.path().canonicalize().unwrap();
+        let mut args = open(&["README.md", "missing.md", "missing.md:10"], &dir);
+        let missing = args.take_missing_targets();
+
+        assert_eq!(args.targets.len(), 1);
+        assert_eq!(
+            missing,
+            vec![dir.join("missing.md"), dir.join("missing.md:10")]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 pub mod app;
 pub mod content;
 pub mod header;
+pub mod history_menu;
 pub mod icon;
 pub mod main_app;
 pub mod mermaid_window;
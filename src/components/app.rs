@@ -1,3 +1,4 @@
+use dioxus::core::Task;
 use dioxus::desktop::tao::dpi::{LogicalPosition, LogicalSize};
 use dioxus::desktop::tao::event::{Event as TaoEvent, WindowEvent};
 use dioxus::desktop::{use_muda_event_handler, use_wry_event_handler, window};
@@ -5,19 +6,30 @@ use dioxus::document;
 use dioxus::html::HasFileData;
 use dioxus::prelude::*;
 use dioxus_core::use_drop;
-use std::path::PathBuf;
+use std::cell::Cell;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::time::Duration;
 
-use super::content::Content;
+use super::content::{scroll_to_anchor, Content};
 use super::header::Header;
 use super::icon::{Icon, IconName};
+use super::main_app::directory_readme;
 use super::sidebar::Sidebar;
 use super::tab_bar::TabBar;
 use crate::assets::MAIN_SCRIPT;
-use crate::events::{DIRECTORY_OPEN_BROADCAST, FILE_OPEN_BROADCAST};
+use crate::config::{SidebarPanel, CONFIG};
+use crate::events::{DIRECTORY_OPEN_BROADCAST, FILE_OPEN_BROADCAST, INLINE_OPEN_BROADCAST};
 use crate::menu;
-use crate::state::{AppState, PersistedState, Tab, LAST_FOCUSED_STATE};
+use crate::state::{
+    remove_session_window, update_session_window, AppState, ClosedTabs, PersistedState,
+    SessionWindow, Tab, LAST_FOCUSED_STATE,
+};
 use crate::theme::Theme;
+use crate::utils::dialog::show_error;
+use crate::utils::file::most_recently_modified;
+use crate::watcher::{WatcherError, FILE_WATCHER};
 
 const WINDOW_METRICS_DEBOUNCE_MS: u64 = 200;
 
@@ -48,12 +60,17 @@ impl DebouncedMetricsUpdater {
 
 #[component]
 pub fn App(
-    tab: Tab,           // Initial tab (always provided, preserves history)
+    tab: Tab, // Initial tab (always provided, preserves history)
+    #[props(default)] additional_tabs: Vec<Tab>, // Further tabs opened in the background (e.g. from the command line)
+    #[props(default)] active_tab: usize, // Index of the active tab (when reopening a window)
+    #[props(default)] closed_tabs: ClosedTabs, // Recently closed tabs (when reopening a window)
+    #[props(default)] expanded_dirs: HashSet<PathBuf>, // Expanded sidebar directories (when restoring a window)
     directory: PathBuf, // Directory (resolved in create_new_main_window)
     theme: Theme,       // The enum: Auto/Light/Dark
     sidebar_open: bool,
     sidebar_width: f64,
     sidebar_show_all_files: bool,
+    sidebar_panel: SidebarPanel,
 ) -> Element {
     // Initialize application state with the provided tab
     let mut state = use_context_provider(|| {
@@ -61,6 +78,15 @@ pub fn App(
 
         // Initialize with provided tab (preserves history)
         app_state.tabs.write()[0] = tab;
+        app_state.tabs.write().extend(additional_tabs);
+        // Tabs moved or reopened into this window scroll back to where they were
+        app_state
+            .tabs
+            .write()
+            .iter_mut()
+            .for_each(Tab::restore_scroll);
+        app_state.switch_to_tab(active_tab);
+        *app_state.closed_tabs.write() = closed_tabs;
 
         // Apply initial directory from params (resolved in create_new_main_window)
         *app_state.directory.write() = Some(directory.clone());
@@ -76,11 +102,14 @@ pub fn App(
             sidebar.open = sidebar_open;
             sidebar.width = sidebar_width;
             sidebar.show_all_files = sidebar_show_all_files;
+            sidebar.panel = sidebar_panel;
+            sidebar.expanded_dirs = expanded_dirs;
             // Update last focused state for "Last Focused" behavior
             let mut state = LAST_FOCUSED_STATE.write();
             state.sidebar_open = sidebar_open;
             state.sidebar_width = sidebar_width;
             state.sidebar_show_all_files = sidebar_show_all_files;
+            state.sidebar_panel = sidebar_panel;
         }
         let metrics = crate::window::metrics::capture_window_metrics(&window().window);
         *app_state.position.write() = LogicalPosition::new(metrics.position.x, metrics.position.y);
@@ -123,6 +152,11 @@ pub fn App(
         menu::handle_menu_event_with_state(event, &mut state);
     });
 
+    // Keep this window in the session restored on the next startup
+    use_effect(move || {
+        update_session_window(window().id(), SessionWindow::from(&state));
+    });
+
     // Handle window events
     use_wry_event_handler(move |event, _| match event {
         TaoEvent::WindowEvent {
@@ -155,22 +189,44 @@ pub fn App(
                 );
             }
         }
+        // The first window only hides when closed, so it leaves the session here
+        TaoEvent::WindowEvent {
+            event: WindowEvent::CloseRequested,
+            window_id,
+            ..
+        } if window_id == &window().id() => {
+            remove_session_window(*window_id);
+        }
+        // ...and comes back when it is shown again
+        TaoEvent::WindowEvent {
+            event: WindowEvent::Focused(true),
+            window_id,
+            ..
+        } if window_id == &window().id() => {
+            update_session_window(*window_id, SessionWindow::from(&state));
+        }
         _ => {}
     });
 
     // Listen for file open broadcasts from background process
     setup_file_open_listener(state);
 
+    // Listen for inline markdown broadcasts from background process
+    setup_inline_open_listener(state);
+
     // Listen for directory open broadcasts from background process
     setup_directory_open_listener(state);
 
+    // Open the Markdown files changed in the root directory while following it
+    setup_directory_follower(state);
+
     // Update window title when active tab changes
     use_effect(move || {
         let active_index = *state.active_tab.read();
         let tabs = state.tabs.read();
 
         if let Some(tab) = tabs.get(active_index) {
-            let title = crate::utils::window_title::generate_window_title(&tab.content);
+            let title = crate::utils::window_title::generate_window_title(tab);
             window().set_title(&title);
         }
     });
@@ -202,7 +258,9 @@ pub fn App(
             if can_accept {
                 // Phase 2a: Commit - insert tab and send Ack
                 let tabs_len = state.tabs.read().len();
-                let insert_index = state.insert_tab(request.tab.clone(), tabs_len);
+                let mut tab = request.tab.clone();
+                tab.restore_scroll();
+                let insert_index = state.insert_tab(tab, tabs_len);
                 state.switch_to_tab(insert_index);
 
                 // Focus this window after receiving the tab
@@ -247,6 +305,12 @@ pub fn App(
         }
         persisted.save();
 
+        // Closed windows are not restored on the next startup
+        remove_session_window(window().id());
+
+        // Remember the window for "Reopen Closed Window"
+        crate::window::record_closed_window(&state, persisted);
+
         // Close child windows
         crate::window::close_child_windows_for_parent(window().id());
     });
@@ -357,11 +421,37 @@ fn setup_file_open_listener(mut state: AppState) {
     use_future(move || async move {
         let mut rx = FILE_OPEN_BROADCAST.subscribe();
 
-        while let Ok(file) = rx.recv().await {
-            // Only handle in the focused window
-            if window().is_focused() {
-                tracing::info!("Opening file from broadcast: {:?}", file);
-                state.open_file(file);
+        while let Ok((file, anchor)) = rx.recv().await {
+            // Only handle in the focused window (or the last focused one while the app is in the background)
+            if crate::window::is_last_focused_main_window(window().id()) {
+                tracing::info!(
+                    "Opening file from broadcast: {:?} (anchor: {:?})",
+                    file,
+                    anchor
+                );
+                let is_current_file = state
+                    .current_tab()
+                    .is_some_and(|tab| tab.file() == Some(file.as_path()));
+                match anchor {
+                    // The document is already rendered, so scroll right away
+                    Some(anchor) if is_current_file => scroll_to_anchor(&anchor),
+                    anchor => state.open_file_with_anchor(file, anchor),
+                }
+            }
+        }
+    });
+}
+
+/// Setup listener for inline markdown broadcasts from the background process
+fn setup_inline_open_listener(mut state: AppState) {
+    use_future(move || async move {
+        let mut rx = INLINE_OPEN_BROADCAST.subscribe();
+
+        while let Ok(document) = rx.recv().await {
+            // Only handle in the focused window (or the last focused one while the app is in the background)
+            if crate::window::is_last_focused_main_window(window().id()) {
+                tracing::info!("Opening inline markdown from {:?}", document.source);
+                state.open_inline_document(document);
             }
         }
     });
@@ -373,19 +463,87 @@ fn setup_directory_open_listener(mut state: AppState) {
         let mut rx = DIRECTORY_OPEN_BROADCAST.subscribe();
 
         while let Ok(dir) = rx.recv().await {
-            // Only handle in the focused window
-            if window().is_focused() {
+            // Only handle in the focused window (or the last focused one while the app is in the background)
+            if crate::window::is_last_focused_main_window(window().id()) {
                 tracing::info!("Opening directory from broadcast: {:?}", dir);
                 state.set_root_directory(dir.clone());
                 // Optionally show the sidebar if it's hidden
                 if !state.sidebar.read().open {
                     state.toggle_sidebar();
                 }
+                if let Some(readme) = directory_readme(&dir) {
+                    state.open_file(readme);
+                }
             }
         }
     });
 }
 
+/// Setup the watch opening the most recently changed Markdown file under the root directory
+fn setup_directory_follower(state: AppState) {
+    let follow = use_memo(move || state.sidebar.read().follow);
+    let follow_task = use_hook(|| Rc::new(Cell::new(None::<Task>)));
+
+    use_effect(move || {
+        let root = state.directory.read().clone().filter(|_| follow());
+        let task = root.map(|root| spawn(follow_directory(root, state)));
+
+        // Dropping the receiver of the previous directory ends its watch
+        if let Some(previous) = follow_task.replace(task) {
+            previous.cancel();
+        }
+    });
+}
+
+/// Watch a directory recursively and open the Markdown files created or modified in it
+async fn follow_directory(root: PathBuf, mut state: AppState) {
+    let mut watcher = match FILE_WATCHER.watch_tree(root.clone()).await {
+        Ok(watcher) => watcher,
+        Err(e @ WatcherError::TreeTooLarge(_)) => {
+            // Watching a directory as large as the home directory would cost too much
+            state.sidebar.write().follow = false;
+            show_error(
+                "Cannot follow this directory",
+                format!("{e}. Choose a smaller root directory or exclude directories in Preferences > Auto-Reload."),
+            )
+            .await;
+            return;
+        }
+        Err(e) => {
+            tracing::error!("Failed to follow directory {:?}: {:?}", root, e);
+            return;
+        }
+    };
+
+    while let Some(changed) = watcher.recv().await {
+        let (candidates, dedicated_tab) = {
+            let config = CONFIG.read();
+            let candidates: Vec<PathBuf> = changed
+                .into_iter()
+                .filter(|path| config.follow.follows(path))
+                .filter(|path| !is_excluded(path, &root, |p| config.watcher.excludes(p)))
+                .collect();
+            (candidates, config.follow.dedicated_tab)
+        };
+        // Removed and renamed-away files don't exist anymore and are skipped
+        if let Some(file) = most_recently_modified(&candidates) {
+            tracing::info!("Following changed file: {:?}", file);
+            state.follow_file(file, dedicated_tab);
+        }
+    }
+
+    // The watch ends by itself when the directory grew too large to be watched
+    tracing::warn!("Stopped following directory: {:?}", root);
+    state.sidebar.write().follow = false;
+}
+
+/// Check whether a path or one of its directories under root is excluded
+fn is_excluded(path: &Path, root: &Path, excludes: impl Fn(&Path) -> bool) -> bool {
+    path.ancestors()
+        .take_while(|ancestor| ancestor.starts_with(root) && *ancestor != root)
+        .any(excludes)
+}
+
 #[component]
 fn DragDropOverlay() -> Element {
     rsx! {
@@ -1,26 +1,37 @@
 mod file_error_view;
 mod file_viewer;
+mod history_view;
+mod html_export;
 mod inline_viewer;
 mod no_file_view;
 mod preferences_view;
+mod print;
 
 use dioxus::prelude::*;
 
 use crate::state::{AppState, TabContent};
 use file_error_view::FileErrorView;
 use file_viewer::FileViewer;
+use history_view::HistoryView;
 use inline_viewer::InlineViewer;
 use no_file_view::NoFileView;
 use preferences_view::PreferencesView;
 
 // Re-export for menu system
+pub use html_export::export_current_tab_as_html;
 pub use preferences_view::set_preferences_tab_to_about;
+pub use print::{print_current_tab, PrintDestination};
+
+// Re-export for the sidebar outline
+pub(crate) use file_viewer::scroll_to_anchor;
 
 #[component]
 pub fn Content() -> Element {
     let state = use_context::<AppState>();
     let zoom_level = state.zoom_level;
 
+    use_scroll_tracker(state);
+
     let current_tab = state.current_tab();
     let content = current_tab.map(|tab| tab.content);
 
@@ -37,8 +48,8 @@ pub fn Content() -> Element {
                 Some(TabContent::File(file)) => {
                     rsx! { FileViewer { file } }
                 },
-                Some(TabContent::Inline(markdown)) => {
-                    rsx! { InlineViewer { markdown } }
+                Some(TabContent::Inline(document)) => {
+                    rsx! { InlineViewer { document } }
                 },
                 Some(TabContent::FileError(file, error)) => {
                     let filename = file
@@ -51,8 +62,73 @@ pub fn Content() -> Element {
                 Some(TabContent::Preferences) => {
                     rsx! { PreferencesView {} }
                 },
+                Some(TabContent::History) => {
+                    rsx! { HistoryView {} }
+                },
                 _ => rsx! { NoFileView {} },
             }
         }
     }
 }
+
+/// Hook to remember the scroll offset of the active tab (to restore it when reopened)
+///
+/// The webview reports when scrolling starts and the offset once it stops. An
+/// offset is dropped if another tab or document was shown in between, as the
+/// offset of the document left was already captured when leaving it.
+fn use_scroll_tracker(mut state: AppState) {
+    use_hook(|| {
+        let mut eval = document::eval(indoc::indoc! {r#"
+            let timer = null;
+            document.addEventListener(
+                "scroll",
+                (event) => {
+                    const target = event.target;
+                    if (!(target instanceof Element) || !target.classList.contains("content")) {
+                        return;
+                    }
+                    if (timer === null) {
+                        dioxus.send(null);
+                    }
+                    clearTimeout(timer);
+                    timer = setTimeout(() => {
+                        timer = null;
+                        dioxus.send(target.scrollTop);
+                    }, 150);
+                },
+                true,
+            );
+        "#});
+        spawn(async move {
+            let shown = move || {
+                let active_tab = *state.active_tab.peek();
+                state.current_tab().map(|tab| (active_tab, tab.content))
+            };
+            let mut scrolled = None;
+            while let Ok(scroll_top) = eval.recv::<Option<f64>>().await {
+                match scroll_top {
+                    None => scrolled = shown(),
+                    Some(scroll_top) => {
+                        if scrolled
+                            .take()
+                            .is_some_and(|scrolled| Some(scrolled) == shown())
+                        {
+                            state.set_current_tab_scroll(scroll_top);
+                        }
+                    }
+                }
+            }
+        });
+    });
+}
+
+/// Read the scroll offset of the document from the webview into the current tab
+///
+/// Scroll reports are debounced, so this catches up right before leaving the document.
+pub(crate) async fn capture_scroll_top(mut state: AppState) {
+    let js = r#"return document.querySelector(".content")?.scrollTop ?? 0;"#;
+    match document::eval(js).join::<f64>().await {
+        Ok(scroll_top) => state.set_current_tab_scroll(scroll_top),
+        Err(e) => tracing::debug!(?e, "Failed to read the scroll offset"),
+    }
+}
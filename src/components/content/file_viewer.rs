@@ -1,17 +1,34 @@
+use base64::{engine::general_purpose, Engine as _};
+use dioxus::core::Task;
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
-use crate::markdown::render_to_html;
-use crate::state::{AppState, TabContent};
-use crate::utils::file::is_markdown_file;
-use crate::watcher::FILE_WATCHER;
+use super::capture_scroll_top;
+use crate::components::icon::{Icon, IconName};
+use crate::config::{SidebarPanel, CONFIG};
+use crate::markdown::{
+    self, get_mime_type, render_markdown_with_options, resolve_local_path, too_large_image_html,
+    RenderOptions,
+};
+use crate::source::{detect_language, parse_line_range, render_source_html};
+use crate::state::{record_recent_visit, AppState, Tab, TabContent};
+use crate::utils::dialog::show_error;
+use crate::utils::file::{
+    classify_link_target, find_directory_index, is_image_file, is_markdown_file, LinkTarget,
+};
+use crate::watcher::{FileEvent, FILE_WATCHER};
 
 /// Data structure for markdown link clicks from JavaScript
 #[derive(Serialize, Deserialize)]
 struct LinkClickData {
     path: String,
     button: u32,
+    /// Scroll offset of the document at the time of the click
+    #[serde(default, rename = "scrollTop")]
+    scroll_top: f64,
 }
 
 /// Mouse button constants
@@ -20,19 +37,48 @@ const MIDDLE_CLICK: u32 = 1;
 
 #[component]
 pub fn FileViewer(file: PathBuf) -> Element {
-    let state = use_context::<AppState>();
+    let mut state = use_context::<AppState>();
     let html = use_signal(String::new);
     let reload_trigger = use_signal(|| 0usize);
+    // Local files embedded in the rendered document (e.g. images)
+    let resources = use_signal(Vec::<PathBuf>::new);
+    // The tab showing this viewer, so that the banner acts on it
+    let owner = *state.active_tab.read();
+    let is_deleted = state.current_tab().is_some_and(|tab| tab.file_deleted);
 
     // Setup component hooks
-    use_file_loader(file.clone(), html, reload_trigger, state);
-    use_file_watcher(file.clone(), reload_trigger);
+    use_file_loader(file.clone(), html, resources, reload_trigger, state);
+    use_outline_cleanup(state);
+    use_file_watcher(file.clone(), resources, reload_trigger, state);
     use_link_click_handler(file, state);
     use_mermaid_window_handler();
 
     rsx! {
         div {
             class: "markdown-viewer",
+            if is_deleted {
+                div {
+                    class: "file-deleted-banner",
+                    Icon { name: IconName::AlertTriangle, size: 16 }
+                    span { class: "file-deleted-message", "This file was deleted." }
+                    button {
+                        class: "file-deleted-button",
+                        onclick: move |_| {
+                            if let Some(tab) = state.tabs.write().get_mut(owner) {
+                                tab.file_deleted = false;
+                            }
+                        },
+                        "Keep Open"
+                    }
+                    button {
+                        class: "file-deleted-button",
+                        onclick: move |_| {
+                            state.close_tab(owner);
+                        },
+                        "Close Tab"
+                    }
+                }
+            }
             article {
                 class: "markdown-body",
                 dangerous_inner_html: "{html}"
@@ -45,27 +91,65 @@ pub fn FileViewer(file: PathBuf) -> Element {
 fn use_file_loader(
     file: PathBuf,
     html: Signal<String>,
+    resources: Signal<Vec<PathBuf>>,
     reload_trigger: Signal<usize>,
     mut state: AppState,
 ) {
     use_effect(use_reactive!(|file, reload_trigger| {
         let mut html = html;
+        let mut resources = resources;
         let _ = reload_trigger();
         let file = file.clone();
 
         spawn(async move {
             tracing::info!("Loading and rendering file: {:?}", &file);
 
+            // Images are shown as-is in the image viewer
+            if is_image_file(&file) {
+                let max_size = render_options_for(&file, &state).max_image_size;
+                match read_image(&file, max_size).await {
+                    Ok(data) => {
+                        html.set(render_image_html(&file, data));
+                        state.outline.set(Vec::new());
+                        set_resources(&mut resources, Vec::new());
+                        state.set_current_tab_title(None);
+                        record_visit(&mut state, &file, None);
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to read image {:?}: {}", file, e);
+                        let error_msg = format!("{:?}", e);
+                        let file_clone = file.clone();
+                        state.update_current_tab(move |tab| {
+                            tab.content = TabContent::FileError(file_clone, error_msg);
+                            tab.title = None;
+                        });
+                        html.set(String::new());
+                        state.outline.set(Vec::new());
+                        set_resources(&mut resources, Vec::new());
+                    }
+                }
+                return;
+            }
+
             // Try to read as string (UTF-8 text file)
             match tokio::fs::read_to_string(file.as_path()).await {
                 Ok(content) => {
                     // Check if file has markdown extension
                     if is_markdown_file(&file) {
                         // Render as markdown
-                        match render_to_html(&content, &file) {
+                        let options = render_options_for(&file, &state);
+                        match render_markdown_with_options(&content, &file, &options) {
                             Ok(rendered) => {
-                                html.set(rendered);
+                                let title = rendered.front_matter_title();
+                                state.set_current_tab_title(title.clone());
+                                record_visit(&mut state, &file, title);
+                                let show_front_matter = CONFIG.read().markdown.show_front_matter;
+                                html.set(rendered.document_html(show_front_matter));
+                                set_resources(&mut resources, rendered.resources);
+                                state.outline.set(rendered.headings);
                                 tracing::trace!("Rendered as Markdown: {:?}", &file);
+                                // Scroll to the anchor requested by the link that opened this file
+                                apply_pending_scroll(&mut state);
                             }
                             Err(e) => {
                                 // Markdown parsing failed, render as plain text
@@ -80,17 +164,27 @@ fn use_file_loader(
                                     escaped_content
                                 );
                                 html.set(plain_html);
+                                state.outline.set(Vec::new());
+                                set_resources(&mut resources, Vec::new());
+                                state.set_current_tab_title(None);
+                                record_visit(&mut state, &file, None);
                             }
                         }
                     } else {
-                        // Non-markdown file, render as plain text directly
-                        tracing::info!("Rendering non-markdown file as plain text: {:?}", &file);
-                        let escaped_content = html_escape::encode_text(&content);
-                        let plain_html = format!(
-                            r#"<pre class="plain-text-viewer">{}</pre>"#,
-                            escaped_content
+                        // Non-markdown file, render as a highlighted source view
+                        let language = detect_language(&file, &content);
+                        tracing::info!(
+                            "Rendering non-markdown file as source ({:?}): {:?}",
+                            language,
+                            &file
                         );
-                        html.set(plain_html);
+                        html.set(render_source_html(&content, language));
+                        state.outline.set(Vec::new());
+                        set_resources(&mut resources, Vec::new());
+                        state.set_current_tab_title(None);
+                        record_visit(&mut state, &file, None);
+                        // Scroll to the lines requested by the link that opened this file
+                        apply_pending_scroll(&mut state);
                     }
                 }
                 Err(e) => {
@@ -102,57 +196,229 @@ fn use_file_loader(
                     let file_clone = file.clone();
                     state.update_current_tab(move |tab| {
                         tab.content = TabContent::FileError(file_clone, error_msg);
+                        tab.title = None;
                     });
                     html.set(String::new());
+                    state.outline.set(Vec::new());
+                    set_resources(&mut resources, Vec::new());
                 }
             }
         });
     }));
 }
 
-/// Hook to watch file for changes and trigger reload
-fn use_file_watcher(file: PathBuf, reload_trigger: Signal<usize>) {
+/// Remember the shown document in the recently viewed history
+///
+/// Only navigation counts as a visit; reloads just refresh the title.
+fn record_visit(state: &mut AppState, file: &Path, title: Option<String>) {
+    let is_new_visit = state.take_pending_visit();
+    record_recent_visit(file, title, is_new_visit);
+}
+
+/// Resolve the root for leading-slash links of a document
+///
+/// The configured root wins if it contains the path, then the enclosing Git
+/// repository, then the sidebar root directory.
+fn document_root_for(path: &Path, state: &AppState) -> Option<PathBuf> {
+    markdown::document_root_for(path, &CONFIG.read().markdown).or_else(|| {
+        state
+            .directory
+            .peek()
+            .clone()
+            .filter(|root| path.starts_with(root))
+    })
+}
+
+/// Build the render options of a Markdown file from the configuration
+pub(super) fn render_options_for(file: &Path, state: &AppState) -> RenderOptions {
+    let document_root = document_root_for(file, state);
+    RenderOptions::for_document(file, document_root, &CONFIG.read().markdown)
+}
+
+/// Read an image for the image viewer (`Err` with its size if above the size limit)
+async fn read_image(file: &Path, max_size: Option<u64>) -> std::io::Result<Result<Vec<u8>, u64>> {
+    let size = tokio::fs::metadata(file).await?.len();
+    if max_size.is_some_and(|max_size| size > max_size) {
+        return Ok(Err(size));
+    }
+    tokio::fs::read(file).await.map(Ok)
+}
+
+/// Build the HTML of the image viewer
+///
+/// Images above the size limit are not embedded, like in documents.
+fn render_image_html(file: &Path, data: Result<Vec<u8>, u64>) -> String {
+    let name = file
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let image = match data {
+        Ok(data) => format!(
+            r#"<img src="data:{};base64,{}" alt="{}" />"#,
+            get_mime_type(file),
+            general_purpose::STANDARD.encode(&data),
+            html_escape::encode_double_quoted_attribute(&name)
+        ),
+        Err(size) => too_large_image_html(&file.to_string_lossy(), size),
+    };
+    format!(r#"<div class="image-viewer">{}</div>"#, image)
+}
+
+/// Update the embedded resources, keeping their watch if they are the same
+fn set_resources(resources: &mut Signal<Vec<PathBuf>>, paths: Vec<PathBuf>) {
+    if *resources.peek() != paths {
+        resources.set(paths);
+    }
+}
+
+/// Hook to clear the outline when the viewer is unmounted
+fn use_outline_cleanup(mut state: AppState) {
+    use_drop(move || {
+        state.outline.set(Vec::new());
+    });
+}
+
+/// Hook to watch the file and follow what happens to it
+///
+/// Changes to the file or to the resources embedded in it reload the document,
+/// unless auto-reload is paused for the tab (it then reloads once resumed).
+/// A deleted file keeps its content and shows a banner, and a moved file is
+/// followed by every tab showing it.
+fn use_file_watcher(
+    file: PathBuf,
+    resources: Signal<Vec<PathBuf>>,
+    reload_trigger: Signal<usize>,
+    state: AppState,
+) {
+    let watch_task = use_hook(|| Rc::new(Cell::new(None::<Task>)));
+    let resources_task = use_hook(|| Rc::new(Cell::new(None::<Task>)));
+    let is_paused = use_memo(move || {
+        state
+            .current_tab()
+            .is_some_and(|tab| tab.auto_reload_paused)
+    });
+    let mut has_unseen_changes = use_signal(|| false);
+    let auto_reload = AutoReload {
+        state,
+        reload_trigger,
+        is_paused,
+        has_unseen_changes,
+    };
+
+    // Catch up with the changes made while auto-reload was paused
+    use_effect(move || {
+        if !is_paused() && *has_unseen_changes.peek() {
+            has_unseen_changes.set(false);
+            spawn(reload(state, reload_trigger));
+        }
+    });
+
     use_effect(use_reactive!(|file| {
-        let mut reload_trigger = reload_trigger;
+        let mut has_unseen_changes = has_unseen_changes;
+        let mut state = state;
         let file = file.clone();
+        has_unseen_changes.set(false);
 
-        spawn(async move {
-            let file_path = file.clone();
-            let mut watcher = match FILE_WATCHER.watch(file_path.clone()).await {
+        let task = spawn(async move {
+            let mut watcher = match FILE_WATCHER.watch(file.clone()).await {
                 Ok(watcher) => watcher,
                 Err(e) => {
-                    tracing::error!(
-                        "Failed to register file watcher for {:?}: {:?}",
-                        file_path,
-                        e
-                    );
+                    tracing::error!("Failed to register file watcher for {:?}: {:?}", file, e);
                     return;
                 }
             };
 
-            while watcher.recv().await.is_some() {
-                tracing::info!("File change detected, reloading: {:?}", file_path);
-                reload_trigger.set(reload_trigger() + 1);
+            while let Some(event) = watcher.recv().await {
+                match event {
+                    FileEvent::Modified => {
+                        tracing::info!("File change detected: {:?}", file);
+                        state.set_file_deleted(&file, false);
+                        auto_reload.reload().await;
+                    }
+                    FileEvent::Removed => {
+                        tracing::info!("File deleted: {:?}", file);
+                        state.set_file_deleted(&file, true);
+                    }
+                    FileEvent::Renamed(new_file) => {
+                        tracing::info!("File moved: {:?} -> {:?}", file, new_file);
+                        capture_scroll_top(state).await;
+                        state.retarget_tabs(&file, &new_file);
+                    }
+                }
             }
+        });
+
+        // Dropping the receiver of the previous file ends its watch
+        if let Some(previous) = watch_task.replace(Some(task)) {
+            previous.cancel();
+        }
+    }));
 
-            if let Err(e) = FILE_WATCHER.unwatch(file_path.clone()).await {
-                tracing::error!(
-                    "Failed to unregister file watcher for {:?}: {:?}",
-                    file_path,
-                    e
-                );
+    use_effect(move || {
+        let paths = resources.read().clone();
+        let task = spawn(async move {
+            if paths.is_empty() {
+                return;
+            }
+            let mut watcher = match FILE_WATCHER.watch_files(paths).await {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    tracing::error!("Failed to register resource watcher: {:?}", e);
+                    return;
+                }
+            };
+
+            while watcher.recv().await.is_some() {
+                // Resources regenerated together are reloaded once
+                while watcher.try_recv().is_ok() {}
+                tracing::info!("Embedded resource changed");
+                auto_reload.reload().await;
             }
         });
-    }));
+
+        if let Some(previous) = resources_task.replace(Some(task)) {
+            previous.cancel();
+        }
+    });
+}
+
+/// Reloads the document when the file or its resources change
+#[derive(Clone, Copy)]
+struct AutoReload {
+    state: AppState,
+    reload_trigger: Signal<usize>,
+    /// Whether auto-reload is paused for the tab
+    is_paused: Memo<bool>,
+    /// Whether changes were left unseen while auto-reload was paused
+    has_unseen_changes: Signal<bool>,
+}
+
+impl AutoReload {
+    /// Render the document again, or remember the change while auto-reload is paused
+    async fn reload(mut self) {
+        if *self.is_paused.peek() {
+            self.has_unseen_changes.set(true);
+        } else {
+            reload(self.state, self.reload_trigger).await;
+        }
+    }
+}
+
+/// Render the document again, scrolled back to where it was
+async fn reload(mut state: AppState, mut reload_trigger: Signal<usize>) {
+    capture_scroll_top(state).await;
+    state.update_current_tab(Tab::restore_scroll);
+    reload_trigger.set(reload_trigger() + 1);
 }
 
 /// Hook to setup JavaScript handler for markdown link clicks
-fn use_link_click_handler(file: PathBuf, state: AppState) {
+pub(super) fn use_link_click_handler(file: PathBuf, state: AppState) {
     use_effect(use_reactive!(|file| {
         let file = file.clone();
         let mut eval_provider = document::eval(indoc::indoc! {r#"
             window.handleMarkdownLinkClick = (path, button) => {
-                dioxus.send({ path, button });
+                const scrollTop = document.querySelector(".content")?.scrollTop ?? 0;
+                dioxus.send({ path, button, scrollTop });
             };
         "#});
 
@@ -173,27 +439,104 @@ fn use_link_click_handler(file: PathBuf, state: AppState) {
 
 /// Handle a markdown link click event
 fn handle_link_click(click_data: LinkClickData, base_dir: &Path, state: &mut AppState) {
-    let LinkClickData { path, button } = click_data;
+    let LinkClickData {
+        path,
+        button,
+        scroll_top,
+    } = click_data;
 
     tracing::info!("Markdown link clicked: {} (button: {})", path, button);
 
+    // Split off the fragment (e.g. "guide.md#install" -> "guide.md", "install")
+    let (path, anchor) = match path.split_once('#') {
+        Some((path, anchor)) => (path.to_string(), Some(anchor.to_string())),
+        None => (path, None),
+    };
+
+    // In-document link (e.g. "#install"): just scroll
+    if path.is_empty() {
+        if let Some(anchor) = anchor {
+            scroll_to_anchor(&anchor);
+        }
+        return;
+    }
+
     // Resolve and normalize the path
-    let target_path = base_dir.join(&path);
-    let Ok(canonical_path) = target_path.canonicalize() else {
+    let document_root = document_root_for(base_dir, state);
+    let target_path = resolve_local_path(base_dir, document_root.as_deref(), &path);
+    let Ok(mut canonical_path) = target_path.canonicalize() else {
         tracing::error!("Failed to resolve path: {:?}", target_path);
         return;
     };
 
-    tracing::info!("Opening file: {:?}", canonical_path);
+    // Directory links show the directory's README (or index) like GitHub
+    if canonical_path.is_dir() {
+        if let Some(index) = find_directory_index(&canonical_path) {
+            canonical_path = index;
+        }
+    }
+
+    // Decide how the target is opened
+    let external_extensions = CONFIG.read().markdown.external_open_extensions.clone();
+    match classify_link_target(&canonical_path, &external_extensions) {
+        LinkTarget::Viewable => {}
+        LinkTarget::Directory => {
+            // Directory without a README: browse it in the file explorer instead
+            tracing::info!("Opening directory in file explorer: {:?}", canonical_path);
+            open_directory_in_sidebar(canonical_path, state);
+            return;
+        }
+        LinkTarget::External => {
+            tracing::info!(
+                "Opening with system default application: {:?}",
+                canonical_path
+            );
+            if let Err(e) = open::that_detached(&canonical_path) {
+                tracing::error!("Failed to open {:?}: {}", canonical_path, e);
+            }
+            return;
+        }
+        LinkTarget::Unsupported => {
+            tracing::warn!(
+                "Cannot open {:?}: not a text or image file and its extension is not allowed to open externally",
+                canonical_path
+            );
+            let name = canonical_path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            spawn(show_error(
+                format!("Cannot open \"{name}\""),
+                "It is not a Markdown, text or image file. Add its extension to Preferences > Markdown > Open Externally to open it with the default application.",
+            ));
+            return;
+        }
+    }
+
+    tracing::info!("Opening file: {:?} (anchor: {:?})", canonical_path, anchor);
 
     match button {
         MIDDLE_CLICK => {
             // Open in new tab (always create a new tab for middle-click)
-            state.add_file_tab(canonical_path, true);
+            let mut tab = Tab::new(canonical_path);
+            tab.pending_anchor = anchor;
+            state.add_tab(tab, true);
         }
         LEFT_CLICK => {
-            // Navigate in current tab (in-tab navigation, no existing tab check)
-            state.navigate_to_file(canonical_path);
+            let is_current_file = state
+                .current_tab()
+                .is_some_and(|tab| tab.file() == Some(canonical_path.as_path()));
+            if is_current_file {
+                // Link to a section of the current file: no reload required
+                if let Some(anchor) = anchor {
+                    scroll_to_anchor(&anchor);
+                }
+            } else {
+                // Navigate in current tab (in-tab navigation, no existing tab check),
+                // remembering where this document was left for going back
+                state.set_current_tab_scroll(scroll_top);
+                state.navigate_to_file_with_anchor(canonical_path, anchor);
+            }
         }
         _ => {
             tracing::debug!("Ignoring click with button: {}", button);
@@ -201,6 +544,104 @@ fn handle_link_click(click_data: LinkClickData, base_dir: &Path, state: &mut App
     }
 }
 
+/// Show a linked directory as the root of the file explorer
+fn open_directory_in_sidebar(dir: PathBuf, state: &mut AppState) {
+    state.set_root_directory(dir);
+    state.set_sidebar_panel(SidebarPanel::Explorer);
+    if !state.sidebar.read().open {
+        state.toggle_sidebar();
+    }
+}
+
+/// Scroll to the pending anchor of the current tab, or else to its pending offset
+pub(super) fn apply_pending_scroll(state: &mut AppState) {
+    if let Some(anchor) = state.take_pending_anchor() {
+        scroll_to_anchor(&anchor);
+    } else if let Some(scroll_top) = state.take_pending_scroll() {
+        scroll_to_offset(scroll_top);
+    }
+}
+
+/// Scroll the document to the given offset
+///
+/// The offset may be out of reach until images and diagrams have been laid out,
+/// so scrolling waits for the document to grow for a few animation frames.
+fn scroll_to_offset(scroll_top: f64) {
+    spawn(async move {
+        let js = indoc::formatdoc! {r#"
+            (() => {{
+                const top = {scroll_top};
+                let attempts = 60;
+                const scroll = () => {{
+                    const content = document.querySelector(".content");
+                    if (!content) {{
+                        return;
+                    }}
+                    if (content.scrollHeight - content.clientHeight < top && attempts-- > 0) {{
+                        requestAnimationFrame(scroll);
+                        return;
+                    }}
+                    content.scrollTo(0, top);
+                }};
+                scroll();
+            }})();
+        "#};
+        let _ = document::eval(&js).await;
+    });
+}
+
+/// Scroll the rendered document to the element with the given anchor id
+///
+/// The element may not exist yet right after the HTML has been updated,
+/// so the lookup is retried for a few animation frames.
+/// Line anchors (`L10` or `L10-L20`) select the lines in the source view.
+pub(crate) fn scroll_to_anchor(anchor: &str) {
+    if let Some((start, end)) = parse_line_range(anchor) {
+        select_source_lines(start, end);
+        return;
+    }
+    let Ok(anchor) = serde_json::to_string(anchor) else {
+        return;
+    };
+    spawn(async move {
+        let js = indoc::formatdoc! {r#"
+            (() => {{
+                let id = {anchor};
+                try {{
+                    id = decodeURIComponent(id);
+                }} catch (_) {{}}
+                if (id === "" || id === "top") {{
+                    document.querySelector(".content")?.scrollTo(0, 0);
+                    return;
+                }}
+                let attempts = 60;
+                // Anchors written in raw HTML are prefixed like on GitHub
+                const find = (name) => document.getElementById(name) ?? document.getElementsByName(name)[0];
+                const scroll = () => {{
+                    const target = find(id) ?? find(`user-content-${{id}}`);
+                    if (target) {{
+                        target.scrollIntoView();
+                    }} else if (attempts-- > 0) {{
+                        requestAnimationFrame(scroll);
+                    }}
+                }};
+                scroll();
+            }})();
+        "#};
+        let _ = document::eval(&js).await;
+    });
+}
+
+/// Ask the source view to select and reveal a range of lines
+fn select_source_lines(start: usize, end: usize) {
+    spawn(async move {
+        let js = format!(
+            r#"document.dispatchEvent(new CustomEvent("arto:select-lines", {{ detail: {{ start: {start}, end: {end} }} }}));"#
+        );
+        let _ = document::eval(&js).await;
+    });
+}
+
 /// Hook to setup Mermaid window open handler
 fn use_mermaid_window_handler() {
     use_effect(|| {
@@ -0,0 +1,119 @@
+use dioxus::prelude::*;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::components::icon::{Icon, IconName};
+use crate::events::RECENTLY_VIEWED_CHANGED;
+use crate::state::{
+    clear_recently_viewed, remove_recent_visit, unix_now, AppState, RecentEntry, RECENTLY_VIEWED,
+};
+
+/// Searchable list of the documents recently viewed in any window
+#[component]
+pub fn HistoryView() -> Element {
+    let mut state = use_context::<AppState>();
+    let mut query = use_signal(String::new);
+    let mut revision = use_signal(|| 0usize);
+
+    // Refresh when a visit is recorded or the history is cleared in any window
+    use_hook(|| {
+        spawn(async move {
+            let mut rx = RECENTLY_VIEWED_CHANGED.subscribe();
+            // Missed notifications (lag) still mean the history changed
+            while let Ok(()) | Err(RecvError::Lagged(_)) = rx.recv().await {
+                revision += 1;
+            }
+        });
+    });
+
+    let _ = revision();
+    let now = unix_now();
+    let (entries, has_history): (Vec<RecentEntry>, bool) = {
+        let recent = RECENTLY_VIEWED.read();
+        let query = query.read();
+        let entries = recent
+            .entries()
+            .iter()
+            .filter(|entry| entry.matches(&query))
+            .cloned()
+            .collect();
+        (entries, !recent.entries().is_empty())
+    };
+
+    rsx! {
+        div {
+            class: "history-page",
+
+            div {
+                class: "history-page-header",
+                h2 { "History" }
+                input {
+                    class: "history-search",
+                    r#type: "search",
+                    placeholder: "Search history",
+                    value: "{query}",
+                    oninput: move |evt| query.set(evt.value()),
+                }
+                button {
+                    class: "history-clear-button",
+                    disabled: !has_history,
+                    onclick: move |_| clear_recently_viewed(),
+                    "Clear History"
+                }
+            }
+
+            if entries.is_empty() {
+                p {
+                    class: "history-empty",
+                    if has_history {
+                        "No documents match your search."
+                    } else {
+                        "Documents you view will appear here."
+                    }
+                }
+            } else {
+                ul {
+                    class: "history-list",
+                    for entry in entries {
+                        li {
+                            key: "{entry.path.display()}",
+                            class: "history-item",
+                            title: "{entry.path.display()}",
+                            onclick: {
+                                let path = entry.path.clone();
+                                move |_| state.open_file(&path)
+                            },
+                            div {
+                                class: "history-item-main",
+                                span { class: "history-item-title", "{entry.display_title()}" }
+                                span { class: "history-item-path", "{entry.path.display()}" }
+                            }
+                            span {
+                                class: "history-item-meta",
+                                {
+                                    let visits = if entry.visit_count == 1 { "visit" } else { "visits" };
+                                    format!(
+                                        "{} · {} {visits}",
+                                        entry.last_visited_label(now),
+                                        entry.visit_count
+                                    )
+                                }
+                            }
+                            button {
+                                class: "history-item-remove",
+                                title: "Remove from history",
+                                onclick: {
+                                    let path = entry.path.clone();
+                                    move |evt: MouseEvent| {
+                                        evt.stop_propagation();
+                                        remove_recent_visit(&path);
+                                    }
+                                },
+                                Icon { name: IconName::Close, size: 14 }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{anyhow, Context, Result};
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+use super::file_viewer::render_options_for;
+use super::inline_viewer::inline_render_options;
+use crate::assets::MAIN_SCRIPT;
+use crate::config::CONFIG;
+use crate::export::{page_title, static_page, WebviewSnapshot};
+use crate::markdown::render_markdown_with_options;
+use crate::state::{AppState, TabContent};
+use crate::utils::dialog::show_error;
+use crate::utils::file::is_markdown_file;
+
+/// Script sending what the webview rendered in the current document
+///
+/// It waits (up to five seconds) for pending diagrams, math and code first.
+/// Copy buttons are left out of the captured HTML, and each element comes with
+/// its source to match it to the placeholders of the exported document.
+fn snapshot_script() -> String {
+    indoc::formatdoc! {r#"
+        const {{ whenRendered }} = await import("{MAIN_SCRIPT}");
+        await whenRendered();
+        const body = document.querySelector(".markdown-viewer .markdown-body");
+        const capture = (selector) =>
+            Array.from(body?.querySelectorAll(selector) ?? [], (element) => {{
+                const clone = element.cloneNode(true);
+                clone.querySelectorAll(".copy-button").forEach((button) => button.remove());
+                return {{
+                    source: element.dataset.originalContent ?? clone.textContent,
+                    class: element.className,
+                    html: clone.innerHTML,
+                }};
+            }});
+        dioxus.send({{
+            theme: document.body.dataset.theme ?? "light",
+            styles: Array.from(document.querySelectorAll("style"))
+                .filter((style) => !style.disabled)
+                .map((style) => style.textContent),
+            mermaid: capture("pre.preprocessed-mermaid"),
+            mathBlocks: capture("pre.preprocessed-math"),
+            mathInline: capture("span.preprocessed-math-inline"),
+            mathDisplay: capture("div.preprocessed-math-display"),
+            code: capture("pre > code"),
+        }});
+    "#}
+}
+
+/// Export the document of the current tab as a single HTML file
+///
+/// The document is rendered again with links to the `.html` pages of other
+/// Markdown files (like `arto render`), and diagrams, math and highlighted
+/// code are taken from the webview, so the file needs no JavaScript.
+pub async fn export_current_tab_as_html(state: AppState) {
+    if let Err(e) = export_tab(state).await {
+        tracing::error!("Failed to export as HTML: {:?}", e);
+        show_error("Cannot export as HTML", format!("{e:#}")).await;
+    }
+}
+
+async fn export_tab(state: AppState) -> Result<()> {
+    let Some(tab) = state.current_tab() else {
+        return Ok(());
+    };
+    let (markdown, base_path, mut options, name) = match &tab.content {
+        TabContent::File(path) if is_markdown_file(path) => {
+            let markdown = tokio::fs::read_to_string(path)
+                .await
+                .with_context(|| format!("failed to read {}", path.display()))?;
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            (
+                markdown,
+                path.clone(),
+                render_options_for(path, &state),
+                name,
+            )
+        }
+        TabContent::Inline(document) => (
+            document.markdown.clone(),
+            document.base_path(),
+            inline_render_options(document, &state),
+            "Untitled".to_string(),
+        ),
+        _ => {
+            tracing::info!("Only Markdown documents can be exported as HTML");
+            return Ok(());
+        }
+    };
+    options.static_links = true;
+
+    let rendered = render_markdown_with_options(&markdown, &base_path, &options)
+        .with_context(|| format!("failed to render {}", base_path.display()))?;
+    let mut eval = document::eval(&snapshot_script());
+    let snapshot = eval
+        .recv::<WebviewSnapshot>()
+        .await
+        .map_err(|e| anyhow!("failed to capture the rendered document: {e:?}"))?;
+
+    let title = tab.title.unwrap_or_else(|| page_title(&rendered, &name));
+    let page = static_page(
+        &rendered.document_html(CONFIG.read().markdown.show_front_matter),
+        &title,
+        &snapshot,
+    );
+    let Some(file) = pick_export_file(&format!("{name}.html"), base_path.parent()).await else {
+        return Ok(());
+    };
+    tokio::fs::write(&file, page)
+        .await
+        .with_context(|| format!("failed to write {}", file.display()))?;
+    tracing::info!("Exported {:?} to {:?}", base_path, file);
+    Ok(())
+}
+
+/// Show save dialog for the exported page (async to prevent UI freeze)
+async fn pick_export_file(file_name: &str, directory: Option<&Path>) -> Option<PathBuf> {
+    use rfd::AsyncFileDialog;
+    let mut dialog = AsyncFileDialog::new()
+        .add_filter("HTML", &["html"])
+        .set_file_name(file_name);
+    if let Some(directory) = directory {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog.save_file().await.map(|h| h.path().to_path_buf())
+}
@@ -1,14 +1,20 @@
 use dioxus::prelude::*;
-use std::path::Path;
 
-use crate::markdown::render_to_html;
+use super::file_viewer::{apply_pending_scroll, render_options_for, use_link_click_handler};
+use crate::config::CONFIG;
+use crate::markdown::{render_markdown_with_options, ImageAccess, RenderOptions, RenderedMarkdown};
+use crate::state::{AppState, InlineDocument, InlineSource};
 
 #[component]
-pub fn InlineViewer(markdown: String) -> Element {
+pub fn InlineViewer(document: InlineDocument) -> Element {
+    let state = use_context::<AppState>();
     let html = use_signal(String::new);
 
     // Setup component hooks
-    use_inline_markdown_loader(markdown, html);
+    use_inline_markdown_loader(document.clone(), html, state);
+    use_outline_cleanup(state);
+    // Relative links resolve against the base directory like in a file there
+    use_link_click_handler(document.base_path(), state);
 
     rsx! {
         div {
@@ -22,18 +28,72 @@ pub fn InlineViewer(markdown: String) -> Element {
 }
 
 /// Hook to render inline markdown content
-fn use_inline_markdown_loader(markdown: String, html: Signal<String>) {
-    use_effect(move || {
+fn use_inline_markdown_loader(document: InlineDocument, html: Signal<String>, state: AppState) {
+    use_effect(use_reactive!(|document| {
         let mut html = html;
-        let markdown = markdown.clone();
+        let mut state = state;
+        let document = document.clone();
 
         spawn(async move {
-            // Render inline markdown (use a dummy path since images are already embedded)
-            let rendered = render_to_html(&markdown, Path::new(".")).unwrap_or_else(|e| {
-                tracing::error!("Failed to render inline markdown: {}", e);
-                format!(r#"<p class="error">Error rendering markdown: {}</p>"#, e)
-            });
-            html.set(rendered);
+            let base_path = document.base_path();
+            let options = inline_render_options(&document, &state);
+            match render_markdown_with_options(&document.markdown, &base_path, &options) {
+                Ok(rendered) => {
+                    state.set_current_tab_title(inline_title(&document, &rendered));
+                    let show_front_matter = CONFIG.read().markdown.show_front_matter;
+                    html.set(rendered.document_html(show_front_matter));
+                    state.outline.set(rendered.headings);
+                    apply_pending_scroll(&mut state);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to render inline markdown: {}", e);
+                    html.set(format!(
+                        r#"<p class="error">Error rendering markdown: {}</p>"#,
+                        e
+                    ));
+                    state.outline.set(Vec::new());
+                }
+            }
         });
+    }));
+}
+
+/// Build the render options of an inline document
+pub(super) fn inline_render_options(document: &InlineDocument, state: &AppState) -> RenderOptions {
+    match document.source {
+        // The welcome screen is bundled with the app (its images are already embedded)
+        InlineSource::Welcome => RenderOptions {
+            trust_html: true,
+            ..RenderOptions::default()
+        },
+        // Piped or pasted text is never trusted, wherever it was opened from
+        InlineSource::Stdin | InlineSource::Clipboard => {
+            let mut options = render_options_for(&document.base_path(), state);
+            options.trust_html = false;
+            if options.images == ImageAccess::Unrestricted {
+                options.images = ImageAccess::DocumentDirectory;
+            }
+            options
+        }
+    }
+}
+
+/// Get the title of an inline document
+///
+/// Documents other than the welcome screen have no file name, so their first
+/// heading is used when the front matter declares no title.
+fn inline_title(document: &InlineDocument, rendered: &RenderedMarkdown) -> Option<String> {
+    rendered.front_matter_title().or_else(|| {
+        (document.source != InlineSource::Welcome)
+            .then(|| rendered.headings.first())
+            .flatten()
+            .map(|heading| heading.text.clone())
+    })
+}
+
+/// Hook to clear the outline when the viewer is unmounted
+fn use_outline_cleanup(mut state: AppState) {
+    use_drop(move || {
+        state.outline.set(Vec::new());
     });
 }
@@ -2,8 +2,10 @@ mod dimension_input;
 mod directory_picker;
 mod option_cards;
 mod slider_input;
+mod text_list_input;
 
 pub use dimension_input::DimensionInput;
 pub use directory_picker::DirectoryPicker;
 pub use option_cards::{OptionCardItem, OptionCards};
 pub use slider_input::SliderInput;
+pub use text_list_input::TextListInput;
@@ -51,11 +51,12 @@ pub fn SliderInput(
                 }
                 span { "{unit}" }
             }
-            button {
-                class: "use-current-button",
-                disabled: current_value.is_none(),
-                onclick: handle_use_current,
-                "Use Current"
+            if current_value.is_some() {
+                button {
+                    class: "use-current-button",
+                    onclick: handle_use_current,
+                    "Use Current"
+                }
             }
         }
     }
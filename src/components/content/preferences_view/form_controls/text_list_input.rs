@@ -0,0 +1,31 @@
+use dioxus::prelude::*;
+
+/// Comma-separated list input (committed when the field loses focus)
+#[component]
+pub fn TextListInput(
+    values: Vec<String>,
+    placeholder: String,
+    on_change: EventHandler<Vec<String>>,
+) -> Element {
+    let joined = values.join(", ");
+
+    rsx! {
+        div {
+            class: "text-list-input",
+            input {
+                r#type: "text",
+                placeholder: "{placeholder}",
+                value: "{joined}",
+                onchange: move |evt| {
+                    let values = evt
+                        .value()
+                        .split(',')
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                        .collect();
+                    on_change.call(values);
+                },
+            }
+        }
+    }
+}
@@ -1,10 +1,13 @@
 use super::tabs::{
-    about_tab::AboutTab, directory_tab::DirectoryTab, sidebar_tab::SidebarTab, theme_tab::ThemeTab,
+    about_tab::AboutTab, directory_tab::DirectoryTab, history_tab::HistoryTab,
+    markdown_tab::MarkdownTab, print_tab::PrintTab, session_tab::SessionTab,
+    sidebar_tab::SidebarTab, theme_tab::ThemeTab, watcher_tab::WatcherTab,
     window_position_tab::WindowPositionTab, window_size_tab::WindowSizeTab,
 };
 use crate::components::icon::{Icon, IconName};
 use crate::config::{Config, CONFIG};
 use crate::state::AppState;
+use crate::watcher::FILE_WATCHER;
 use dioxus::prelude::*;
 use parking_lot::RwLock;
 use std::sync::LazyLock;
@@ -17,6 +20,11 @@ pub enum PreferencesTab {
     WindowSize,
     Sidebar,
     Directory,
+    Markdown,
+    Print,
+    Session,
+    History,
+    Watcher,
     About,
 }
 
@@ -62,6 +70,9 @@ pub fn PreferencesView() -> Element {
                 save_status.set(SaveStatus::Idle);
             } else {
                 *CONFIG.write() = cfg.clone();
+                if let Err(e) = FILE_WATCHER.reconfigure().await {
+                    tracing::error!("Failed to reconfigure file watcher: {:?}", e);
+                }
                 has_changes.set(false);
                 save_status.set(SaveStatus::Saved);
                 // Reset to idle after showing success
@@ -130,6 +141,51 @@ pub fn PreferencesView() -> Element {
                         Icon { name: IconName::Folder, size: 18 }
                         span { "Directory" }
                     }
+                    button {
+                        class: if current_tab == PreferencesTab::Markdown { "nav-tab active" } else { "nav-tab" },
+                        onclick: move |_| {
+                            active_tab.set(PreferencesTab::Markdown);
+                            *LAST_PREFERENCES_TAB.write() = PreferencesTab::Markdown;
+                        },
+                        Icon { name: IconName::Markdown, size: 18 }
+                        span { "Markdown" }
+                    }
+                    button {
+                        class: if current_tab == PreferencesTab::Print { "nav-tab active" } else { "nav-tab" },
+                        onclick: move |_| {
+                            active_tab.set(PreferencesTab::Print);
+                            *LAST_PREFERENCES_TAB.write() = PreferencesTab::Print;
+                        },
+                        Icon { name: IconName::Printer, size: 18 }
+                        span { "Print" }
+                    }
+                    button {
+                        class: if current_tab == PreferencesTab::Session { "nav-tab active" } else { "nav-tab" },
+                        onclick: move |_| {
+                            active_tab.set(PreferencesTab::Session);
+                            *LAST_PREFERENCES_TAB.write() = PreferencesTab::Session;
+                        },
+                        Icon { name: IconName::Restore, size: 18 }
+                        span { "Session" }
+                    }
+                    button {
+                        class: if current_tab == PreferencesTab::History { "nav-tab active" } else { "nav-tab" },
+                        onclick: move |_| {
+                            active_tab.set(PreferencesTab::History);
+                            *LAST_PREFERENCES_TAB.write() = PreferencesTab::History;
+                        },
+                        Icon { name: IconName::History, size: 18 }
+                        span { "History" }
+                    }
+                    button {
+                        class: if current_tab == PreferencesTab::Watcher { "nav-tab active" } else { "nav-tab" },
+                        onclick: move |_| {
+                            active_tab.set(PreferencesTab::Watcher);
+                            *LAST_PREFERENCES_TAB.write() = PreferencesTab::Watcher;
+                        },
+                        Icon { name: IconName::Refresh, size: 18 }
+                        span { "Auto-Reload" }
+                    }
 
                     // Spacer to push About to bottom
                     div { class: "nav-spacer" }
@@ -207,6 +263,37 @@ pub fn PreferencesView() -> Element {
                                 current_directory: state.directory.read().clone(),
                             }
                         },
+                        PreferencesTab::Markdown => rsx! {
+                            MarkdownTab {
+                                config,
+                                has_changes,
+                                current_directory: state.directory.read().clone(),
+                            }
+                        },
+                        PreferencesTab::Print => rsx! {
+                            PrintTab {
+                                config,
+                                has_changes,
+                            }
+                        },
+                        PreferencesTab::Session => rsx! {
+                            SessionTab {
+                                config,
+                                has_changes,
+                            }
+                        },
+                        PreferencesTab::History => rsx! {
+                            HistoryTab {
+                                config,
+                                has_changes,
+                            }
+                        },
+                        PreferencesTab::Watcher => rsx! {
+                            WatcherTab {
+                                config,
+                                has_changes,
+                            }
+                        },
                         PreferencesTab::About => rsx! {
                             AboutTab {}
                         },
@@ -1,6 +1,11 @@
 pub mod about_tab;
 pub mod directory_tab;
+pub mod history_tab;
+pub mod markdown_tab;
+pub mod print_tab;
+pub mod session_tab;
 pub mod sidebar_tab;
 pub mod theme_tab;
+pub mod watcher_tab;
 pub mod window_position_tab;
 pub mod window_size_tab;
@@ -36,6 +36,37 @@ pub fn DirectoryTab(
                 }
             }
 
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Open README" }
+                    p { class: "preference-description", "Whether opening a directory also opens its README (or index) file." }
+                }
+                OptionCards {
+                    name: "dir-open-readme".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Directory Only".to_string(),
+                            description: Some("Only show the directory in the sidebar".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Open README".to_string(),
+                            description: Some("Also open README.md or index.md in a tab".to_string()),
+                        },
+                    ],
+                    selected: directory.open_readme,
+                    on_change: move |new_state| {
+                        config.write().directory.open_readme = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
             h3 { class: "preference-section-title", "Behavior" }
 
             div {
@@ -0,0 +1,85 @@
+use super::super::form_controls::{OptionCardItem, OptionCards, TextListInput};
+use crate::config::Config;
+use crate::state::clear_recently_viewed;
+use dioxus::prelude::*;
+
+#[component]
+pub fn HistoryTab(config: Signal<Config>, has_changes: Signal<bool>) -> Element {
+    // Extract values upfront to avoid holding read guard across closures
+    let history = config.read().history.clone();
+    let mut is_cleared = use_signal(|| false);
+
+    rsx! {
+        div {
+            class: "preferences-pane",
+
+            h3 { class: "preference-section-title", "Recently Viewed" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Remember Documents" }
+                    p { class: "preference-description", "Whether viewed documents are listed in File > Open Recent and History > Show All History." }
+                }
+                OptionCards {
+                    name: "history-enabled".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Remember".to_string(),
+                            description: Some("Keep a history of viewed documents".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Don't Remember".to_string(),
+                            description: Some("Stop recording viewed documents".to_string()),
+                        },
+                    ],
+                    selected: history.enabled,
+                    on_change: move |new_state| {
+                        config.write().history.enabled = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Exclude" }
+                    p { class: "preference-description", "Comma-separated glob patterns of documents never remembered. Patterns without a slash match any file or directory name (e.g. *.private.md); others match full paths (e.g. ~/Private/**)." }
+                }
+                TextListInput {
+                    values: history.exclude.clone(),
+                    placeholder: "Not set".to_string(),
+                    on_change: move |patterns: Vec<String>| {
+                        config.write().history.exclude = patterns;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Clear History" }
+                    p { class: "preference-description", "Forget every remembered document. This takes effect immediately." }
+                }
+                button {
+                    class: "history-clear-button",
+                    disabled: is_cleared(),
+                    onclick: move |_| {
+                        clear_recently_viewed();
+                        is_cleared.set(true);
+                    },
+                    if is_cleared() { "Cleared" } else { "Clear History" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use super::super::form_controls::{
+    DirectoryPicker, OptionCardItem, OptionCards, SliderInput, TextListInput,
+};
+use crate::config::Config;
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+#[component]
+pub fn MarkdownTab(
+    config: Signal<Config>,
+    has_changes: Signal<bool>,
+    current_directory: Option<PathBuf>,
+) -> Element {
+    // Extract values upfront to avoid holding read guard across closures
+    let markdown = config.read().markdown.clone();
+
+    rsx! {
+        div {
+            class: "preferences-pane",
+
+            h3 { class: "preference-section-title", "Rendering" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Front Matter" }
+                    p { class: "preference-description", "How YAML or TOML front matter at the top of a document is displayed." }
+                }
+                OptionCards {
+                    name: "markdown-show-front-matter".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Hidden".to_string(),
+                            description: Some("Do not display front matter".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Table".to_string(),
+                            description: Some("Show front matter as a metadata table".to_string()),
+                        },
+                    ],
+                    selected: markdown.show_front_matter,
+                    on_change: move |new_state| {
+                        config.write().markdown.show_front_matter = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            h3 { class: "preference-section-title", "Links" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Open Externally" }
+                    p { class: "preference-description", "Comma-separated extensions of linked files opened with the default application. Other text and image files open in a tab." }
+                }
+                TextListInput {
+                    values: markdown.external_open_extensions.clone(),
+                    placeholder: "pdf, docx, zip".to_string(),
+                    on_change: move |extensions| {
+                        config.write().markdown.external_open_extensions = extensions;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Document Root" }
+                    p { class: "preference-description", "Directory that links and images starting with \"/\" are resolved against. When not set, the Git repository root or the sidebar directory is used." }
+                }
+                DirectoryPicker {
+                    value: markdown.document_root.clone(),
+                    placeholder: "Detect automatically".to_string(),
+                    on_change: move |new_value| {
+                        config.write().markdown.document_root = new_value;
+                        has_changes.set(true);
+                    },
+                    current_directory: current_directory.clone(),
+                }
+            }
+
+            h3 { class: "preference-section-title", "Images" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Local Images" }
+                    p { class: "preference-description", "Which local image files a document may display." }
+                }
+                OptionCards {
+                    name: "markdown-restrict-images".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Document Root".to_string(),
+                            description: Some("Only images inside the document root (or the document's directory)".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Anywhere".to_string(),
+                            description: Some("Any image file readable by the application (piped and pasted text stays limited to the document root)".to_string()),
+                        },
+                    ],
+                    selected: markdown.restrict_images_to_root,
+                    on_change: move |new_state| {
+                        config.write().markdown.restrict_images_to_root = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Maximum Image Size" }
+                    p { class: "preference-description", "Larger local images are replaced with a placeholder. Set to 0 for no limit." }
+                }
+                SliderInput {
+                    value: markdown.max_image_size_mb as f64,
+                    min: 0.0,
+                    max: 100.0,
+                    step: 1.0,
+                    unit: "MB".to_string(),
+                    on_change: move |new_size: f64| {
+                        config.write().markdown.max_image_size_mb = new_size.round() as u64;
+                        has_changes.set(true);
+                    },
+                    current_value: None,
+                }
+            }
+
+            h3 { class: "preference-section-title", "Security" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Trusted Directories" }
+                    p { class: "preference-description", "Comma-separated directories whose documents may use any raw HTML, including scripts and styles. Raw HTML in other documents is limited to the same tags and attributes as GitHub." }
+                }
+                TextListInput {
+                    values: markdown
+                        .trusted_directories
+                        .iter()
+                        .map(|dir| dir.display().to_string())
+                        .collect::<Vec<_>>(),
+                    placeholder: "Not set".to_string(),
+                    on_change: move |dirs: Vec<String>| {
+                        config.write().markdown.trusted_directories =
+                            dirs.into_iter().map(PathBuf::from).collect();
+                        has_changes.set(true);
+                    },
+                }
+            }
+        }
+    }
+}
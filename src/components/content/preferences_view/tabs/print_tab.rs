@@ -0,0 +1,114 @@
+use super::super::form_controls::{OptionCardItem, OptionCards, SliderInput};
+use crate::config::{Config, PaperSize};
+use dioxus::prelude::*;
+
+#[component]
+pub fn PrintTab(config: Signal<Config>, has_changes: Signal<bool>) -> Element {
+    // Extract values upfront to avoid holding read guard across closures
+    let print = config.read().print.clone();
+
+    rsx! {
+        div {
+            class: "preferences-pane",
+
+            h3 { class: "preference-section-title", "Page" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Paper Size" }
+                    p { class: "preference-description", "Paper size used by File > Print and File > Save as PDF. It can still be changed in the print panel." }
+                }
+                OptionCards {
+                    name: "print-paper-size".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: PaperSize::A4,
+                            title: "A4".to_string(),
+                            description: Some("210 × 297 mm".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: PaperSize::A5,
+                            title: "A5".to_string(),
+                            description: Some("148 × 210 mm".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: PaperSize::Letter,
+                            title: "Letter".to_string(),
+                            description: Some("8.5 × 11 in".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: PaperSize::Legal,
+                            title: "Legal".to_string(),
+                            description: Some("8.5 × 14 in".to_string()),
+                        },
+                    ],
+                    selected: print.paper_size,
+                    on_change: move |new_size| {
+                        config.write().print.paper_size = new_size;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Margins" }
+                    p { class: "preference-description", "Blank space on every side of a page." }
+                }
+                SliderInput {
+                    value: print.margin_mm,
+                    min: 0.0,
+                    max: 50.0,
+                    step: 1.0,
+                    unit: "mm".to_string(),
+                    on_change: move |new_margin: f64| {
+                        config.write().print.margin_mm = new_margin.round();
+                        has_changes.set(true);
+                    },
+                    current_value: None,
+                }
+            }
+
+            h3 { class: "preference-section-title", "Appearance" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Theme" }
+                    p { class: "preference-description", "Theme of printed pages. Diagrams and math are rendered again in that theme before printing." }
+                }
+                OptionCards {
+                    name: "print-force-light-theme".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Light".to_string(),
+                            description: Some("Always print in the light theme".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Window Theme".to_string(),
+                            description: Some("Print in the theme of the window".to_string()),
+                        },
+                    ],
+                    selected: print.force_light_theme,
+                    on_change: move |new_state| {
+                        config.write().print.force_light_theme = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use super::super::form_controls::{OptionCardItem, OptionCards};
+use crate::config::{Config, SessionStartupBehavior};
+use dioxus::prelude::*;
+
+#[component]
+pub fn SessionTab(config: Signal<Config>, has_changes: Signal<bool>) -> Element {
+    // Extract values upfront to avoid holding read guard across closures
+    let session = config.read().session.clone();
+
+    rsx! {
+        div {
+            class: "preferences-pane",
+
+            h3 { class: "preference-section-title", "Behavior" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "On Startup" }
+                    p { class: "preference-description", "What to show when the application starts without files to open." }
+                }
+                OptionCards {
+                    name: "session-startup".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: SessionStartupBehavior::Default,
+                            title: "New Window".to_string(),
+                            description: Some("Start with a single window".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: SessionStartupBehavior::RestoreSession,
+                            title: "Restore Session".to_string(),
+                            description: Some("Reopen all windows and tabs from last time".to_string()),
+                        },
+                    ],
+                    selected: session.on_startup,
+                    on_change: move |new_behavior| {
+                        config.write().session.on_startup = new_behavior;
+                        has_changes.set(true);
+                    },
+                }
+            }
+        }
+    }
+}
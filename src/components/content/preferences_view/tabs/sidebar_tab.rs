@@ -1,5 +1,6 @@
-use super::super::form_controls::{OptionCardItem, OptionCards, SliderInput};
-use crate::config::{Config, NewWindowBehavior, StartupBehavior};
+use super::super::form_controls::{OptionCardItem, OptionCards, SliderInput, TextListInput};
+use crate::components::icon::IconName;
+use crate::config::{Config, NewWindowBehavior, SidebarPanel, StartupBehavior};
 use dioxus::prelude::*;
 
 #[component]
@@ -10,6 +11,7 @@ pub fn SidebarTab(
 ) -> Element {
     // Extract values upfront to avoid holding read guard across closures
     let sidebar = config.read().sidebar.clone();
+    let follow = config.read().follow.clone();
 
     rsx! {
         div {
@@ -100,6 +102,37 @@ pub fn SidebarTab(
                 }
             }
 
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Default Panel" }
+                    p { class: "preference-description", "The panel shown in the sidebar when opening a window." }
+                }
+                OptionCards {
+                    name: "sidebar-default-panel".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: Some(IconName::Folders),
+                            value: SidebarPanel::Explorer,
+                            title: "Files".to_string(),
+                            description: Some("Show the file explorer".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: Some(IconName::ListTree),
+                            value: SidebarPanel::Outline,
+                            title: "Outline".to_string(),
+                            description: Some("Show the document outline".to_string()),
+                        },
+                    ],
+                    selected: sidebar.default_panel,
+                    on_change: move |new_panel| {
+                        config.write().sidebar.default_panel = new_panel;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
             h3 { class: "preference-section-title", "Behavior" }
 
             div {
@@ -163,6 +196,56 @@ pub fn SidebarTab(
                     },
                 }
             }
+
+            h3 { class: "preference-section-title", "Follow Directory" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Open Changed Files" }
+                    p { class: "preference-description", "Where Markdown files created or modified in the root directory are shown while following it (toggled from the file explorer toolbar)." }
+                }
+                OptionCards {
+                    name: "follow-dedicated-tab".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Own Tabs".to_string(),
+                            description: Some("Open each file like from the file explorer".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Dedicated Tab".to_string(),
+                            description: Some("Show every file in one tab that follows the directory".to_string()),
+                        },
+                    ],
+                    selected: follow.dedicated_tab,
+                    on_change: move |new_state| {
+                        config.write().follow.dedicated_tab = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Include" }
+                    p { class: "preference-description", "Comma-separated glob patterns of the files to follow (e.g. report-*.md). Every Markdown file is followed when not set." }
+                }
+                TextListInput {
+                    values: follow.include.clone(),
+                    placeholder: "Not set".to_string(),
+                    on_change: move |patterns: Vec<String>| {
+                        config.write().follow.include = patterns;
+                        has_changes.set(true);
+                    },
+                }
+            }
         }
     }
 }
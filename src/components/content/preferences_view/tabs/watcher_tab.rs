@@ -0,0 +1,140 @@
+use super::super::form_controls::{OptionCardItem, OptionCards, SliderInput, TextListInput};
+use crate::config::Config;
+use dioxus::prelude::*;
+
+#[component]
+pub fn WatcherTab(config: Signal<Config>, has_changes: Signal<bool>) -> Element {
+    // Extract values upfront to avoid holding read guard across closures
+    let watcher = config.read().watcher.clone();
+
+    rsx! {
+        div {
+            class: "preferences-pane",
+
+            h3 { class: "preference-section-title", "Change Detection" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Reload Delay" }
+                    p { class: "preference-description", "How long changes must settle before documents are reloaded. Longer delays avoid reloading several times while a file is being written." }
+                }
+                SliderInput {
+                    value: watcher.debounce_ms as f64,
+                    min: 50.0,
+                    max: 5000.0,
+                    step: 50.0,
+                    unit: "ms".to_string(),
+                    on_change: move |new_delay: f64| {
+                        config.write().watcher.debounce_ms = new_delay.round() as u64;
+                        has_changes.set(true);
+                    },
+                    current_value: None,
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Detection Method" }
+                    p { class: "preference-description", "How changes to documents and directories are noticed." }
+                }
+                OptionCards {
+                    name: "watcher-polling".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "File System Events".to_string(),
+                            description: Some("Be notified by the system as soon as files change".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Polling".to_string(),
+                            description: Some("Check every file periodically (works everywhere, uses more resources)".to_string()),
+                        },
+                    ],
+                    selected: watcher.polling,
+                    on_change: move |new_state| {
+                        config.write().watcher.polling = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Network Volumes" }
+                    p { class: "preference-description", "File system events are often missing on network mounts and FUSE volumes. Polling them keeps documents up to date." }
+                }
+                OptionCards {
+                    name: "watcher-poll-remote".to_string(),
+                    options: vec![
+                        OptionCardItem {
+                            icon: None,
+                            value: true,
+                            title: "Poll".to_string(),
+                            description: Some("Poll documents on non-local volumes".to_string()),
+                        },
+                        OptionCardItem {
+                            icon: None,
+                            value: false,
+                            title: "Use Detection Method".to_string(),
+                            description: Some("Treat them like local documents".to_string()),
+                        },
+                    ],
+                    selected: watcher.poll_remote,
+                    on_change: move |new_state| {
+                        config.write().watcher.poll_remote = new_state;
+                        has_changes.set(true);
+                    },
+                }
+            }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Polling Interval" }
+                    p { class: "preference-description", "How often polled documents and directories are checked for changes." }
+                }
+                SliderInput {
+                    value: watcher.poll_interval_ms as f64 / 1000.0,
+                    min: 0.5,
+                    max: 60.0,
+                    step: 0.5,
+                    unit: "s".to_string(),
+                    on_change: move |new_interval: f64| {
+                        config.write().watcher.poll_interval_ms = (new_interval * 1000.0).round() as u64;
+                        has_changes.set(true);
+                    },
+                    current_value: None,
+                }
+            }
+
+            h3 { class: "preference-section-title", "Exclusions" }
+
+            div {
+                class: "preference-item",
+                div {
+                    class: "preference-item-header",
+                    label { "Exclude" }
+                    p { class: "preference-description", "Comma-separated glob patterns of files and directories never watched. Patterns without a slash match any file or directory name (e.g. node_modules); others match full paths (e.g. /Volumes/Archive/**)." }
+                }
+                TextListInput {
+                    values: watcher.exclude.clone(),
+                    placeholder: "Not set".to_string(),
+                    on_change: move |patterns: Vec<String>| {
+                        config.write().watcher.exclude = patterns;
+                        has_changes.set(true);
+                    },
+                }
+            }
+        }
+    }
+}
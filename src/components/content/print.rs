@@ -0,0 +1,85 @@
+use dioxus::prelude::*;
+use std::path::{Path, PathBuf};
+
+use crate::assets::MAIN_SCRIPT;
+use crate::config::CONFIG;
+use crate::state::{AppState, TabContent};
+use crate::utils::print::print_webview;
+
+/// Where the pages of a print job go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrintDestination {
+    /// Printer chosen in the print panel (which can also save a PDF)
+    Printer,
+    /// PDF file chosen in a save dialog, without the print panel
+    Pdf,
+}
+
+/// Print the current tab, or save it as a PDF file
+///
+/// Diagrams and math are rendered before the pages are laid out, and the
+/// document is shown in the light theme meanwhile when the configuration
+/// forces it.
+pub async fn print_current_tab(state: AppState, destination: PrintDestination) {
+    let Some(tab) = state.current_tab() else {
+        return;
+    };
+    let pdf_file = match destination {
+        PrintDestination::Printer => None,
+        PrintDestination::Pdf => {
+            let (name, directory) = match &tab.content {
+                TabContent::File(path) => (
+                    path.file_stem()
+                        .map(|stem| stem.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    path.parent().map(Path::to_path_buf),
+                ),
+                TabContent::Inline(document) => ("Untitled".to_string(), document.base_dir.clone()),
+                _ => ("Untitled".to_string(), None),
+            };
+            let name = tab.title.clone().unwrap_or(name);
+            match pick_pdf_file(&format!("{name}.pdf"), directory.as_deref()).await {
+                Some(file) => Some(file),
+                None => return,
+            }
+        }
+    };
+    let config = CONFIG.read().print.clone();
+
+    let mut eval = document::eval(&indoc::formatdoc! {r#"
+        const {{ preparePrint }} = await import("{MAIN_SCRIPT}");
+        await preparePrint({{ forceLightTheme: {} }});
+        dioxus.send(true);
+    "#, config.force_light_theme});
+    if let Err(e) = eval.recv::<bool>().await {
+        tracing::warn!("Failed to prepare the document for printing: {:?}", e);
+    }
+
+    let desktop = dioxus::desktop::window();
+    match print_webview(&desktop.webview, &config, pdf_file.as_deref()).await {
+        Ok(true) => match &pdf_file {
+            Some(file) => tracing::info!("Saved PDF to {:?}", file),
+            None => tracing::info!("Printed the current tab"),
+        },
+        Ok(false) => tracing::info!("Printing was cancelled"),
+        Err(e) => tracing::error!("Failed to print: {:?}", e),
+    }
+
+    let _ = document::eval(&indoc::formatdoc! {r#"
+        const {{ finishPrint }} = await import("{MAIN_SCRIPT}");
+        finishPrint();
+    "#})
+    .await;
+}
+
+/// Show save dialog for the PDF file (async to prevent UI freeze)
+async fn pick_pdf_file(file_name: &str, directory: Option<&Path>) -> Option<PathBuf> {
+    use rfd::AsyncFileDialog;
+    let mut dialog = AsyncFileDialog::new()
+        .add_filter("PDF", &["pdf"])
+        .set_file_name(file_name);
+    if let Some(directory) = directory {
+        dialog = dialog.set_directory(directory);
+    }
+    dialog.save_file().await.map(|h| h.path().to_path_buf())
+}
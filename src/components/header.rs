@@ -1,10 +1,16 @@
 use dioxus::document;
+use dioxus::html::input_data::MouseButton;
 use dioxus::prelude::*;
 
+use crate::components::content::capture_scroll_top;
+use crate::components::history_menu::{history_menu_entries, HistoryDirection, HistoryMenu};
 use crate::components::icon::{Icon, IconName};
 use crate::components::theme_selector::ThemeSelector;
 use crate::state::{AppState, TabContent};
 
+/// How long the back/forward buttons must be held to list the history
+const HISTORY_LONG_PRESS_MS: u64 = 500;
+
 #[component]
 pub fn Header() -> Element {
     let mut state = use_context::<AppState>();
@@ -30,22 +36,66 @@ pub fn Header() -> Element {
 
     let is_sidebar_open = state.sidebar.read().open;
 
+    // History menu of the back/forward buttons: (direction, position)
+    let mut history_menu = use_signal(|| None::<(HistoryDirection, (i32, i32))>);
+    // Long-presses in progress are cancelled by bumping the counter
+    let mut press_count = use_signal(|| 0usize);
+    // A long-press opens the menu, so the click that ends it must not navigate
+    let mut suppress_click = use_signal(|| false);
+
     let on_back = move |_| {
-        state.update_current_tab(|tab| {
-            if let Some(path) = tab.history.go_back() {
-                tab.content = crate::state::TabContent::File(path.to_owned());
-            }
+        if suppress_click() {
+            suppress_click.set(false);
+            return;
+        }
+        spawn(async move {
+            capture_scroll_top(state).await;
+            state.go_back();
         });
     };
 
     let on_forward = move |_| {
-        state.update_current_tab(|tab| {
-            if let Some(path) = tab.history.go_forward() {
-                tab.content = crate::state::TabContent::File(path.to_owned());
+        if suppress_click() {
+            suppress_click.set(false);
+            return;
+        }
+        spawn(async move {
+            capture_scroll_top(state).await;
+            state.go_forward();
+        });
+    };
+
+    let mut open_history_menu = move |direction: HistoryDirection, evt: &MouseEvent| {
+        let point = evt.data().client_coordinates();
+        history_menu.set(Some((direction, (point.x as i32, point.y as i32))));
+    };
+
+    let mut start_long_press = move |direction: HistoryDirection, evt: MouseEvent| {
+        if evt.data().trigger_button() != Some(MouseButton::Primary) {
+            return;
+        }
+        let press = press_count() + 1;
+        press_count.set(press);
+        let point = evt.data().client_coordinates();
+        spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(HISTORY_LONG_PRESS_MS)).await;
+            if press_count() == press {
+                suppress_click.set(true);
+                history_menu.set(Some((direction, (point.x as i32, point.y as i32))));
             }
         });
     };
 
+    let cancel_long_press = move |_| {
+        press_count += 1;
+    };
+
+    let history_menu_items = history_menu().and_then(|(direction, position)| {
+        let tab = current_tab.as_ref()?;
+        let entries = history_menu_entries(&tab.history, direction);
+        (!entries.is_empty()).then_some((position, entries))
+    });
+
     let is_reloading = use_signal(|| false);
     let mut is_reloading_write = is_reloading;
 
@@ -69,6 +119,9 @@ pub fn Header() -> Element {
 
     // Check if there's a file to reload/copy
     let can_reload = file_path.is_some();
+    let is_auto_reload_paused = current_tab
+        .as_ref()
+        .is_some_and(|tab| tab.auto_reload_paused);
 
     // Copy feedback state
     let mut is_copied = use_signal(|| false);
@@ -94,22 +147,55 @@ pub fn Header() -> Element {
                     }
                 }
 
-                // Back button
+                // Back button (right-click or long-press lists the history)
                 button {
                     class: "nav-button",
                     disabled: !can_go_back,
                     onclick: on_back,
+                    onmousedown: move |evt| start_long_press(HistoryDirection::Back, evt),
+                    onmouseup: cancel_long_press,
+                    onmouseleave: cancel_long_press,
+                    oncontextmenu: move |evt| {
+                        evt.prevent_default();
+                        open_history_menu(HistoryDirection::Back, &evt);
+                    },
                     Icon { name: IconName::ChevronLeft }
                 }
 
-                // Forward button
+                // Forward button (right-click or long-press lists the history)
                 button {
                     class: "nav-button",
                     disabled: !can_go_forward,
                     onclick: on_forward,
+                    onmousedown: move |evt| start_long_press(HistoryDirection::Forward, evt),
+                    onmouseup: cancel_long_press,
+                    onmouseleave: cancel_long_press,
+                    oncontextmenu: move |evt| {
+                        evt.prevent_default();
+                        open_history_menu(HistoryDirection::Forward, &evt);
+                    },
                     Icon { name: IconName::ChevronRight }
                 }
 
+                if let Some((position, entries)) = history_menu_items {
+                    HistoryMenu {
+                        position,
+                        entries,
+                        on_select: move |index| {
+                            history_menu.set(None);
+                            suppress_click.set(false);
+                            spawn(async move {
+                                capture_scroll_top(state).await;
+                                state.go_to_history(index);
+                            });
+                        },
+                        on_close: move |_| {
+                            history_menu.set(None);
+                            suppress_click.set(false);
+                        },
+                    }
+                }
+
                 // File name
                 span {
                     class: "file-name",
@@ -148,6 +234,19 @@ pub fn Header() -> Element {
             div {
                 class: "header-right",
 
+                // Auto-reload toggle (per tab)
+                button {
+                    class: "nav-button auto-reload-button",
+                    class: if is_auto_reload_paused { "active" },
+                    disabled: !can_reload,
+                    onclick: move |_| state.toggle_auto_reload(),
+                    title: if is_auto_reload_paused { "Resume auto-reload" } else { "Pause auto-reload" },
+                    Icon {
+                        name: if is_auto_reload_paused { IconName::PlayerPlay } else { IconName::PlayerPause },
+                        size: 18,
+                    }
+                }
+
                 // Reload button
                 button {
                     class: "nav-button reload-button",
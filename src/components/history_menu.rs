@@ -0,0 +1,99 @@
+use dioxus::prelude::*;
+use std::path::PathBuf;
+
+use crate::history::HistoryManager;
+
+/// Which side of the current entry the menu lists
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryDirection {
+    Back,
+    Forward,
+}
+
+/// Get the entries to list for a direction as (index, path), nearest first
+pub fn history_menu_entries(
+    history: &HistoryManager,
+    direction: HistoryDirection,
+) -> Vec<(usize, PathBuf)> {
+    let current = history.current_index();
+    let entries = history.entries().iter().enumerate();
+    match direction {
+        HistoryDirection::Back => entries
+            .take(current)
+            .rev()
+            .map(|(index, entry)| (index, entry.path.clone()))
+            .collect(),
+        HistoryDirection::Forward => entries
+            .skip(current + 1)
+            .map(|(index, entry)| (index, entry.path.clone()))
+            .collect(),
+    }
+}
+
+/// Menu of the back/forward history of a tab (shown by right-click or long-press)
+#[component]
+pub fn HistoryMenu(
+    position: (i32, i32),
+    entries: Vec<(usize, PathBuf)>,
+    on_select: EventHandler<usize>,
+    on_close: EventHandler<()>,
+) -> Element {
+    rsx! {
+        // Backdrop to close menu on outside click
+        div {
+            class: "context-menu-backdrop",
+            onclick: move |_| on_close.call(()),
+            oncontextmenu: move |evt| {
+                evt.prevent_default();
+                on_close.call(());
+            },
+        }
+
+        div {
+            class: "context-menu history-menu",
+            style: "left: {position.0}px; top: {position.1}px;",
+            onclick: move |evt| evt.stop_propagation(),
+
+            for (index, path) in entries {
+                div {
+                    key: "{index}",
+                    class: "context-menu-item",
+                    title: "{path.display()}",
+                    onclick: move |_| on_select.call(index),
+                    {
+                        path.file_name()
+                            .unwrap_or(path.as_os_str())
+                            .to_string_lossy()
+                            .to_string()
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_menu_entries() {
+        let mut history = HistoryManager::new();
+        history.push("/test/a.md");
+        history.push("/test/b.md");
+        history.push("/test/c.md");
+        history.push("/test/d.md");
+        history.go_to(2);
+
+        let back = history_menu_entries(&history, HistoryDirection::Back);
+        assert_eq!(
+            back,
+            vec![
+                (1, PathBuf::from("/test/b.md")),
+                (0, PathBuf::from("/test/a.md"))
+            ]
+        );
+        let forward = history_menu_entries(&history, HistoryDirection::Forward);
+        assert_eq!(forward, vec![(3, PathBuf::from("/test/d.md"))]);
+    }
+}
@@ -35,6 +35,15 @@ pub enum IconName {
     InfoCircle,
     BrandGithub,
     Bug,
+    Folders,
+    ListTree,
+    Markdown,
+    Printer,
+    Restore,
+    History,
+    PlayerPause,
+    PlayerPlay,
+    LiveView,
 }
 
 impl fmt::Display for IconName {
@@ -70,6 +79,15 @@ impl fmt::Display for IconName {
             IconName::InfoCircle => "info-circle",
             IconName::BrandGithub => "brand-github",
             IconName::Bug => "bug",
+            IconName::Folders => "folders",
+            IconName::ListTree => "list-tree",
+            IconName::Markdown => "markdown",
+            IconName::Printer => "printer",
+            IconName::Restore => "restore",
+            IconName::History => "history",
+            IconName::PlayerPause => "player-pause",
+            IconName::PlayerPlay => "player-play",
+            IconName::LiveView => "live-view",
         };
         write!(f, "{}", name)
     }
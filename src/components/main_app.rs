@@ -1,14 +1,20 @@
-use crate::events::{DIRECTORY_OPEN_BROADCAST, FILE_OPEN_BROADCAST};
-use crate::state::Tab;
+use crate::cli::{CliArgs, StdinDocument};
+use crate::config::CONFIG;
+use crate::events::{
+    DIRECTORY_OPEN_BROADCAST, FILE_OPEN_BROADCAST, INLINE_OPEN_BROADCAST, RECENTLY_VIEWED_CHANGED,
+};
+use crate::state::{InlineDocument, InlineSource, Session, SessionWindow, Tab};
+use crate::utils::file::find_directory_index;
 use crate::window as window_manager;
 use crate::window::metrics::update_outer_to_inner_metrics;
-use crate::window::{settings, CreateMainWindowConfigParams};
+use crate::window::{CreateMainWindowConfigParams, WindowContents};
 use dioxus::core::spawn_forever;
 use dioxus::desktop::use_muda_event_handler;
 use dioxus::desktop::{window, WindowCloseBehaviour};
 use dioxus::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::mpsc::Receiver;
 
 // ============================================================================
@@ -19,10 +25,14 @@ use tokio::sync::mpsc::Receiver;
 /// Used to communicate between OS event handler (main.rs) and MainApp component
 #[derive(Debug, Clone)]
 pub enum OpenEvent {
-    /// File opened from Finder/CLI
-    File(PathBuf),
-    /// Directory opened from Finder/CLI (should set sidebar root)
+    /// File opened from Finder, with an optional anchor to scroll to
+    File(PathBuf, Option<String>),
+    /// Directory opened from Finder (should set sidebar root)
     Directory(PathBuf),
+    /// Files, directories and window options given on the command line
+    Arguments(CliArgs),
+    /// Windows and tabs of the previous session (restored on startup)
+    Session(Session),
     /// App icon clicked (reopen event)
     Reopen,
 }
@@ -35,37 +45,135 @@ pub static OPEN_EVENT_RECEIVER: Mutex<Option<Receiver<OpenEvent>>> = Mutex::new(
 // System event handling
 // ============================================================================
 
+/// Get the README (or index) file to open along with a directory, if enabled
+pub(crate) fn directory_readme(dir: &Path) -> Option<PathBuf> {
+    if !CONFIG.read().directory.open_readme {
+        return None;
+    }
+    find_directory_index(dir)
+}
+
+/// Convert Markdown piped to standard input into an inline document
+fn stdin_document(stdin: StdinDocument) -> InlineDocument {
+    InlineDocument::new(stdin.markdown, InlineSource::Stdin, Some(stdin.base_dir))
+}
+
+/// Build the tabs and sidebar directory of a window for command-line arguments
+///
+/// Standard input and each file become a tab; a directory becomes the sidebar
+/// root (the last one wins) and adds its README tab when enabled.
+fn tabs_for_arguments(args: &CliArgs) -> (Vec<Tab>, Option<PathBuf>) {
+    let mut tabs: Vec<Tab> = args
+        .stdin
+        .clone()
+        .map(|stdin| Tab::with_inline_document(stdin_document(stdin)))
+        .into_iter()
+        .collect();
+    let mut directory = None;
+    for target in &args.targets {
+        if target.path.is_dir() {
+            tabs.extend(directory_readme(&target.path).map(Tab::new));
+            directory = Some(target.path.clone());
+        } else {
+            let mut tab = Tab::new(target.path.clone());
+            tab.pending_anchor = target.anchor.clone();
+            tabs.push(tab);
+        }
+    }
+    (tabs, directory)
+}
+
+/// Build the contents and layout of a window of a saved session
+fn session_window_contents(
+    window: SessionWindow,
+) -> (WindowContents, CreateMainWindowConfigParams) {
+    let contents = WindowContents {
+        tabs: window.tabs.into_iter().map(|tab| tab.into_tab()).collect(),
+        active_tab: window.active_tab,
+        closed_tabs: Default::default(),
+        expanded_dirs: window.expanded_dirs.into_iter().collect(),
+    };
+    (contents, window.layout.into())
+}
+
+/// Open a window for each window of a saved session
+fn restore_session_windows(windows: Vec<SessionWindow>) {
+    for window in windows {
+        spawn(async move {
+            let (contents, params) = session_window_contents(window);
+            window_manager::restore_main_window(contents, params).await;
+        });
+    }
+}
+
 #[tracing::instrument]
 fn handle_open_event(event: OpenEvent) {
     tracing::debug!(?event, "Handling system open event");
 
     match event {
-        OpenEvent::File(file) => {
+        OpenEvent::File(file, anchor) => {
             if window_manager::has_any_main_windows() {
-                let _ = FILE_OPEN_BROADCAST.send(file);
+                let _ = FILE_OPEN_BROADCAST.send((file, anchor));
             } else {
                 spawn(async move {
-                    window_manager::create_new_main_window_with_file(
-                        file,
+                    let mut tab = Tab::new(file);
+                    tab.pending_anchor = anchor;
+                    window_manager::create_new_main_window_with_tabs(
+                        vec![tab],
                         CreateMainWindowConfigParams::default(),
                     )
                     .await;
                 });
             }
         }
+        // A plain launch forwarded from another process behaves like clicking the app icon
+        OpenEvent::Arguments(args) if args.is_empty() => handle_open_event(OpenEvent::Reopen),
+        OpenEvent::Arguments(args) => {
+            // Forwarded invocations come from another application (e.g. a terminal),
+            // so bring the window that receives them to the front
+            let reuse_window = !args.new_window && window_manager::focus_last_focused_main_window();
+            if !reuse_window {
+                spawn(async move {
+                    let (tabs, directory) = tabs_for_arguments(&args);
+                    let mut params = CreateMainWindowConfigParams::default();
+                    args.apply_to(&mut params);
+                    params.directory = directory.or(params.directory);
+                    window_manager::create_new_main_window_with_tabs(tabs, params).await;
+                });
+            } else {
+                // Window options only apply to new windows
+                if let Some(stdin) = args.stdin {
+                    let _ = INLINE_OPEN_BROADCAST.send(stdin_document(stdin));
+                }
+                for target in args.targets {
+                    if target.path.is_dir() {
+                        let _ = DIRECTORY_OPEN_BROADCAST.send(target.path);
+                    } else {
+                        let _ = FILE_OPEN_BROADCAST.send((target.path, target.anchor));
+                    }
+                }
+            }
+        }
         OpenEvent::Directory(dir) => {
             if window_manager::has_any_main_windows() {
                 let _ = DIRECTORY_OPEN_BROADCAST.send(dir);
             } else {
                 spawn(async move {
+                    let readme = directory_readme(&dir);
                     let params = CreateMainWindowConfigParams {
                         directory: Some(dir),
                         ..Default::default()
                     };
-                    window_manager::create_new_main_window_with_empty(params).await;
+                    match readme {
+                        Some(readme) => {
+                            window_manager::create_new_main_window_with_file(readme, params).await
+                        }
+                        None => window_manager::create_new_main_window_with_empty(params).await,
+                    };
                 });
             }
         }
+        OpenEvent::Session(session) => restore_session_windows(session.windows),
         OpenEvent::Reopen => {
             if !window_manager::focus_last_focused_main_window() {
                 spawn(async move {
@@ -125,29 +233,80 @@ pub fn MainApp() -> Element {
         None
     };
 
-    // Resolve initial tab and directory from event
+    // Get initial configuration values
     let is_first_window = true;
-    let (tab, directory_override) = match &first_event {
-        Some(OpenEvent::File(path)) => (Tab::new(path.clone()), None),
-        Some(OpenEvent::Directory(path)) => (Tab::default(), Some(path.clone())),
-        _ => {
-            let welcome_content = crate::assets::get_default_markdown_content();
-            (Tab::with_inline_content(welcome_content), None)
+    let mut params = CreateMainWindowConfigParams::from_preferences(is_first_window);
+
+    // Resolve initial tabs and directory from event
+    let welcome_tab = || Tab::with_inline_content(crate::assets::get_default_markdown_content());
+    let mut restored = WindowContents::default();
+    let mut other_session_windows = Vec::new();
+    let (tab, additional_tabs, directory_override) = match first_event {
+        Some(OpenEvent::File(path, anchor)) => {
+            let mut tab = Tab::new(path);
+            tab.pending_anchor = anchor;
+            (tab, Vec::new(), None)
+        }
+        Some(OpenEvent::Directory(path)) => {
+            let tab = directory_readme(&path).map(Tab::new).unwrap_or_default();
+            (tab, Vec::new(), Some(path))
+        }
+        Some(OpenEvent::Arguments(args)) => {
+            args.apply_to(&mut params);
+            let (mut tabs, directory) = tabs_for_arguments(&args);
+            if tabs.is_empty() {
+                // Only a directory (or only options) was given
+                let tab = if directory.is_some() {
+                    Tab::default()
+                } else {
+                    welcome_tab()
+                };
+                (tab, Vec::new(), directory)
+            } else {
+                let tab = tabs.remove(0);
+                (tab, tabs, directory)
+            }
+        }
+        Some(OpenEvent::Session(session)) => {
+            // The first window shows the first window of the session, the others open later
+            let mut windows = session.windows.into_iter();
+            let (contents, session_params) =
+                session_window_contents(windows.next().unwrap_or_default());
+            other_session_windows = windows.collect();
+            params = session_params;
+            restored = contents;
+            let mut tabs = std::mem::take(&mut restored.tabs);
+            let tab = if tabs.is_empty() {
+                Tab::default()
+            } else {
+                tabs.remove(0)
+            };
+            (tab, tabs, params.directory.clone())
         }
+        _ => (welcome_tab(), Vec::new(), None),
     };
 
-    // Get initial configuration values
-    let directory_pref = settings::get_directory_preference(is_first_window);
-    let theme_pref = settings::get_theme_preference(is_first_window);
-    let sidebar_pref = settings::get_sidebar_preference(is_first_window);
-
     // Directory resolution: override (from event) → config → tab parent → home → root
     let directory = directory_override
-        .or(directory_pref.directory)
+        .or(params.directory.clone())
         .or_else(|| tab.file().and_then(|p| p.parent().map(|p| p.to_path_buf())))
         .or_else(dirs::home_dir)
         .unwrap_or_else(|| PathBuf::from("/"));
 
+    // Open the other windows of the restored session
+    use_hook(|| restore_session_windows(other_session_windows));
+
+    // Keep File > Open Recent in sync with the recently viewed history
+    use_hook(|| {
+        spawn_forever(async move {
+            let mut rx = RECENTLY_VIEWED_CHANGED.subscribe();
+            // Missed notifications (lag) still mean the history changed
+            while let Ok(()) | Err(RecvError::Lagged(_)) = rx.recv().await {
+                crate::menu::update_open_recent_menu();
+            }
+        });
+    });
+
     // Set up system event handler (for subsequent events)
     use_hook(|| {
         spawn_forever(async move {
@@ -161,11 +320,15 @@ pub fn MainApp() -> Element {
     rsx! {
         crate::components::app::App {
             tab: tab,
+            additional_tabs: additional_tabs,
+            active_tab: restored.active_tab,
+            expanded_dirs: restored.expanded_dirs,
             directory: directory,
-            theme: theme_pref.theme,
-            sidebar_open: sidebar_pref.open,
-            sidebar_width: sidebar_pref.width,
-            sidebar_show_all_files: sidebar_pref.show_all_files,
+            theme: params.theme,
+            sidebar_open: params.sidebar_open,
+            sidebar_width: params.sidebar_width,
+            sidebar_show_all_files: params.sidebar_show_all_files,
+            sidebar_panel: params.sidebar_panel,
         }
     }
 }
@@ -1,8 +1,11 @@
 pub mod file_explorer;
+pub mod outline;
 
 use dioxus::document;
 use dioxus::prelude::*;
 
+use crate::components::icon::{Icon, IconName};
+use crate::config::SidebarPanel;
 use crate::state::{AppState, LAST_FOCUSED_STATE};
 
 #[component]
@@ -11,6 +14,7 @@ pub fn Sidebar() -> Element {
     let sidebar_state = state.sidebar.read();
     let is_visible = sidebar_state.open;
     let width = sidebar_state.width;
+    let panel = sidebar_state.panel;
 
     let mut is_resizing = use_signal(|| false);
 
@@ -47,8 +51,13 @@ pub fn Sidebar() -> Element {
             class: if is_resizing() { "resizing" },
             style: "{style}",
 
-            // File explorer content (always mounted for animation)
-            file_explorer::FileExplorer {}
+            PanelSwitcher { panel }
+
+            // Panel content (always mounted for animation)
+            match panel {
+                SidebarPanel::Explorer => rsx! { file_explorer::FileExplorer {} },
+                SidebarPanel::Outline => rsx! { outline::Outline {} },
+            }
 
             // Resize handle (only when visible)
             if is_visible {
@@ -114,3 +123,30 @@ pub fn Sidebar() -> Element {
         }
     }
 }
+
+#[component]
+fn PanelSwitcher(panel: SidebarPanel) -> Element {
+    let mut state = use_context::<AppState>();
+
+    rsx! {
+        div {
+            class: "sidebar-panel-switcher",
+            button {
+                class: "sidebar-panel-tab",
+                class: if panel == SidebarPanel::Explorer { "active" },
+                title: "Files",
+                onclick: move |_| state.set_sidebar_panel(SidebarPanel::Explorer),
+                Icon { name: IconName::Folders, size: 16 }
+                span { "Files" }
+            }
+            button {
+                class: "sidebar-panel-tab",
+                class: if panel == SidebarPanel::Outline { "active" },
+                title: "Outline",
+                onclick: move |_| state.set_sidebar_panel(SidebarPanel::Outline),
+                Icon { name: IconName::ListTree, size: 16 }
+                span { "Outline" }
+            }
+        }
+    }
+}
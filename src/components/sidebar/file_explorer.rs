@@ -1,12 +1,21 @@
+use dioxus::core::Task;
 use dioxus::document;
 use dioxus::prelude::*;
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use std::rc::Rc;
 
 use crate::components::icon::{Icon, IconName};
 use crate::state::AppState;
 use crate::utils::file::is_markdown_file;
+use crate::watcher::FILE_WATCHER;
+
+/// Sorted entries of the watched directories (the root and the expanded directories)
+type DirectoryListings = HashMap<PathBuf, Vec<PathBuf>>;
 
 // Sort entries: directories first, then files, both alphabetically
 fn sort_entries(items: &mut [PathBuf]) {
@@ -37,22 +46,123 @@ fn read_sorted_entries(path: &PathBuf) -> Vec<PathBuf> {
     }
 }
 
+/// Apply changed entries of a directory to its sorted listing
+///
+/// Entries that exist are added if missing and the others are removed, so a
+/// rename (reported as both paths) moves the entry. Returns whether anything changed.
+fn update_listing(entries: &mut Vec<PathBuf>, changed: &[PathBuf]) -> bool {
+    let mut is_updated = false;
+    for path in changed {
+        // Broken symlinks are listed too, so don't follow links
+        let exists = path.symlink_metadata().is_ok();
+        let is_listed = entries.contains(path);
+        if exists && !is_listed {
+            entries.push(path.clone());
+            is_updated = true;
+        } else if !exists && is_listed {
+            entries.retain(|entry| entry != path);
+            is_updated = true;
+        }
+    }
+    if is_updated {
+        sort_entries(entries);
+    }
+    is_updated
+}
+
+/// Get the entries of a directory, from its listing if it is watched
+fn directory_entries(listings: Signal<DirectoryListings>, path: &PathBuf) -> Vec<PathBuf> {
+    match listings.read().get(path) {
+        Some(entries) => entries.clone(),
+        None => read_sorted_entries(path),
+    }
+}
+
+/// Hook to keep the listings of the root and expanded directories in sync with the file system
+///
+/// Each directory is watched while it is shown, and changes only update its own
+/// listing so that the rest of the tree (and its scroll position) is untouched.
+fn use_directory_watches(state: AppState, mut listings: Signal<DirectoryListings>) {
+    let watches = use_hook(|| Rc::new(RefCell::new(HashMap::<PathBuf, Task>::new())));
+
+    use_effect(move || {
+        let root = state.directory.read().clone();
+        let wanted: HashSet<PathBuf> = match root {
+            Some(root) => state
+                .sidebar
+                .read()
+                .expanded_dirs
+                .iter()
+                .filter(|dir| dir.starts_with(&root))
+                .cloned()
+                .chain(std::iter::once(root.clone()))
+                .collect(),
+            None => HashSet::new(),
+        };
+
+        let mut watches = watches.borrow_mut();
+        watches.retain(|dir, task| {
+            let keep = wanted.contains(dir);
+            if !keep {
+                // Dropping the receiver ends the watch
+                task.cancel();
+                listings.write().remove(dir);
+            }
+            keep
+        });
+        for dir in wanted {
+            if let Entry::Vacant(entry) = watches.entry(dir) {
+                let task = spawn(watch_directory(entry.key().clone(), listings));
+                entry.insert(task);
+            }
+        }
+    });
+}
+
+/// Watch a directory and apply its changes to its listing
+async fn watch_directory(dir: PathBuf, mut listings: Signal<DirectoryListings>) {
+    let mut watcher = match FILE_WATCHER.watch_directory(dir.clone()).await {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            tracing::error!(
+                "Failed to register directory watcher for {:?}: {:?}",
+                dir,
+                e
+            );
+            return;
+        }
+    };
+    listings
+        .write()
+        .insert(dir.clone(), read_sorted_entries(&dir));
+
+    while let Some(changed) = watcher.recv().await {
+        let Some(mut entries) = listings.peek().get(&dir).cloned() else {
+            continue;
+        };
+        // Modified files don't change the tree, so skip the re-render
+        if update_listing(&mut entries, &changed) {
+            tracing::debug!("Directory entries changed: {:?}", dir);
+            listings.write().insert(dir.clone(), entries);
+        }
+    }
+}
+
 #[component]
 pub fn FileExplorer() -> Element {
     let state = use_context::<AppState>();
     let root_directory = state.directory.read().clone();
 
-    // Refresh counter to force DirectoryTree re-render
-    let refresh_counter = use_signal(|| 0u32);
+    let listings = use_signal(DirectoryListings::new);
+    use_directory_watches(state, listings);
 
     rsx! {
         div {
             class: "file-explorer",
-            key: "{refresh_counter}",
 
             if let Some(root) = root_directory {
-                ParentNavigation { current_dir: root.clone(), refresh_counter }
-                DirectoryTree { path: root, refresh_counter }
+                ParentNavigation { current_dir: root.clone(), listings }
+                DirectoryTree { path: root, listings }
             } else {
                 div {
                     class: "file-explorer-empty",
@@ -64,9 +174,10 @@ pub fn FileExplorer() -> Element {
 }
 
 #[component]
-fn ParentNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -> Element {
+fn ParentNavigation(current_dir: PathBuf, mut listings: Signal<DirectoryListings>) -> Element {
     let mut state = use_context::<AppState>();
     let show_all_files = state.sidebar.read().show_all_files;
+    let is_following = state.sidebar.read().follow;
 
     let has_parent = current_dir.parent().is_some();
 
@@ -85,8 +196,10 @@ fn ParentNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -> E
         // Set reloading state for animation
         is_reloading_write.set(true);
 
-        // Increment counter to force DirectoryTree re-render
-        refresh_counter.set(refresh_counter() + 1);
+        // Read every watched directory again (in case an event was missed)
+        for (dir, entries) in listings.write().iter_mut() {
+            *entries = read_sorted_entries(dir);
+        }
 
         // Reset reloading state after animation
         spawn(async move {
@@ -158,6 +271,18 @@ fn ParentNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -> E
                     }
                 }
 
+                // Follow directory toggle button
+                button {
+                    class: "file-explorer-toolbar-button",
+                    class: if is_following { "active" },
+                    title: if is_following { "Stop following directory" } else { "Follow directory (open changed files)" },
+                    onclick: move |_| state.toggle_follow_directory(),
+                    Icon {
+                        name: IconName::LiveView,
+                        size: 20,
+                    }
+                }
+
                 // File visibility toggle button
                 button {
                     class: "file-explorer-toolbar-button",
@@ -176,22 +301,21 @@ fn ParentNavigation(current_dir: PathBuf, mut refresh_counter: Signal<u32>) -> E
 }
 
 #[component]
-fn DirectoryTree(path: PathBuf, refresh_counter: Signal<u32>) -> Element {
-    let entries = read_sorted_entries(&path);
+fn DirectoryTree(path: PathBuf, listings: Signal<DirectoryListings>) -> Element {
+    let entries = directory_entries(listings, &path);
 
     rsx! {
         div {
             class: "directory-tree",
-            key: "{refresh_counter}",
             for entry in entries {
-                FileTreeNode { path: entry, depth: 0, refresh_counter }
+                FileTreeNode { key: "{entry.display()}", path: entry, depth: 0, listings }
             }
         }
     }
 }
 
 #[component]
-fn FileTreeNode(path: PathBuf, depth: usize, refresh_counter: Signal<u32>) -> Element {
+fn FileTreeNode(path: PathBuf, depth: usize, listings: Signal<DirectoryListings>) -> Element {
     let mut state = use_context::<AppState>();
 
     let is_dir = path.is_dir();
@@ -320,12 +444,16 @@ fn FileTreeNode(path: PathBuf, depth: usize, refresh_counter: Signal<u32>) -> El
             // Expanded directory children
             if is_dir && is_expanded {
                 {
-                    let children = read_sorted_entries(&path);
+                    let children = directory_entries(listings, &path);
                     rsx! {
                         div {
-                            key: "{refresh_counter}",
                             for child in children {
-                                FileTreeNode { path: child, depth: depth + 1, refresh_counter }
+                                FileTreeNode {
+                                    key: "{child.display()}",
+                                    path: child,
+                                    depth: depth + 1,
+                                    listings,
+                                }
                             }
                         }
                     }
@@ -334,3 +462,40 @@ fn FileTreeNode(path: PathBuf, depth: usize, refresh_counter: Signal<u32>) -> El
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_listing() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.md");
+        let b = dir.path().join("b.md");
+        let sub = dir.path().join("sub");
+        fs::write(&a, "# A").unwrap();
+        let mut entries = read_sorted_entries(&dir.path().to_path_buf());
+        assert_eq!(entries, vec![a.clone()]);
+
+        // Added file and directory (directories first)
+        fs::write(&b, "# B").unwrap();
+        fs::create_dir(&sub).unwrap();
+        assert!(update_listing(&mut entries, &[b.clone(), sub.clone()]));
+        assert_eq!(entries, vec![sub.clone(), a.clone(), b.clone()]);
+
+        // Modified file
+        fs::write(&a, "# A2").unwrap();
+        assert!(!update_listing(&mut entries, std::slice::from_ref(&a)));
+
+        // Renamed file
+        let c = dir.path().join("c.md");
+        fs::rename(&a, &c).unwrap();
+        assert!(update_listing(&mut entries, &[a.clone(), c.clone()]));
+        assert_eq!(entries, vec![sub.clone(), b.clone(), c.clone()]);
+
+        // Removed directory
+        fs::remove_dir(&sub).unwrap();
+        assert!(update_listing(&mut entries, &[sub]));
+        assert_eq!(entries, vec![b, c]);
+    }
+}
@@ -0,0 +1,203 @@
+use dioxus::document;
+use dioxus::prelude::*;
+use std::collections::HashSet;
+
+use crate::components::content::scroll_to_anchor;
+use crate::components::icon::{Icon, IconName};
+use crate::markdown::Heading;
+use crate::state::AppState;
+
+/// Script that reports the id of the heading currently at the top of the content area
+const SCROLL_SPY_SCRIPT: &str = indoc::indoc! {r#"
+    window.artoOutlineSpy?.dispose();
+    let lastId;
+    const update = () => {
+        const container = document.querySelector(".content");
+        if (!container) {
+            return;
+        }
+        const top = container.getBoundingClientRect().top;
+        let current = null;
+        for (const heading of document.querySelectorAll(".markdown-body :is(h1, h2, h3, h4, h5, h6)[id]")) {
+            if (heading.getBoundingClientRect().top - top > 16) {
+                break;
+            }
+            current = heading.id;
+        }
+        if (current !== lastId) {
+            lastId = current;
+            dioxus.send(current);
+        }
+    };
+    const options = { capture: true, passive: true };
+    document.addEventListener("scroll", update, options);
+    window.artoOutlineSpy = {
+        update,
+        dispose: () => document.removeEventListener("scroll", update, options),
+    };
+    update();
+"#};
+
+/// A heading positioned in the outline tree
+struct OutlineEntry {
+    heading: Heading,
+    depth: usize,
+    has_children: bool,
+}
+
+/// Resolve tree depth for each heading and drop descendants of collapsed headings
+fn visible_entries(headings: &[Heading], collapsed: &HashSet<String>) -> Vec<OutlineEntry> {
+    let mut entries = Vec::new();
+    let mut ancestors: Vec<u8> = Vec::new();
+    let mut hidden_below: Option<u8> = None;
+
+    for (index, heading) in headings.iter().enumerate() {
+        while ancestors
+            .last()
+            .is_some_and(|&level| level >= heading.level)
+        {
+            ancestors.pop();
+        }
+        let depth = ancestors.len();
+        ancestors.push(heading.level);
+
+        if let Some(level) = hidden_below {
+            if heading.level > level {
+                continue;
+            }
+            hidden_below = None;
+        }
+        if collapsed.contains(&heading.id) {
+            hidden_below = Some(heading.level);
+        }
+
+        let has_children = headings
+            .get(index + 1)
+            .is_some_and(|next| next.level > heading.level);
+        entries.push(OutlineEntry {
+            heading: heading.clone(),
+            depth,
+            has_children,
+        });
+    }
+
+    entries
+}
+
+#[component]
+pub fn Outline() -> Element {
+    let state = use_context::<AppState>();
+    let headings = state.outline.read().clone();
+    let mut collapsed = use_signal(HashSet::<String>::new);
+    let active_heading = use_active_heading(state);
+
+    let entries = visible_entries(&headings, &collapsed.read());
+    let active_id = active_heading.read().clone();
+
+    rsx! {
+        div {
+            class: "outline",
+
+            if headings.is_empty() {
+                div {
+                    class: "outline-empty",
+                    "No headings"
+                }
+            } else {
+                div {
+                    class: "outline-tree",
+                    for entry in entries {
+                        OutlineItem {
+                            key: "{entry.heading.id}",
+                            is_active: active_id.as_deref() == Some(entry.heading.id.as_str()),
+                            is_collapsed: collapsed.read().contains(&entry.heading.id),
+                            heading: entry.heading,
+                            depth: entry.depth,
+                            has_children: entry.has_children,
+                            on_toggle: move |id: String| {
+                                let mut collapsed = collapsed.write();
+                                if !collapsed.remove(&id) {
+                                    collapsed.insert(id);
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OutlineItem(
+    heading: Heading,
+    depth: usize,
+    has_children: bool,
+    is_collapsed: bool,
+    is_active: bool,
+    on_toggle: EventHandler<String>,
+) -> Element {
+    let indent_style = format!("padding-left: {}px", depth * 12 + 4);
+    let toggle_id = heading.id.clone();
+
+    rsx! {
+        div {
+            class: "outline-node",
+            class: if is_active { "active" },
+            style: "{indent_style}",
+            title: "{heading.text}",
+            onclick: move |_| scroll_to_anchor(&heading.id),
+
+            if has_children {
+                span {
+                    class: "outline-chevron",
+                    onclick: move |evt| {
+                        // Prevent jumping to the heading when only toggling
+                        evt.stop_propagation();
+                        on_toggle.call(toggle_id.clone());
+                    },
+                    Icon {
+                        name: if is_collapsed { IconName::ChevronRight } else { IconName::ChevronDown },
+                        size: 14,
+                    }
+                }
+            } else {
+                span { class: "outline-spacer" }
+            }
+
+            span {
+                class: "outline-label",
+                "{heading.text}"
+            }
+        }
+    }
+}
+
+/// Hook to track the heading currently in view of the content area
+fn use_active_heading(state: AppState) -> Signal<Option<String>> {
+    let mut active_heading = use_signal(|| None::<String>);
+
+    use_hook(|| {
+        spawn(async move {
+            let mut eval = document::eval(SCROLL_SPY_SCRIPT);
+            while let Ok(id) = eval.recv::<Option<String>>().await {
+                active_heading.set(id);
+            }
+        });
+    });
+
+    // Re-evaluate once a new document has been rendered
+    use_effect(move || {
+        let _ = state.outline.read();
+        spawn(async move {
+            let _ = document::eval("requestAnimationFrame(() => window.artoOutlineSpy?.update());")
+                .await;
+        });
+    });
+
+    use_drop(|| {
+        let _ = document::eval("window.artoOutlineSpy?.dispose();");
+    });
+
+    active_heading
+}
@@ -12,13 +12,17 @@ use crate::state::AppState;
 /// Extract display name from a tab's content
 fn get_tab_display_name(tab: &crate::state::Tab) -> String {
     use crate::state::TabContent;
+    if let (TabContent::File(_) | TabContent::Inline(_), Some(title)) = (&tab.content, &tab.title) {
+        return title.clone();
+    }
     match &tab.content {
         TabContent::File(path) | TabContent::FileError(path, _) => path
             .file_name()
             .map(|name| name.to_string_lossy().to_string())
             .unwrap_or_else(|| "Unnamed file".to_string()),
-        TabContent::Inline(_) => "Welcome".to_string(),
+        TabContent::Inline(document) => document.source.label().to_string(),
         TabContent::Preferences => "Preferences".to_string(),
+        TabContent::History => "History".to_string(),
         TabContent::None => "No file".to_string(),
     }
 }
@@ -99,8 +103,8 @@ fn TabItem(index: usize, tab: crate::state::Tab, is_active: bool) -> Element {
                 crate::window::main::create_new_main_window(tab, params).await;
             });
 
-            // Close tab in source window
-            state.close_tab(index);
+            // Remove tab from source window (it lives on in the new window)
+            state.remove_tab(index);
         }
         show_context_menu.set(false);
     };
@@ -153,7 +157,8 @@ fn TabItem(index: usize, tab: crate::state::Tab, is_active: bool) -> Element {
                                 TabTransferResponse::Ack { request_id: id, .. } if id == request_id => {
                                     // Phase 2: Commit - close tab (remove from source)
                                     tracing::info!(?request_id, tab_index = index, "Closing tab in source window");
-                                    state.close_tab(index);
+                                    // The tab was moved, not closed, so it is not remembered for reopening
+                                    state.remove_tab(index);
                                     tracing::info!(?request_id, "Tab transferred successfully");
                                     break;
                                 }
@@ -2,16 +2,28 @@ use serde::{Deserialize, Serialize};
 
 mod behavior;
 mod directory_config;
+mod follow_config;
+mod history_config;
+mod markdown_config;
+mod print_config;
+mod session_config;
 mod sidebar_config;
 mod theme_config;
+mod watcher_config;
 mod window_dimension;
 mod window_position_config;
 mod window_size_config;
 
-pub use behavior::{NewWindowBehavior, StartupBehavior};
+pub use behavior::{NewWindowBehavior, SessionStartupBehavior, StartupBehavior};
 pub use directory_config::DirectoryConfig;
-pub use sidebar_config::SidebarConfig;
+pub use follow_config::FollowConfig;
+pub use history_config::HistoryConfig;
+pub use markdown_config::MarkdownConfig;
+pub use print_config::{PaperSize, PrintConfig};
+pub use session_config::SessionConfig;
+pub use sidebar_config::{SidebarConfig, SidebarPanel};
 pub use theme_config::ThemeConfig;
+pub use watcher_config::WatcherConfig;
 pub use window_dimension::{WindowDimension, WindowDimensionUnit};
 pub use window_position_config::{
     WindowPosition, WindowPositionConfig, WindowPositionMode, WindowPositionOffset,
@@ -25,6 +37,12 @@ pub struct Config {
     pub directory: DirectoryConfig,
     pub theme: ThemeConfig,
     pub sidebar: SidebarConfig,
+    pub markdown: MarkdownConfig,
+    pub print: PrintConfig,
+    pub session: SessionConfig,
+    pub history: HistoryConfig,
+    pub watcher: WatcherConfig,
+    pub follow: FollowConfig,
     pub window_position: WindowPositionConfig,
     pub window_size: WindowSizeConfig,
 }
@@ -34,7 +52,7 @@ mod tests {
     use super::window_position_config::WindowPositionOffset;
     use super::*;
     use crate::theme::Theme;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn test_config_default() {
@@ -47,16 +65,51 @@ mod tests {
 
         // Directory defaults
         assert_eq!(config.directory.default_directory, None);
+        assert!(config.directory.open_readme);
         assert_eq!(config.directory.on_startup, StartupBehavior::Default);
         assert_eq!(config.directory.on_new_window, NewWindowBehavior::Default);
 
+        // Session defaults
+        assert_eq!(config.session.on_startup, SessionStartupBehavior::Default);
+        assert!(!config.session.restores_session());
+
+        // History defaults
+        assert!(config.history.enabled);
+        assert!(config.history.exclude.is_empty());
+
+        // Watcher defaults
+        assert_eq!(config.watcher.debounce_ms, 500);
+        assert!(!config.watcher.polling);
+        assert!(config.watcher.poll_remote);
+
+        // Follow defaults
+        assert!(!config.follow.dedicated_tab);
+        assert!(config.follow.include.is_empty());
+
         // Sidebar defaults
         assert!(!config.sidebar.default_open); // Default is false
         assert_eq!(config.sidebar.default_width, 280.0);
         assert!(!config.sidebar.default_show_all_files);
+        assert_eq!(config.sidebar.default_panel, SidebarPanel::Explorer);
         assert_eq!(config.sidebar.on_startup, StartupBehavior::Default);
         assert_eq!(config.sidebar.on_new_window, NewWindowBehavior::Default);
 
+        // Markdown defaults
+        assert!(config.markdown.show_front_matter);
+        assert_eq!(config.markdown.document_root, None);
+        assert!(config.markdown.trusted_directories.is_empty());
+        assert!(config.markdown.restrict_images_to_root);
+        assert_eq!(config.markdown.max_image_size(), Some(20 * 1024 * 1024));
+        assert!(config
+            .markdown
+            .external_open_extensions
+            .contains(&"pdf".to_string()));
+
+        // Print defaults
+        assert_eq!(config.print.paper_size, PaperSize::A4);
+        assert_eq!(config.print.margin_mm, 15.0);
+        assert!(config.print.force_light_theme);
+
         // Window size defaults
         assert_eq!(config.window_size.default_size.width.value, 1000.0);
         assert_eq!(
@@ -105,6 +158,7 @@ mod tests {
             },
             directory: DirectoryConfig {
                 default_directory: Some(PathBuf::from("/home/user")),
+                open_readme: false,
                 on_startup: StartupBehavior::Default,
                 on_new_window: NewWindowBehavior::Default,
             },
@@ -112,9 +166,41 @@ mod tests {
                 default_open: false,
                 default_width: 320.0,
                 default_show_all_files: true,
+                default_panel: SidebarPanel::Outline,
                 on_startup: StartupBehavior::LastClosed,
                 on_new_window: NewWindowBehavior::LastFocused,
             },
+            markdown: MarkdownConfig {
+                show_front_matter: false,
+                external_open_extensions: vec!["pdf".to_string()],
+                document_root: Some(PathBuf::from("/home/user/notes")),
+                trusted_directories: vec![PathBuf::from("/home/user/notes")],
+                restrict_images_to_root: false,
+                max_image_size_mb: 0,
+            },
+            print: PrintConfig {
+                paper_size: PaperSize::Legal,
+                margin_mm: 10.0,
+                force_light_theme: false,
+            },
+            session: SessionConfig {
+                on_startup: SessionStartupBehavior::RestoreSession,
+            },
+            history: HistoryConfig {
+                enabled: false,
+                exclude: vec!["*.private.md".to_string()],
+            },
+            watcher: WatcherConfig {
+                debounce_ms: 200,
+                polling: true,
+                poll_interval_ms: 5000,
+                poll_remote: false,
+                exclude: vec!["node_modules".to_string()],
+            },
+            follow: FollowConfig {
+                dedicated_tab: true,
+                include: vec!["report-*.md".to_string()],
+            },
             window_position: WindowPositionConfig {
                 default_position: WindowPosition {
                     x: WindowDimension {
@@ -156,8 +242,37 @@ mod tests {
             parsed.directory.default_directory,
             Some(PathBuf::from("/home/user"))
         );
+        assert!(!parsed.directory.open_readme);
         assert!(!parsed.sidebar.default_open);
         assert_eq!(parsed.sidebar.default_width, 320.0);
+        assert_eq!(parsed.sidebar.default_panel, SidebarPanel::Outline);
+        assert!(!parsed.markdown.show_front_matter);
+        assert_eq!(
+            parsed.markdown.document_root,
+            Some(PathBuf::from("/home/user/notes"))
+        );
+        assert_eq!(parsed.markdown.external_open_extensions, vec!["pdf"]);
+        assert!(!parsed.markdown.restrict_images_to_root);
+        assert_eq!(parsed.markdown.max_image_size(), None);
+        assert!(parsed
+            .markdown
+            .is_trusted(Path::new("/home/user/notes/docs/index.md")));
+        assert!(!parsed
+            .markdown
+            .is_trusted(Path::new("/home/user/Downloads/README.md")));
+        assert_eq!(parsed.print.paper_size, PaperSize::Legal);
+        assert_eq!(parsed.print.margin_mm, 10.0);
+        assert!(!parsed.print.force_light_theme);
+        assert!(parsed.session.restores_session());
+        assert!(!parsed.history.enabled);
+        assert_eq!(parsed.history.exclude, vec!["*.private.md"]);
+        assert_eq!(parsed.watcher.debounce_ms, 200);
+        assert!(parsed.watcher.polling);
+        assert_eq!(parsed.watcher.poll_interval_ms, 5000);
+        assert!(!parsed.watcher.poll_remote);
+        assert_eq!(parsed.watcher.exclude, vec!["node_modules"]);
+        assert!(parsed.follow.dedicated_tab);
+        assert_eq!(parsed.follow.include, vec!["report-*.md"]);
         assert_eq!(parsed.window_position.default_position.x.value, 10.0);
         assert_eq!(
             parsed.window_position.default_position.x.unit,
@@ -11,6 +11,17 @@ pub enum StartupBehavior {
     LastClosed,
 }
 
+/// What to show when the application starts without files to open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionStartupBehavior {
+    /// Start with a single window
+    #[default]
+    Default,
+    /// Restore every window and tab of the previous session
+    RestoreSession,
+}
+
 /// Behavior when opening a new window
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -44,6 +55,16 @@ mod tests {
         assert_eq!(parsed_last, StartupBehavior::LastClosed);
     }
 
+    #[test]
+    fn test_session_startup_behavior_serialization() {
+        let restore_session_json =
+            serde_json::to_string(&SessionStartupBehavior::RestoreSession).unwrap();
+        assert_eq!(restore_session_json, r#""restore_session""#);
+
+        let parsed: SessionStartupBehavior = serde_json::from_str(r#""default""#).unwrap();
+        assert_eq!(parsed, SessionStartupBehavior::Default);
+    }
+
     #[test]
     fn test_new_window_behavior_serialization() {
         let default = NewWindowBehavior::Default;
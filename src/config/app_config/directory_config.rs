@@ -2,14 +2,32 @@ use super::behavior::{NewWindowBehavior, StartupBehavior};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
+fn default_open_readme() -> bool {
+    true
+}
+
 /// Configuration for directory-related settings
-#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DirectoryConfig {
     /// Default directory to open
     pub default_directory: Option<PathBuf>,
+    /// Whether opening a directory also opens its README (or index) file in a tab
+    #[serde(default = "default_open_readme")]
+    pub open_readme: bool,
     /// Behavior on app startup: "default" or "last_closed"
     pub on_startup: StartupBehavior,
     /// Behavior when opening a new window: "default" or "last_focused"
     pub on_new_window: NewWindowBehavior,
 }
+
+impl Default for DirectoryConfig {
+    fn default() -> Self {
+        Self {
+            default_directory: None,
+            open_readme: default_open_readme(),
+            on_startup: StartupBehavior::Default,
+            on_new_window: NewWindowBehavior::Default,
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::utils::file::is_markdown_file;
+use crate::utils::glob;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Configuration for following the root directory of the file explorer
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FollowConfig {
+    /// Whether changed documents are shown in one dedicated tab instead of their own tabs
+    pub dedicated_tab: bool,
+    /// Glob patterns of the documents to follow (every Markdown file if empty)
+    pub include: Vec<String>,
+}
+
+impl FollowConfig {
+    /// Check whether a created or modified file is shown
+    pub fn follows(&self, path: &Path) -> bool {
+        is_markdown_file(path)
+            && (self.include.is_empty() || glob::matches_any(&self.include, path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_config_follows() {
+        let config = FollowConfig::default();
+        assert!(!config.dedicated_tab);
+        assert!(config.follows(Path::new("/reports/daily.md")));
+        assert!(!config.follows(Path::new("/reports/daily.json")));
+
+        let config: FollowConfig = serde_json::from_str(r#"{"include":["report-*.md"]}"#).unwrap();
+        assert!(config.follows(Path::new("/reports/report-1.md")));
+        assert!(!config.follows(Path::new("/reports/notes.md")));
+        // Only Markdown files are followed, whatever the patterns
+        assert!(!config.follows(Path::new("/reports/report-1.txt")));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::utils::glob;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Configuration for the app-wide history of recently viewed documents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryConfig {
+    /// Whether viewed documents are remembered
+    pub enabled: bool,
+    /// Glob patterns of documents that are never remembered
+    pub exclude: Vec<String>,
+}
+
+impl HistoryConfig {
+    /// Check whether a visit to the document is remembered
+    pub fn records(&self, path: &Path) -> bool {
+        self.enabled && !glob::matches_any(&self.exclude, path)
+    }
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exclude: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_config_records() {
+        let config: HistoryConfig =
+            serde_json::from_str(r#"{"exclude":["*.private.md","/tmp/**"]}"#).unwrap();
+        assert!(config.enabled);
+        assert!(config.records(Path::new("/docs/notes.md")));
+        assert!(!config.records(Path::new("/docs/notes.private.md")));
+        assert!(!config.records(Path::new("/tmp/scratch.md")));
+
+        let config = HistoryConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(!config.records(Path::new("/docs/notes.md")));
+    }
+}
@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Extensions opened with the system default application by default
+const DEFAULT_EXTERNAL_OPEN_EXTENSIONS: &[&str] = &[
+    "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp", "pages", "numbers",
+    "key", "zip", "mp3", "mp4", "mov", "wav",
+];
+
+/// Configuration for Markdown rendering
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MarkdownConfig {
+    /// Whether front matter is shown as a metadata table above the document
+    pub show_front_matter: bool,
+    /// Extensions of linked files opened with the system default application
+    pub external_open_extensions: Vec<String>,
+    /// Root for links and images with a leading `/` (detected from Git when unset)
+    pub document_root: Option<PathBuf>,
+    /// Directories whose documents may use raw HTML without sanitization
+    pub trusted_directories: Vec<PathBuf>,
+    /// Whether local images must be inside the document root to be shown
+    pub restrict_images_to_root: bool,
+    /// Maximum size of an embedded local image in megabytes (0 for no limit)
+    pub max_image_size_mb: u64,
+}
+
+impl MarkdownConfig {
+    /// Check whether raw HTML of a document is rendered as written
+    pub fn is_trusted(&self, path: &Path) -> bool {
+        self.trusted_directories
+            .iter()
+            .any(|dir| path.starts_with(dir))
+    }
+
+    /// Get the maximum size of an embedded local image in bytes
+    pub fn max_image_size(&self) -> Option<u64> {
+        (self.max_image_size_mb > 0).then(|| self.max_image_size_mb * 1024 * 1024)
+    }
+}
+
+impl Default for MarkdownConfig {
+    fn default() -> Self {
+        Self {
+            show_front_matter: true,
+            external_open_extensions: DEFAULT_EXTERNAL_OPEN_EXTENSIONS
+                .iter()
+                .map(|ext| ext.to_string())
+                .collect(),
+            document_root: None,
+            trusted_directories: Vec::new(),
+            restrict_images_to_root: true,
+            max_image_size_mb: 20,
+        }
+    }
+}
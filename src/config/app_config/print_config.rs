@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Points per millimeter (PostScript points are 1/72 inch)
+const POINTS_PER_MM: f64 = 72.0 / 25.4;
+
+/// Paper size used for printing and PDF export
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaperSize {
+    /// ISO A4 (210 x 297 mm)
+    #[default]
+    A4,
+    /// ISO A5 (148 x 210 mm)
+    A5,
+    /// US Letter (8.5 x 11 in)
+    Letter,
+    /// US Legal (8.5 x 14 in)
+    Legal,
+}
+
+impl PaperSize {
+    /// Get the width and height of the paper in points
+    pub fn size_in_points(self) -> (f64, f64) {
+        match self {
+            Self::A4 => (210.0 * POINTS_PER_MM, 297.0 * POINTS_PER_MM),
+            Self::A5 => (148.0 * POINTS_PER_MM, 210.0 * POINTS_PER_MM),
+            Self::Letter => (612.0, 792.0),
+            Self::Legal => (612.0, 1008.0),
+        }
+    }
+}
+
+/// Configuration for printing and PDF export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PrintConfig {
+    /// Paper size of printed pages
+    pub paper_size: PaperSize,
+    /// Margin on every side of a page in millimeters
+    pub margin_mm: f64,
+    /// Whether documents are printed in the light theme whatever the window theme
+    pub force_light_theme: bool,
+}
+
+impl PrintConfig {
+    /// Get the page margin in points
+    pub fn margin_in_points(&self) -> f64 {
+        self.margin_mm.max(0.0) * POINTS_PER_MM
+    }
+}
+
+impl Default for PrintConfig {
+    fn default() -> Self {
+        Self {
+            paper_size: PaperSize::default(),
+            margin_mm: 15.0,
+            force_light_theme: true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paper_size_in_points() {
+        let (width, height) = PaperSize::A4.size_in_points();
+        assert_eq!((width.round(), height.round()), (595.0, 842.0));
+        assert_eq!(PaperSize::Letter.size_in_points(), (612.0, 792.0));
+    }
+
+    #[test]
+    fn test_print_config_deserialize_partial() {
+        let config: PrintConfig = serde_json::from_str(r#"{"paperSize":"letter"}"#).unwrap();
+        assert_eq!(config.paper_size, PaperSize::Letter);
+        assert_eq!(config.margin_mm, 15.0);
+        assert!(config.force_light_theme);
+        assert_eq!(config.margin_in_points().round(), 43.0);
+    }
+}
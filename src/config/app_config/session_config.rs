@@ -0,0 +1,35 @@
+use super::behavior::SessionStartupBehavior;
+use serde::{Deserialize, Serialize};
+
+/// Configuration for restoring windows and tabs across restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionConfig {
+    /// Behavior on app startup: "default" or "restore_session"
+    pub on_startup: SessionStartupBehavior,
+}
+
+impl SessionConfig {
+    /// Whether the session is saved and restored on the next startup
+    pub fn restores_session(&self) -> bool {
+        self.on_startup == SessionStartupBehavior::RestoreSession
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_config_deserialize() {
+        let config: SessionConfig = serde_json::from_str(r#"{}"#).unwrap();
+        assert!(!config.restores_session());
+
+        let config: SessionConfig =
+            serde_json::from_str(r#"{"onStartup":"restore_session"}"#).unwrap();
+        assert!(config.restores_session());
+
+        // The settings of the last closed window are not a session
+        assert!(serde_json::from_str::<SessionConfig>(r#"{"onStartup":"last_closed"}"#).is_err());
+    }
+}
@@ -8,6 +8,17 @@ fn default_sidebar_width() -> f64 {
     DEFAULT_SIDEBAR_WIDTH
 }
 
+/// Panel shown in the sidebar
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SidebarPanel {
+    /// File explorer of the root directory
+    #[default]
+    Explorer,
+    /// Outline (heading tree) of the active document
+    Outline,
+}
+
 /// Configuration for sidebar-related settings
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -19,6 +30,9 @@ pub struct SidebarConfig {
     pub default_width: f64,
     /// Whether to show all files (including non-markdown) by default
     pub default_show_all_files: bool,
+    /// Panel shown in the sidebar by default
+    #[serde(default)]
+    pub default_panel: SidebarPanel,
     /// Behavior on app startup: "default" or "last_closed"
     pub on_startup: StartupBehavior,
     /// Behavior when opening a new window: "default" or "last_focused"
@@ -31,6 +45,7 @@ impl Default for SidebarConfig {
             default_open: false,
             default_width: default_sidebar_width(),
             default_show_all_files: false,
+            default_panel: SidebarPanel::default(),
             on_startup: StartupBehavior::Default,
             on_new_window: NewWindowBehavior::Default,
         }
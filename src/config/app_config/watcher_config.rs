@@ -0,0 +1,92 @@
+use crate::utils::glob;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// Shortest accepted delay or interval, to keep watching from spinning
+const MIN_INTERVAL_MS: u64 = 50;
+
+/// Configuration for watching documents and directories for changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WatcherConfig {
+    /// Delay in milliseconds for changes to settle before they are reported
+    pub debounce_ms: u64,
+    /// Whether every path is polled instead of relying on file system events
+    pub polling: bool,
+    /// Interval in milliseconds between two polls
+    pub poll_interval_ms: u64,
+    /// Whether paths on network or other non-local file systems are polled
+    pub poll_remote: bool,
+    /// Glob patterns of paths that are never watched
+    pub exclude: Vec<String>,
+}
+
+impl WatcherConfig {
+    /// Get the delay for changes to settle
+    pub fn debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_ms.max(MIN_INTERVAL_MS))
+    }
+
+    /// Get the interval between two polls
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_millis(self.poll_interval_ms.max(MIN_INTERVAL_MS))
+    }
+
+    /// Check whether a path is polled, given whether it is on a non-local file system
+    pub fn polls(&self, is_remote: bool) -> bool {
+        self.polling || (self.poll_remote && is_remote)
+    }
+
+    /// Check whether a path is never watched
+    pub fn excludes(&self, path: &Path) -> bool {
+        glob::matches_any(&self.exclude, path)
+    }
+}
+
+impl Default for WatcherConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 500,
+            polling: false,
+            poll_interval_ms: 2000,
+            poll_remote: true,
+            exclude: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watcher_config_polls() {
+        let config = WatcherConfig::default();
+        assert!(!config.polls(false));
+        assert!(config.polls(true));
+
+        let config = WatcherConfig {
+            poll_remote: false,
+            ..Default::default()
+        };
+        assert!(!config.polls(true));
+
+        let config = WatcherConfig {
+            polling: true,
+            poll_remote: false,
+            ..Default::default()
+        };
+        assert!(config.polls(false));
+    }
+
+    #[test]
+    fn test_watcher_config_intervals_and_excludes() {
+        let config: WatcherConfig =
+            serde_json::from_str(r#"{"debounceMs":0,"exclude":["node_modules"]}"#).unwrap();
+        assert_eq!(config.debounce(), Duration::from_millis(MIN_INTERVAL_MS));
+        assert_eq!(config.poll_interval(), Duration::from_secs(2));
+        assert!(config.excludes(Path::new("/project/node_modules/pkg/README.md")));
+        assert!(!config.excludes(Path::new("/project/README.md")));
+    }
+}
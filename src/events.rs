@@ -12,8 +12,10 @@
 //! │                                                                     │
 //! │  OS Event (Finder/CLI)                                             │
 //! │       │                                                             │
-//! │       ├──→ Event::Opened { urls } ──→ OpenEvent::File(path)        │
+//! │       ├──→ Event::Opened { urls } ──→ OpenEvent::File(path, None)  │
 //! │       ├──→ Event::Opened { urls } ──→ OpenEvent::Directory(path)   │
+//! │       ├──→ std::env::args         ──→ OpenEvent::Arguments(args)   │
+//! │       ├──→ single-instance socket ──→ OpenEvent::Arguments(args)   │
 //! │       └──→ Event::Reopen          ──→ OpenEvent::Reopen            │
 //! │                │                                                    │
 //! │                v                                                    │
@@ -34,7 +36,8 @@
 //! │    - Handle Reopen specially (focus or create)                     │
 //! │    - Broadcast to all windows if needed                            │
 //! │                │                                                    │
-//! │                ├──→ FILE_OPEN_BROADCAST.send(path)                 │
+//! │                ├──→ FILE_OPEN_BROADCAST.send((path, anchor))       │
+//! │                ├──→ INLINE_OPEN_BROADCAST.send(document)           │
 //! │                └──→ DIRECTORY_OPEN_BROADCAST.send(path)            │
 //! │                         │                                           │
 //! │                         v                                           │
@@ -51,13 +54,15 @@
 //! │ File: components/app.rs                                            │
 //! │                                                                     │
 //! │  Each App component:                                               │
-//! │    while let Ok(path) = rx.recv().await {                          │
-//! │        if window().is_focused() {  ← Only focused window handles   │
-//! │            state.open_file(path);                                  │
+//! │    while let Ok((path, anchor)) = rx.recv().await {                │
+//! │        if is_last_focused_main_window(id) {  ← Only one handles    │
+//! │            state.open_file_with_anchor(path, anchor);              │
 //! │        }                                                            │
 //! │    }                                                                │
 //! │                                                                     │
 //! │  Why focus check? Without it, ALL windows would open the file!     │
+//! │  The last focused window is used (not `is_focused`) because        │
+//! │  forwarded invocations arrive while a terminal is in front.        │
 //! └─────────────────────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -77,17 +82,24 @@
 //!
 //! The Entrypoint layer provides this coordination logic before broadcasting to App components.
 
-use crate::state::Tab;
+use crate::state::{InlineDocument, Tab};
 use dioxus::desktop::tao::window::WindowId;
 use std::path::PathBuf;
 use tokio::sync::broadcast;
 
 /// Global broadcast sender for opening files in tabs.
 ///
-/// Distributes file open events from Entrypoint to all App components.
+/// Distributes file open events (with an optional anchor to scroll to) from
+/// Entrypoint to all App components.
 /// Each window's App component subscribes via `FILE_OPEN_BROADCAST.subscribe()`.
-/// Only the focused window should handle the event (checked via `window().is_focused()`).
-pub static FILE_OPEN_BROADCAST: std::sync::LazyLock<broadcast::Sender<PathBuf>> =
+/// Only the last focused window should handle the event (checked via `is_last_focused_main_window`).
+pub static FILE_OPEN_BROADCAST: std::sync::LazyLock<broadcast::Sender<(PathBuf, Option<String>)>> =
+    std::sync::LazyLock::new(|| broadcast::channel(100).0);
+
+/// Global broadcast sender for opening inline markdown (e.g. piped to `arto -`) in tabs.
+///
+/// Like file events, only the last focused window should handle the event.
+pub static INLINE_OPEN_BROADCAST: std::sync::LazyLock<broadcast::Sender<InlineDocument>> =
     std::sync::LazyLock::new(|| broadcast::channel(100).0);
 
 /// Global broadcast sender for opening directories in sidebar.
@@ -169,3 +181,9 @@ pub static TAB_TRANSFER_REQUEST: std::sync::LazyLock<broadcast::Sender<TabTransf
 /// Smaller buffer makes lag issues more obvious during development.
 pub static TAB_TRANSFER_RESPONSE: std::sync::LazyLock<broadcast::Sender<TabTransferResponse>> =
     std::sync::LazyLock::new(|| broadcast::channel(10).0);
+
+/// Global broadcast sender notifying that the recently viewed history changed.
+///
+/// Every window's History page and the File > Open Recent menu subscribe to refresh their lists.
+pub static RECENTLY_VIEWED_CHANGED: std::sync::LazyLock<broadcast::Sender<()>> =
+    std::sync::LazyLock::new(|| broadcast::channel(16).0);
@@ -0,0 +1,373 @@
+//! Standalone HTML pages of rendered documents.
+//!
+//! A page embeds the bundled stylesheet, so it can be opened without Arto or
+//! any other file. Pages rendered headlessly also embed the bundled script,
+//! which renders Mermaid diagrams, math and syntax highlighting in the browser
+//! exactly like the app does (site pages share a single copy of it). Pages
+//! exported from a window instead take those from the webview and need no
+//! JavaScript at all.
+
+mod site;
+
+pub use site::{render_directory, render_file};
+
+use base64::{engine::general_purpose, Engine as _};
+use html_escape::decode_html_entities;
+use lol_html::html_content::{ContentType, Element};
+use lol_html::{element, text, HandlerResult, HtmlRewriter, Settings};
+use serde::Deserialize;
+use std::cell::RefCell;
+
+use crate::assets::{MAIN_SCRIPT_SOURCE, MAIN_STYLE_SOURCE};
+use crate::config::CONFIG;
+use crate::markdown::RenderedMarkdown;
+use crate::theme::{resolve_theme, Theme};
+
+/// Get the title of a page: the front matter title, then the first heading
+pub fn page_title(rendered: &RenderedMarkdown, fallback: &str) -> String {
+    rendered
+        .front_matter_title()
+        .or_else(|| {
+            rendered
+                .headings
+                .first()
+                .map(|heading| heading.text.clone())
+        })
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// Where a standalone page loads the bundled script from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageScript<'a> {
+    /// Embedded in the page as a data URL
+    Embedded,
+    /// A shared copy at a URL relative to the page (see [`MAIN_SCRIPT_FILE`])
+    Shared(&'a str),
+}
+
+/// Path of the shared bundled script in a rendered site
+pub const MAIN_SCRIPT_FILE: &str = "_arto/main.js";
+
+/// Build a standalone HTML page around a rendered document
+pub fn standalone_page(
+    rendered: &RenderedMarkdown,
+    title: &str,
+    theme: Theme,
+    script: PageScript,
+) -> String {
+    // Imported as the bundle is an ES module exporting `init`
+    let url = match script {
+        PageScript::Embedded => format!(
+            "data:text/javascript;base64,{}",
+            general_purpose::STANDARD.encode(MAIN_SCRIPT_SOURCE)
+        ),
+        PageScript::Shared(url) => url.to_string(),
+    };
+    let script = indoc::formatdoc! {r#"
+        const {{ init }} = await import("{url}");
+        init();"#,
+    };
+    page_html(
+        title,
+        &resolve_theme(theme).to_string(),
+        &[MAIN_STYLE_SOURCE],
+        &rendered.document_html(CONFIG.read().markdown.show_front_matter),
+        Some(&script),
+    )
+}
+
+/// An element rendered by the webview (a diagram, math or highlighted code)
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RenderedFragment {
+    /// Source the element was rendered from (its `data-original-content`, or
+    /// the text of code)
+    #[serde(default)]
+    pub source: String,
+    /// Class list of the element after rendering (e.g. `hljs language-rust`)
+    pub class: String,
+    /// Inner HTML of the element after rendering
+    pub html: String,
+}
+
+/// What the webview rendered on top of the HTML of a document
+///
+/// Each list is in document order, and its fragments are matched to the
+/// placeholders of the document by their source.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebviewSnapshot {
+    /// Theme the document is shown in (`light` or `dark`)
+    pub theme: String,
+    /// Stylesheets enabled in the webview (Markdown and code themes)
+    pub styles: Vec<String>,
+    pub mermaid: Vec<RenderedFragment>,
+    pub math_blocks: Vec<RenderedFragment>,
+    pub math_inline: Vec<RenderedFragment>,
+    pub math_display: Vec<RenderedFragment>,
+    pub code: Vec<RenderedFragment>,
+}
+
+/// Build a page that needs no JavaScript from a document and its webview snapshot
+///
+/// The zoom level of the window is not applied, so the page has the layout
+/// of the document at actual size.
+pub fn static_page(body: &str, title: &str, snapshot: &WebviewSnapshot) -> String {
+    let mut stylesheets = vec![MAIN_STYLE_SOURCE];
+    stylesheets.extend(snapshot.styles.iter().map(String::as_str));
+    page_html(
+        title,
+        &snapshot.theme,
+        &stylesheets,
+        &fill_rendered_fragments(body, snapshot),
+        None,
+    )
+}
+
+/// Replace the placeholders of diagrams, math and code with what the webview rendered
+///
+/// Each placeholder takes the first fragment of its kind with the same source,
+/// so a document that differs from the one shown keeps the placeholders that
+/// have no counterpart in the snapshot.
+fn fill_rendered_fragments(body: &str, snapshot: &WebviewSnapshot) -> String {
+    let queues: Vec<(&str, RefCell<Vec<&RenderedFragment>>)> = [
+        ("pre.preprocessed-mermaid", &snapshot.mermaid),
+        ("pre.preprocessed-math", &snapshot.math_blocks),
+        ("span.preprocessed-math-inline", &snapshot.math_inline),
+        ("div.preprocessed-math-display", &snapshot.math_display),
+        (CODE_SELECTOR, &snapshot.code),
+    ]
+    .into_iter()
+    .map(|(selector, fragments)| (selector, RefCell::new(fragments.iter().collect())))
+    .collect();
+    let code_sources = RefCell::new(code_sources(body).into_iter());
+
+    let mut output = Vec::new();
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: queues
+                .iter()
+                .map(|(selector, queue)| {
+                    element!(selector, |el| {
+                        let source = if el.tag_name() == "code" {
+                            code_sources.borrow_mut().next()
+                        } else {
+                            el.get_attribute("data-original-content")
+                                .map(|source| decode_html_entities(&source).into_owned())
+                        };
+                        fill_matching(el, source.as_deref(), queue)
+                    })
+                })
+                .collect(),
+            ..Settings::default()
+        },
+        |chunk: &[u8]| output.extend_from_slice(chunk),
+    );
+    if rewriter.write(body.as_bytes()).is_err() || rewriter.end().is_err() {
+        return body.to_string();
+    }
+    String::from_utf8(output).unwrap_or_else(|_| body.to_string())
+}
+
+/// Selector of highlighted code blocks
+const CODE_SELECTOR: &str = "pre > code";
+
+/// Get the text of each code block in document order
+fn code_sources(body: &str) -> Vec<String> {
+    let sources = RefCell::new(Vec::new());
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![
+                element!(CODE_SELECTOR, |_| {
+                    sources.borrow_mut().push(String::new());
+                    Ok(())
+                }),
+                text!(CODE_SELECTOR, |chunk| {
+                    if let Some(source) = sources.borrow_mut().last_mut() {
+                        source.push_str(chunk.as_str());
+                    }
+                    Ok(())
+                }),
+            ],
+            ..Settings::default()
+        },
+        |_: &[u8]| {},
+    );
+    if rewriter.write(body.as_bytes()).is_err() {
+        return Vec::new();
+    }
+    if rewriter.end().is_err() {
+        return Vec::new();
+    }
+    sources
+        .into_inner()
+        .iter()
+        .map(|source| decode_html_entities(source).into_owned())
+        .collect()
+}
+
+/// Fill an element with the first fragment of its kind rendered from the same source
+fn fill_matching(
+    el: &mut Element,
+    source: Option<&str>,
+    queue: &RefCell<Vec<&RenderedFragment>>,
+) -> HandlerResult {
+    let Some(source) = source else {
+        return Ok(());
+    };
+    let mut queue = queue.borrow_mut();
+    if let Some(index) = queue.iter().position(|fragment| fragment.source == source) {
+        let fragment = queue.remove(index);
+        el.set_attribute("class", &fragment.class)?;
+        el.set_inner_content(&fragment.html, ContentType::Html);
+    }
+    Ok(())
+}
+
+/// Lay out a page like the content area of a window
+fn page_html(
+    title: &str,
+    theme: &str,
+    stylesheets: &[&str],
+    body: &str,
+    script: Option<&str>,
+) -> String {
+    let title = html_escape::encode_text(title);
+    let theme = html_escape::encode_double_quoted_attribute(theme);
+    let styles: String = stylesheets
+        .iter()
+        .map(|css| format!("<style>{css}</style>\n"))
+        .collect();
+    let script = script
+        .map(|script| format!("<script type=\"module\">\n{script}\n</script>\n"))
+        .unwrap_or_default();
+    indoc::formatdoc! {r#"
+        <!DOCTYPE html>
+        <html>
+        <head>
+        <meta charset="utf-8">
+        <meta name="viewport" content="width=device-width, initial-scale=1.0">
+        <meta name="generator" content="Arto">
+        <title>{title}</title>
+        {styles}<style>body {{ overflow: auto; }}</style>
+        </head>
+        <body data-theme="{theme}">
+        <div class="content">
+        <div class="markdown-viewer">
+        <article class="markdown-body">
+        {body}
+        </article>
+        </div>
+        </div>
+        {script}</body>
+        </html>
+    "#}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::{render_markdown_with_options, Heading, RenderOptions};
+    use std::path::Path;
+
+    #[test]
+    fn test_page_title() {
+        let mut rendered = RenderedMarkdown::default();
+        assert_eq!(page_title(&rendered, "README"), "README");
+
+        rendered.headings.push(Heading {
+            level: 1,
+            id: "guide".to_string(),
+            text: "Guide".to_string(),
+        });
+        assert_eq!(page_title(&rendered, "README"), "Guide");
+    }
+
+    #[test]
+    fn test_standalone_page() {
+        let rendered = RenderedMarkdown {
+            html: "<p>Hello</p>".to_string(),
+            ..RenderedMarkdown::default()
+        };
+        let page = standalone_page(&rendered, "A <b> & C", Theme::Dark, PageScript::Embedded);
+
+        assert!(page.starts_with("<!DOCTYPE html>"));
+        assert!(page.contains("<title>A &lt;b&gt; &amp; C</title>"));
+        assert!(page.contains(r#"<body data-theme="dark">"#));
+        assert!(page.contains("<p>Hello</p>"));
+        assert!(page.contains("data:text/javascript;base64,"));
+
+        let page = standalone_page(
+            &rendered,
+            "A",
+            Theme::Dark,
+            PageScript::Shared("../_arto/main.js"),
+        );
+        assert!(page.contains(r#"await import("../_arto/main.js");"#));
+        assert!(!page.contains("data:text/javascript"));
+    }
+
+    #[test]
+    fn test_fill_rendered_fragments_matches_sources() {
+        let fragment = |source: &str, html: &str| RenderedFragment {
+            source: source.to_string(),
+            class: "preprocessed-math-inline".to_string(),
+            html: html.to_string(),
+        };
+        // The snapshot is of an older version of the document
+        let snapshot = WebviewSnapshot {
+            math_inline: vec![
+                fragment("x", "X"),
+                fragment("a < b", "A"),
+                fragment("z", "Z"),
+            ],
+            code: vec![RenderedFragment {
+                source: "if a < b {}\n".to_string(),
+                class: "hljs".to_string(),
+                html: "<b>if</b> a &lt; b {}".to_string(),
+            }],
+            ..WebviewSnapshot::default()
+        };
+        let body = concat!(
+            r#"<span class="preprocessed-math-inline" data-original-content="a &lt; b">a</span>"#,
+            r#"<span class="preprocessed-math-inline" data-original-content="y">y</span>"#,
+            r#"<span class="preprocessed-math-inline" data-original-content="x">x</span>"#,
+            "<pre><code>if a &lt; b {}\n</code></pre>",
+        );
+
+        assert_eq!(
+            fill_rendered_fragments(body, &snapshot),
+            concat!(
+                r#"<span class="preprocessed-math-inline" data-original-content="a &lt; b">A</span>"#,
+                r#"<span class="preprocessed-math-inline" data-original-content="y">y</span>"#,
+                r#"<span class="preprocessed-math-inline" data-original-content="x">X</span>"#,
+                r#"<pre><code class="hljs"><b>if</b> a &lt; b {}</code></pre>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_static_page_matches_fixture() {
+        let markdown = include_str!("export/fixtures/document.md");
+        let snapshot: WebviewSnapshot =
+            serde_json::from_str(include_str!("export/fixtures/snapshot.json")).unwrap();
+        let options = RenderOptions {
+            static_links: true,
+            ..RenderOptions::default()
+        };
+        let rendered =
+            render_markdown_with_options(markdown, Path::new("/docs/document.md"), &options)
+                .unwrap();
+
+        // The bundled stylesheet is left out as it is only built with the app
+        let mut stylesheets = vec!["/* main.css */"];
+        stylesheets.extend(snapshot.styles.iter().map(String::as_str));
+        let page = page_html(
+            &page_title(&rendered, "document"),
+            &snapshot.theme,
+            &stylesheets,
+            &fill_rendered_fragments(&rendered.document_html(true), &snapshot),
+            None,
+        );
+        assert_eq!(page, include_str!("export/fixtures/document.html"));
+    }
+}
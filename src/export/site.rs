@@ -0,0 +1,270 @@
+use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use super::{page_title, standalone_page, PageScript, MAIN_SCRIPT_FILE};
+use crate::assets::MAIN_SCRIPT_SOURCE;
+use crate::config::CONFIG;
+use crate::markdown::{self, render_markdown_with_options, RenderOptions, RenderedMarkdown};
+use crate::theme::Theme;
+use crate::utils::file::is_markdown_file;
+
+/// Broken local references found while rendering
+#[derive(Debug, Default)]
+pub struct RenderReport {
+    /// Number of pages rendered
+    pub pages: usize,
+    /// Links whose target does not exist (or is outside the rendered directory),
+    /// with the document they appear in
+    pub broken_links: Vec<(PathBuf, String)>,
+    /// Images that could not be embedded, with the document they appear in
+    pub broken_images: Vec<(PathBuf, String)>,
+}
+
+impl RenderReport {
+    /// Check whether every local link and image was found
+    pub fn is_ok(&self) -> bool {
+        self.broken_links.is_empty() && self.broken_images.is_empty()
+    }
+}
+
+/// Build the render options of a page like the app does for the file
+///
+/// Links are kept as anchors to the exported pages. `document_root` is used
+/// for leading-slash links, falling back to the one the app would use.
+fn render_options_for(file: &Path, document_root: Option<&Path>) -> RenderOptions {
+    let config = CONFIG.read().markdown.clone();
+    let document_root = document_root
+        .map(Path::to_path_buf)
+        .or_else(|| markdown::document_root_for(file, &config));
+    RenderOptions {
+        static_links: true,
+        ..RenderOptions::for_document(file, document_root, &config)
+    }
+}
+
+/// Render a Markdown file to a standalone page, recording its broken references
+fn render_page(
+    file: &Path,
+    document_root: Option<&Path>,
+    theme: Theme,
+    script: PageScript,
+    report: &mut RenderReport,
+) -> Result<(String, RenderedMarkdown)> {
+    let markdown = std::fs::read_to_string(file)
+        .with_context(|| format!("failed to read {}", file.display()))?;
+    let options = render_options_for(file, document_root);
+    let rendered = render_markdown_with_options(&markdown, file, &options)
+        .with_context(|| format!("failed to render {}", file.display()))?;
+
+    let fallback = file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let page = standalone_page(&rendered, &page_title(&rendered, &fallback), theme, script);
+
+    report.pages += 1;
+    for link in &rendered.broken_links {
+        report.broken_links.push((file.to_path_buf(), link.clone()));
+    }
+    for image in &rendered.broken_images {
+        report
+            .broken_images
+            .push((file.to_path_buf(), image.clone()));
+    }
+    Ok((page, rendered))
+}
+
+/// Render a single Markdown file to a standalone page
+///
+/// The page embeds the bundled script, so it is the only file needed.
+pub fn render_file(file: &Path, theme: Theme) -> Result<(String, RenderReport)> {
+    let mut report = RenderReport::default();
+    let (page, _) = render_page(file, None, theme, PageScript::Embedded, &mut report)?;
+    Ok((page, report))
+}
+
+/// Render every Markdown file under `input` to an `.html` page under `output`
+///
+/// The directory structure is kept, and `input` is the root of leading-slash
+/// links. Hidden files and directories are skipped. Other local files linked
+/// from the pages are copied next to them, and the pages share one copy of the
+/// bundled script.
+pub fn render_directory(input: &Path, output: &Path, theme: Theme) -> Result<RenderReport> {
+    let input = input
+        .canonicalize()
+        .with_context(|| format!("failed to resolve {}", input.display()))?;
+    let mut report = RenderReport::default();
+    let mut assets = BTreeSet::new();
+    for file in markdown_files(&input)? {
+        let relative = file.strip_prefix(&input).unwrap_or(&file);
+        let depth = relative.components().count().saturating_sub(1);
+        let script = format!("{}{}", "../".repeat(depth), MAIN_SCRIPT_FILE);
+        let (page, rendered) = render_page(
+            &file,
+            Some(&input),
+            theme,
+            PageScript::Shared(&script),
+            &mut report,
+        )?;
+        for (link, target) in rendered.links {
+            if !target.starts_with(&input) {
+                // The page would point outside the rendered site
+                report.broken_links.push((file.clone(), link));
+            } else if target.is_file() && !is_markdown_file(&target) {
+                assets.insert(target);
+            }
+        }
+
+        let destination = output.join(relative).with_extension("html");
+        write_file(&destination, page)?;
+        tracing::debug!(?file, ?destination, "Rendered page");
+    }
+
+    for asset in assets {
+        let destination = output.join(asset.strip_prefix(&input).unwrap_or(&asset));
+        if let Some(parent) = destination.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        std::fs::copy(&asset, &destination)
+            .with_context(|| format!("failed to copy {}", asset.display()))?;
+        tracing::debug!(?asset, ?destination, "Copied linked file");
+    }
+    if report.pages > 0 {
+        write_file(&output.join(MAIN_SCRIPT_FILE), MAIN_SCRIPT_SOURCE)?;
+    }
+    Ok(report)
+}
+
+/// Write a file, creating its parent directories
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("failed to write {}", path.display()))
+}
+
+/// List the Markdown files under a directory in a stable order
+///
+/// Symbolic links to directories are skipped, so that links pointing back up
+/// the tree don't recurse forever and pages outside the directory stay out.
+fn markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries: Vec<_> = std::fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            // The type of the entry itself, without following symbolic links
+            let is_dir = entry.file_type().ok()?.is_dir();
+            Some((entry.path(), is_dir))
+        })
+        .filter(|(path, _)| {
+            !path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+        })
+        .collect();
+    entries.sort();
+
+    let mut files = Vec::new();
+    for (path, is_dir) in entries {
+        if is_dir {
+            files.extend(markdown_files(&path)?);
+        } else if is_markdown_file(&path) && !path.is_dir() {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("docs");
+        let output = temp_dir.path().join("site");
+        std::fs::create_dir_all(input.join("guide")).unwrap();
+        std::fs::create_dir_all(input.join(".hidden")).unwrap();
+        std::fs::write(temp_dir.path().join("outside.md"), "# Outside").unwrap();
+        std::fs::write(
+            input.join("README.md"),
+            "# Home\n\n[Setup](guide/setup.md) [Outside](../outside.md)",
+        )
+        .unwrap();
+        std::fs::write(
+            input.join("guide/setup.md"),
+            "# Setup\n\n[Home](/README.md) [Gone](gone.md) [Report](report.pdf) ![Logo](logo.png)",
+        )
+        .unwrap();
+        std::fs::write(input.join("guide/report.pdf"), "%PDF").unwrap();
+        std::fs::write(input.join(".hidden/secret.md"), "# Secret").unwrap();
+        std::fs::write(input.join("notes.txt"), "not markdown").unwrap();
+
+        let report = render_directory(&input, &output, Theme::Light).unwrap();
+
+        assert_eq!(report.pages, 2);
+        let home = std::fs::read_to_string(output.join("README.html")).unwrap();
+        assert!(home.contains("<title>Home</title>"));
+        assert!(home.contains(r#"<a href="guide/setup.html">"#));
+        assert!(home.contains(r#"await import("_arto/main.js");"#));
+        let setup = std::fs::read_to_string(output.join("guide/setup.html")).unwrap();
+        assert!(setup.contains(r#"<a href="../README.html">"#));
+        assert!(setup.contains(r#"await import("../_arto/main.js");"#));
+        assert!(!setup.contains(&input.to_string_lossy().to_string()));
+        assert!(!output.join(".hidden").exists());
+
+        // Linked files are copied, unlinked ones are not
+        assert_eq!(
+            std::fs::read_to_string(output.join("guide/report.pdf")).unwrap(),
+            "%PDF"
+        );
+        assert!(!output.join("notes.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(output.join(MAIN_SCRIPT_FILE)).unwrap(),
+            MAIN_SCRIPT_SOURCE
+        );
+
+        let input = input.canonicalize().unwrap();
+        let setup_md = input.join("guide/setup.md");
+        assert_eq!(
+            report.broken_links,
+            vec![
+                (input.join("README.md"), "../outside.md".to_string()),
+                (setup_md.clone(), "gone.md".to_string()),
+            ]
+        );
+        assert_eq!(
+            report.broken_images,
+            vec![(setup_md, "logo.png".to_string())]
+        );
+        assert!(!report.is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_render_directory_skips_symlinked_directories() {
+        let temp_dir = TempDir::new().unwrap();
+        let input = temp_dir.path().join("docs");
+        let output = temp_dir.path().join("site");
+        let elsewhere = temp_dir.path().join("elsewhere");
+        std::fs::create_dir_all(&input).unwrap();
+        std::fs::create_dir_all(&elsewhere).unwrap();
+        std::fs::write(input.join("README.md"), "# Home").unwrap();
+        std::fs::write(elsewhere.join("private.md"), "# Private").unwrap();
+        // A link back up the tree and a link leaving it
+        std::os::unix::fs::symlink("..", input.join("self")).unwrap();
+        std::os::unix::fs::symlink(&elsewhere, input.join("elsewhere")).unwrap();
+
+        let report = render_directory(&input, &output, Theme::Light).unwrap();
+
+        assert_eq!(report.pages, 1);
+        assert!(output.join("README.html").exists());
+        assert!(!output.join("self").exists());
+        assert!(!output.join("elsewhere").exists());
+    }
+}
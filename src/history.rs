@@ -1,9 +1,63 @@
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
+/// A document in the navigation history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "StoredHistoryEntry")]
+pub struct HistoryEntry {
+    pub path: PathBuf,
+    /// Scroll offset of the document when it was left
+    pub scroll_top: f64,
+    /// Fragment the document was opened at (e.g. a heading id)
+    pub anchor: Option<String>,
+}
+
+/// A saved history entry, also read from sessions listing bare paths
+#[derive(Deserialize)]
+#[serde(untagged, rename_all_fields = "camelCase")]
+enum StoredHistoryEntry {
+    Path(PathBuf),
+    Entry {
+        path: PathBuf,
+        #[serde(default)]
+        scroll_top: f64,
+        #[serde(default)]
+        anchor: Option<String>,
+    },
+}
+
+impl From<StoredHistoryEntry> for HistoryEntry {
+    fn from(entry: StoredHistoryEntry) -> Self {
+        match entry {
+            StoredHistoryEntry::Path(path) => Self::new(path, None),
+            StoredHistoryEntry::Entry {
+                path,
+                scroll_top,
+                anchor,
+            } => Self {
+                path,
+                scroll_top,
+                anchor,
+            },
+        }
+    }
+}
+
+impl HistoryEntry {
+    fn new(path: PathBuf, anchor: Option<String>) -> Self {
+        Self {
+            path,
+            scroll_top: 0.0,
+            anchor,
+        }
+    }
+}
+
 /// Manages navigation history for markdown files
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct HistoryManager {
-    history: Vec<PathBuf>,
+    history: Vec<HistoryEntry>,
     current_index: usize,
 }
 
@@ -25,6 +79,11 @@ impl HistoryManager {
     /// Push a new file to the history
     /// Clears forward history if not at the end
     pub fn push(&mut self, path: impl Into<PathBuf>) {
+        self.push_with_anchor(path, None);
+    }
+
+    /// Push a new file opened at an anchor to the history
+    pub fn push_with_anchor(&mut self, path: impl Into<PathBuf>, anchor: Option<String>) {
         let path = path.into();
         // Don't add duplicate if it's the same as current
         if let Some(current) = self.current() {
@@ -33,19 +92,27 @@ impl HistoryManager {
             }
         }
 
+        let entry = HistoryEntry::new(path, anchor);
         if self.history.is_empty() {
             // First item
-            self.history.push(path);
+            self.history.push(entry);
             self.current_index = 0;
         } else {
             // Remove all items after current index (forward history)
             self.history.truncate(self.current_index + 1);
-            // Add new path
-            self.history.push(path);
+            // Add new entry
+            self.history.push(entry);
             self.current_index += 1;
         }
     }
 
+    /// Remember the scroll offset of the current document
+    pub fn set_scroll_top(&mut self, scroll_top: f64) {
+        if let Some(entry) = self.history.get_mut(self.current_index) {
+            entry.scroll_top = scroll_top;
+        }
+    }
+
     /// Check if we can go back
     pub fn can_go_back(&self) -> bool {
         self.current_index > 0
@@ -74,12 +141,42 @@ impl HistoryManager {
         None
     }
 
+    /// Jump directly to the entry at index, returns its path
+    pub fn go_to(&mut self, index: usize) -> Option<&Path> {
+        if index >= self.history.len() {
+            return None;
+        }
+        self.current_index = index;
+        self.current()
+    }
+
+    /// Point the entries of a file that was moved to its new path
+    pub fn rename(&mut self, from: &Path, to: &Path) {
+        for entry in &mut self.history {
+            if entry.path == from {
+                entry.path = to.to_path_buf();
+            }
+        }
+    }
+
+    /// Get all entries, oldest first
+    pub fn entries(&self) -> &[HistoryEntry] {
+        &self.history
+    }
+
+    /// Get the index of the current entry
+    pub fn current_index(&self) -> usize {
+        self.current_index
+    }
+
     /// Get the current file path
     pub fn current(&self) -> Option<&Path> {
-        if !self.history.is_empty() {
-            return self.history.get(self.current_index).map(|v| v.as_path());
-        }
-        None
+        self.current_entry().map(|entry| entry.path.as_path())
+    }
+
+    /// Get the current history entry
+    pub fn current_entry(&self) -> Option<&HistoryEntry> {
+        self.history.get(self.current_index)
     }
 
     /// Get the history length
@@ -200,4 +297,82 @@ mod tests {
 
         assert_eq!(manager.len(), 1);
     }
+
+    #[test]
+    fn test_go_to() {
+        let mut manager = HistoryManager::new();
+        manager.push("/test/file1.md");
+        manager.push("/test/file2.md");
+        manager.push("/test/file3.md");
+
+        assert_eq!(manager.go_to(0), Some(Path::new("/test/file1.md")));
+        assert_eq!(manager.current_index(), 0);
+        assert!(manager.can_go_forward());
+        // Jumping keeps the forward entries
+        assert_eq!(manager.entries().len(), 3);
+
+        assert_eq!(manager.go_to(3), None);
+        assert_eq!(manager.current_index(), 0);
+    }
+
+    #[test]
+    fn test_rename() {
+        let mut manager = HistoryManager::new();
+        manager.push("/test/file1.md");
+        manager.push("/test/file2.md");
+        manager.push("/test/file1.md");
+
+        manager.rename(Path::new("/test/file1.md"), Path::new("/test/moved.md"));
+
+        let paths: Vec<&Path> = manager.entries().iter().map(|e| e.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("/test/moved.md"),
+                Path::new("/test/file2.md"),
+                Path::new("/test/moved.md")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scroll_top_is_kept_per_entry() {
+        let mut manager = HistoryManager::new();
+        manager.push("/test/file1.md");
+        manager.set_scroll_top(300.0);
+        manager.push_with_anchor("/test/file2.md", Some("usage".to_string()));
+
+        let entry = manager.current_entry().unwrap();
+        assert_eq!(entry.scroll_top, 0.0);
+        assert_eq!(entry.anchor.as_deref(), Some("usage"));
+
+        manager.go_back();
+        assert_eq!(manager.current_entry().unwrap().scroll_top, 300.0);
+    }
+
+    #[test]
+    fn test_history_serialization() {
+        let mut manager = HistoryManager::new();
+        manager.push("/test/file1.md");
+        manager.set_scroll_top(120.0);
+        manager.push_with_anchor("/test/file2.md", Some("install".to_string()));
+        manager.go_back();
+
+        let json = serde_json::to_string(&manager).unwrap();
+        let parsed: HistoryManager = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, manager);
+        assert!(parsed.can_go_forward());
+    }
+
+    #[test]
+    fn test_history_reads_bare_paths() {
+        // Sessions saved before entries had a scroll offset
+        let json = r#"{"history":["/test/file1.md","/test/file2.md"],"currentIndex":1}"#;
+        let parsed: HistoryManager = serde_json::from_str(json).unwrap();
+
+        assert_eq!(parsed.current(), Some(Path::new("/test/file2.md")));
+        assert_eq!(parsed.current_entry().unwrap().scroll_top, 0.0);
+        assert!(parsed.can_go_back());
+    }
 }
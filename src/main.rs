@@ -1,10 +1,14 @@
 mod assets;
+mod cli;
 mod components;
 mod config;
 mod events;
+mod export;
 mod history;
 mod markdown;
 mod menu;
+mod single_instance;
+mod source;
 mod state;
 mod theme;
 mod utils;
@@ -23,23 +27,62 @@ const DEFAULT_LOGLEVEL: &str = if cfg!(debug_assertions) {
 };
 
 fn main() {
+    let args = parse_command_line();
+
     // Load environment variables from .env file
     if let Ok(dotenv) = dotenvy::dotenv() {
         println!("Loaded .env file from: {}", dotenv.display());
     }
     init_tracing();
 
+    // Hand the invocation over to the running instance, if any
+    let socket = single_instance::socket_path();
+    if !args.standalone && single_instance::forward_to_running_instance(&socket, &args) {
+        tracing::info!("Forwarded to the running instance");
+        return;
+    }
+
     // Create event channel and store receiver for MainApp
-    let (tx, rx) = channel::<components::main_app::OpenEvent>(10);
+    let (tx, rx) = channel::<components::main_app::OpenEvent>(100);
     components::main_app::OPEN_EVENT_RECEIVER
         .lock()
         .expect("Failed to lock OPEN_EVENT_RECEIVER")
         .replace(rx);
 
+    // Receive invocations forwarded by later launches
+    let is_listening = !args.standalone && {
+        let tx = tx.clone();
+        let result = single_instance::listen(&socket, move |args| {
+            let _ = tx.blocking_send(components::main_app::OpenEvent::Arguments(args));
+        });
+        if let Err(e) = &result {
+            tracing::warn!("Single-instance mode is disabled: {e}");
+        }
+        result.is_ok()
+    };
+
     let menu = menu::build_menu();
 
-    // Get window parameters for first window from preferences
-    let params = window::CreateMainWindowConfigParams::from_preferences(true);
+    // Get window parameters for first window from preferences and command-line options
+    let mut params = window::CreateMainWindowConfigParams::from_preferences(true);
+    args.apply_to(&mut params);
+
+    // Restore the previous session when launched without files or options
+    let session = (args.is_empty() && config::CONFIG.read().session.restores_session())
+        .then(state::Session::load)
+        .flatten();
+    if let Some(session) = session {
+        // The first window of the session becomes the first window of the app
+        params = session.windows[0].layout.clone().into();
+        tx.try_send(components::main_app::OpenEvent::Session(session))
+            .expect("Failed to send session");
+    }
+
+    // Files and directories from the command line are consumed by MainApp like Finder events
+    if !args.is_empty() {
+        tx.try_send(components::main_app::OpenEvent::Arguments(args))
+            .expect("Failed to send command-line arguments");
+    }
 
     let config = window::create_main_window_config(&params)
         .with_custom_event_handler(move |event, _target| match event {
@@ -49,7 +92,7 @@ fn main() {
                         let open_event = if path.is_dir() {
                             components::main_app::OpenEvent::Directory(path)
                         } else if path.is_file() {
-                            components::main_app::OpenEvent::File(path)
+                            components::main_app::OpenEvent::File(path, None)
                         } else {
                             // Skip invalid paths
                             continue;
@@ -69,6 +112,14 @@ fn main() {
             } => {
                 window::update_last_focused_window(*window_id);
             }
+            Event::LoopDestroyed => {
+                // Windows still open when quitting make up the session
+                state::end_session();
+                state::flush_recently_viewed();
+                if is_listening {
+                    single_instance::remove_socket(&socket);
+                }
+            }
             _ => {}
         })
         .with_menu(menu);
@@ -80,6 +131,81 @@ fn main() {
         .launch(components::main_app::MainApp);
 }
 
+/// Parse the command line, exiting for `--help`, `--version` and invalid arguments
+fn parse_command_line() -> cli::CliArgs {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mut args = match cli::parse_args(std::env::args().skip(1), &cwd) {
+        Ok(cli::CliCommand::Open(args)) => args,
+        Ok(cli::CliCommand::Render(args)) => std::process::exit(render_command(&args)),
+        Ok(cli::CliCommand::Help) => {
+            print!("{}", cli::USAGE);
+            std::process::exit(0);
+        }
+        Ok(cli::CliCommand::Version) => {
+            println!("arto {}", env!("CARGO_PKG_VERSION"));
+            std::process::exit(0);
+        }
+        Err(e) => {
+            eprintln!("arto: {e}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    for path in args.take_missing_targets() {
+        eprintln!("arto: {}: No such file or directory", path.display());
+    }
+    // Read before forwarding, as the running instance cannot see our standard input
+    if let Err(e) = args.read_stdin_from(std::io::stdin().lock(), &cwd) {
+        eprintln!("arto: failed to read standard input: {e}");
+        std::process::exit(1);
+    }
+    args
+}
+
+/// Render Markdown to standalone HTML without starting the application
+///
+/// Returns the exit status: 1 for broken local links or images, 2 for errors.
+fn render_command(args: &cli::RenderArgs) -> i32 {
+    let result = if args.input.is_dir() {
+        // The output is required for directories (checked by the parser)
+        let output = args.output.as_deref().unwrap_or(std::path::Path::new("."));
+        export::render_directory(&args.input, output, args.theme)
+    } else {
+        export::render_file(&args.input, args.theme).and_then(|(page, report)| {
+            match &args.output {
+                Some(output) => std::fs::write(output, page)?,
+                None => print!("{page}"),
+            }
+            Ok(report)
+        })
+    };
+    let report = match result {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("arto: {e:#}");
+            return 2;
+        }
+    };
+
+    if let Some(output) = args.output.as_ref().filter(|_| args.input.is_dir()) {
+        eprintln!(
+            "arto: rendered {} pages to {}",
+            report.pages,
+            output.display()
+        );
+    }
+    for (file, link) in &report.broken_links {
+        eprintln!("{}: broken link '{link}'", file.display());
+    }
+    for (file, image) in &report.broken_images {
+        eprintln!("{}: broken image '{image}'", file.display());
+    }
+    if report.is_ok() {
+        0
+    } else {
+        1
+    }
+}
+
 fn init_tracing() {
     let silence_filter = tracing_subscriber::filter::filter_fn(|metadata| {
         // Filter out specific error from dioxus_core::properties:136
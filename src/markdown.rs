@@ -1,11 +1,194 @@
+mod front_matter;
+mod sanitize;
+mod slug;
+
 use anyhow::Result;
 use base64::{engine::general_purpose, Engine as _};
+use lol_html::html_content::ContentType;
 use lol_html::{element, HtmlRewriter, Settings};
-use pulldown_cmark::{html, CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
-use std::path::{Path, PathBuf};
+use pulldown_cmark::{
+    html, BlockQuoteKind, CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd,
+};
+use std::cell::RefCell;
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::MarkdownConfig;
+use crate::utils::file::find_git_root;
+pub use front_matter::FrontMatter;
+use sanitize::{is_safe_url, sanitize_html, LINK_SCHEMES};
+use slug::Slugger;
+
+/// A heading of a rendered document (used for the outline)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heading {
+    /// Heading level (1-6)
+    pub level: u8,
+    /// Anchor id assigned to the heading element
+    pub id: String,
+    /// Plain text of the heading
+    pub text: String,
+}
+
+/// Result of rendering a Markdown document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RenderedMarkdown {
+    /// Rendered HTML fragment
+    pub html: String,
+    /// Headings in document order
+    pub headings: Vec<Heading>,
+    /// Front matter stripped from the top of the document
+    pub front_matter: Option<FrontMatter>,
+    /// Local link targets that do not exist (only checked with `static_links`)
+    pub broken_links: Vec<String>,
+    /// Local images that could not be embedded
+    pub broken_images: Vec<String>,
+    /// Local files embedded in the HTML (e.g. images), to re-render when they change
+    pub resources: Vec<PathBuf>,
+    /// Existing local link targets as written, with their canonical path
+    /// (only collected with `static_links`)
+    pub links: Vec<(String, PathBuf)>,
+}
+
+impl RenderedMarkdown {
+    /// Get the document title declared in the front matter
+    pub fn front_matter_title(&self) -> Option<String> {
+        self.front_matter
+            .as_ref()
+            .and_then(|front_matter| front_matter.title())
+            .map(str::to_string)
+    }
+
+    /// Build the HTML to display, prepending the front matter table if shown
+    pub fn document_html(&self, show_front_matter: bool) -> String {
+        match &self.front_matter {
+            Some(front_matter) if show_front_matter => {
+                format!("{}{}", front_matter.to_html(), self.html)
+            }
+            _ => self.html.clone(),
+        }
+    }
+}
+
+/// Default maximum size of an embedded local image (20 MB)
+pub const DEFAULT_MAX_IMAGE_SIZE: u64 = 20 * 1024 * 1024;
+
+/// Which local images may be embedded in a document
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum ImageAccess {
+    /// Images inside the directory of the document
+    #[default]
+    DocumentDirectory,
+    /// Images inside the given directory (e.g. the document root)
+    Within(PathBuf),
+    /// Any local image (only when explicitly allowed for trusted sources)
+    Unrestricted,
+}
+
+/// Options controlling how a document is rendered
+///
+/// The default is the restricted policy for untrusted documents: raw HTML is
+/// sanitized and only reasonably sized images next to the document are embedded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// Root directory used to resolve links and images with a leading `/`
+    /// (like the repository root on GitHub)
+    pub document_root: Option<PathBuf>,
+    /// Pass raw HTML through without sanitization (for trusted documents)
+    pub trust_html: bool,
+    /// Local images that may be embedded
+    pub images: ImageAccess,
+    /// Maximum size in bytes of an embedded local image (unlimited if `None`)
+    pub max_image_size: Option<u64>,
+    /// Keep local links as anchors for pages viewed outside the app, pointing
+    /// Markdown targets at their `.html` page (the app handles clicks instead)
+    pub static_links: bool,
+}
+
+impl RenderOptions {
+    /// Build the render options of a Markdown file from the configuration
+    ///
+    /// `document_root` is the root of leading-slash links (see [`document_root_for`]).
+    pub fn for_document(
+        file: &Path,
+        document_root: Option<PathBuf>,
+        config: &MarkdownConfig,
+    ) -> Self {
+        let images = if config.restrict_images_to_root {
+            // Without a document root, images are limited to the document's directory
+            document_root
+                .clone()
+                .map_or(ImageAccess::DocumentDirectory, ImageAccess::Within)
+        } else {
+            ImageAccess::Unrestricted
+        };
+        Self {
+            document_root,
+            trust_html: config.is_trusted(file),
+            images,
+            max_image_size: config.max_image_size(),
+            static_links: false,
+        }
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            document_root: None,
+            trust_html: false,
+            images: ImageAccess::default(),
+            max_image_size: Some(DEFAULT_MAX_IMAGE_SIZE),
+            static_links: false,
+        }
+    }
+}
+
+/// Resolve the root for leading-slash links of a document from the configuration
+///
+/// The configured root wins if it contains the path, then the enclosing Git repository.
+pub fn document_root_for(path: &Path, config: &MarkdownConfig) -> Option<PathBuf> {
+    config
+        .document_root
+        .clone()
+        .filter(|root| path.starts_with(root))
+        .or_else(|| find_git_root(path))
+}
+
+/// Why a local image could not be embedded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImageError {
+    NotFound,
+    OutsideRoot,
+    TooLarge(u64),
+}
+
+impl std::fmt::Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "Image not found"),
+            Self::OutsideRoot => write!(f, "Image outside the document root"),
+            Self::TooLarge(size) => write!(
+                f,
+                "Image too large to embed ({:.1} MB)",
+                *size as f64 / (1024.0 * 1024.0)
+            ),
+        }
+    }
+}
 
 /// Render Markdown to HTML
+#[cfg(test)]
 pub fn render_to_html(markdown: impl AsRef<str>, base_path: impl AsRef<Path>) -> Result<String> {
+    render_markdown_with_options(markdown, base_path, &RenderOptions::default())
+        .map(|rendered| rendered.html)
+}
+
+/// Render Markdown to HTML with the given options
+pub fn render_markdown_with_options(
+    markdown: impl AsRef<str>,
+    base_path: impl AsRef<Path>,
+    render_options: &RenderOptions,
+) -> Result<RenderedMarkdown> {
     let markdown = markdown.as_ref();
     let base_path = base_path.as_ref();
 
@@ -18,23 +201,73 @@ pub fn render_to_html(markdown: impl AsRef<str>, base_path: impl AsRef<Path>) ->
         .map(|p| p.to_path_buf())
         .unwrap_or_else(|| PathBuf::from("."));
 
-    // Process GitHub alerts
-    let processed_markdown = process_github_alerts(markdown);
+    // Strip front matter so it is not rendered as a rule and paragraph
+    let (front_matter, markdown) = front_matter::extract_front_matter(markdown);
 
     // Parse Markdown and process blocks
-    let parser = Parser::new_ext(&processed_markdown, options);
+    let mut headings = Vec::new();
+    let parser = Parser::new_ext(markdown, options);
+    let parser = sanitize_raw_html(parser, !render_options.trust_html);
+    let parser = process_github_alerts(parser);
+    let parser = process_headings(parser, &mut headings);
     let parser = process_code_blocks(parser, "mermaid");
     let parser = process_code_blocks(parser, "math");
     let parser = process_math_expressions(parser);
+    let parser = process_page_breaks(parser);
 
     // Convert to HTML
     let mut html_output = String::new();
     html::push_html(&mut html_output, parser);
 
     // Post-process HTML to handle all img and anchor tags (both from Markdown syntax and HTML tags)
-    let html_output = post_process_html_tags(&html_output, base_dir.as_path());
+    let processed = post_process_html_tags(&html_output, base_dir.as_path(), render_options);
+
+    Ok(RenderedMarkdown {
+        html: processed.html,
+        headings,
+        front_matter,
+        broken_links: processed.broken_links,
+        broken_images: processed.broken_images,
+        resources: processed.resources,
+        links: processed.links,
+    })
+}
 
-    Ok(html_output)
+/// Sanitize raw HTML written in the document
+///
+/// Runs before the other passes so the HTML they generate is left untouched.
+/// The lines of an HTML block are sanitized together so elements spanning
+/// several lines are handled as a whole.
+fn sanitize_raw_html<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+    enabled: bool,
+) -> impl Iterator<Item = Event<'a>> {
+    let mut block: Option<String> = None;
+
+    parser.flat_map(move |event| match event {
+        _ if !enabled => vec![event],
+        Event::Start(Tag::HtmlBlock) => {
+            block = Some(String::new());
+            vec![event]
+        }
+        Event::Html(html) if block.is_some() => {
+            if let Some(block) = block.as_mut() {
+                block.push_str(&html);
+            }
+            vec![]
+        }
+        Event::End(TagEnd::HtmlBlock) => {
+            let html = block.take().unwrap_or_default();
+            // Page break markers are kept for `process_page_breaks`
+            if is_page_break_comment(&html) {
+                return vec![Event::Html(html.into()), event];
+            }
+            vec![Event::Html(sanitize_html(&html).into()), event]
+        }
+        Event::Html(html) => vec![Event::Html(sanitize_html(&html).into())],
+        Event::InlineHtml(html) => vec![Event::InlineHtml(sanitize_html(&html).into())],
+        _ => vec![event],
+    })
 }
 
 /// Get SVG icon placeholder for alert type (actual SVG injected by JavaScript)
@@ -45,99 +278,108 @@ fn get_alert_icon_placeholder(alert_type: &str) -> String {
     )
 }
 
-/// Check if a line starts a GitHub alert and return alert info
-fn parse_alert_start(line: &str) -> Option<(&'static str, &'static str, &str)> {
-    const ALERT_TYPES: [(&str, &str); 5] = [
-        ("NOTE", "note"),
-        ("TIP", "tip"),
-        ("IMPORTANT", "important"),
-        ("WARNING", "warning"),
-        ("CAUTION", "caution"),
-    ];
-
-    for &(alert_name, alert_class) in &ALERT_TYPES {
-        if let Some(rest) = line.strip_prefix(&format!("> [!{}]", alert_name)) {
-            return Some((alert_name, alert_class, rest));
-        }
-    }
-    None
-}
-
-/// Process a single alert block and return HTML lines and next index
-fn process_alert_block(
-    lines: &[&str],
-    start_index: usize,
-    alert_name: &str,
-    alert_class: &str,
-    first_line_content: &str,
-) -> (Vec<String>, usize) {
-    let mut html_lines = Vec::new();
-
-    // Alert opening tag
-    html_lines.push(format!(
-        r#"<div class="markdown-alert markdown-alert-{}" dir="auto">"#,
-        alert_class
-    ));
-
-    // Alert title with icon
-    let icon_placeholder = get_alert_icon_placeholder(alert_class);
-    html_lines.push(format!(
-        r#"<p class="markdown-alert-title" dir="auto">{}{}</p>"#,
-        icon_placeholder, alert_name
-    ));
-
-    // Collect alert content as markdown
-    let mut content_lines = Vec::new();
-    if !first_line_content.trim().is_empty() {
-        content_lines.push(first_line_content.trim().to_string());
-    }
-
-    // Collect following quoted lines
-    let mut i = start_index + 1;
-    while i < lines.len() && lines[i].starts_with('>') {
-        if let Some(content) = lines[i].strip_prefix('>') {
-            // Preserve the structure by keeping leading space after '>'
-            content_lines.push(content.trim_start().to_string());
-        }
-        i += 1;
+/// Get the display name and CSS class of a GitHub alert kind
+fn alert_kind_info(kind: BlockQuoteKind) -> (&'static str, &'static str) {
+    match kind {
+        BlockQuoteKind::Note => ("NOTE", "note"),
+        BlockQuoteKind::Tip => ("TIP", "tip"),
+        BlockQuoteKind::Important => ("IMPORTANT", "important"),
+        BlockQuoteKind::Warning => ("WARNING", "warning"),
+        BlockQuoteKind::Caution => ("CAUTION", "caution"),
     }
+}
 
-    // Render the collected content as markdown
-    if !content_lines.is_empty() {
-        let content_markdown = content_lines.join("\n");
-        let options = Options::all();
-        let parser = Parser::new_ext(&content_markdown, options);
-        let mut content_html = String::new();
-        html::push_html(&mut content_html, parser);
-        html_lines.push(content_html);
-    }
+/// Process GitHub alerts (`> [!NOTE]` blockquotes reported by the parser)
+///
+/// Alerts are rendered in place, so they nest inside lists and share the
+/// document's link reference definitions and footnotes.
+fn process_github_alerts<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+) -> impl Iterator<Item = Event<'a>> {
+    parser.map(|event| match event {
+        Event::Start(Tag::BlockQuote(Some(kind))) => {
+            let (alert_name, alert_class) = alert_kind_info(kind);
+            let icon_placeholder = get_alert_icon_placeholder(alert_class);
+            Event::Html(
+                format!(
+                    r#"<div class="markdown-alert markdown-alert-{}" dir="auto"><p class="markdown-alert-title" dir="auto">{}{}</p>"#,
+                    alert_class, icon_placeholder, alert_name
+                )
+                .into(),
+            )
+        }
+        Event::End(TagEnd::BlockQuote(Some(_))) => Event::Html("</div>\n".into()),
+        _ => event,
+    })
+}
 
-    html_lines.push("</div>".to_string());
+/// Assign GitHub-compatible anchor ids to headings and collect them into `headings`
+///
+/// Headings with an explicit id (`# Title {#custom}`) keep it as-is.
+fn process_headings<'a, 'h>(
+    parser: impl Iterator<Item = Event<'a>> + 'h,
+    headings: &'h mut Vec<Heading>,
+) -> impl Iterator<Item = Event<'a>> + 'h
+where
+    'a: 'h,
+{
+    let mut slugger = Slugger::default();
+    let mut heading_events: Vec<Event<'a>> = Vec::new();
+    let mut in_heading = false;
 
-    (html_lines, i)
+    parser.flat_map(move |event| match event {
+        Event::Start(Tag::Heading { .. }) => {
+            in_heading = true;
+            heading_events.clear();
+            heading_events.push(event);
+            vec![]
+        }
+        Event::End(TagEnd::Heading(_)) if in_heading => {
+            in_heading = false;
+            let mut events = std::mem::take(&mut heading_events);
+            let text = extract_plain_text(&events[1..]);
+            if let Some(Event::Start(Tag::Heading { level, id, .. })) = events.first_mut() {
+                let id = id.get_or_insert_with(|| slugger.slug(&text).into());
+                headings.push(Heading {
+                    level: heading_level_to_u8(*level),
+                    id: id.to_string(),
+                    text: text.trim().to_string(),
+                });
+            }
+            events.push(event);
+            events
+        }
+        _ if in_heading => {
+            heading_events.push(event);
+            vec![]
+        }
+        _ => vec![event],
+    })
 }
 
-/// Process GitHub alert format
-fn process_github_alerts(markdown: &str) -> String {
-    let lines: Vec<&str> = markdown.lines().collect();
-    let mut result = Vec::new();
-    let mut i = 0;
-
-    while i < lines.len() {
-        let line = lines[i];
+/// Convert a pulldown-cmark heading level to its numeric value
+fn heading_level_to_u8(level: HeadingLevel) -> u8 {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
 
-        if let Some((alert_name, alert_class, rest)) = parse_alert_start(line) {
-            let (alert_html, next_index) =
-                process_alert_block(&lines, i, alert_name, alert_class, rest);
-            result.extend(alert_html);
-            i = next_index;
-        } else {
-            result.push(line.to_string());
-            i += 1;
+/// Concatenate the textual content of inline events (used for heading slugs)
+fn extract_plain_text(events: &[Event]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            Event::Text(t) | Event::Code(t) | Event::InlineMath(t) => text.push_str(t),
+            Event::SoftBreak | Event::HardBreak => text.push(' '),
+            _ => {}
         }
     }
-
-    result.join("\n")
+    text
 }
 
 /// Process Code blocks
@@ -159,11 +401,12 @@ fn process_code_blocks<'a>(
         Event::End(TagEnd::CodeBlock) if in_block => {
             in_block = false;
             // Store original content in data attribute for JavaScript processing
+            // (the body is escaped too, as it is emitted after raw HTML is sanitized)
             let html = format!(
                 r#"<pre class="preprocessed-{}" data-original-content="{}">{}</pre>"#,
                 target_lang,
                 html_escape::encode_double_quoted_attribute(&content),
-                &content,
+                html_escape::encode_text(&content),
             );
             vec![Event::Html(html.into())]
         }
@@ -184,8 +427,8 @@ fn process_math_expressions<'a>(
             // Convert inline math to custom HTML structure
             let html = format!(
                 r#"<span class="preprocessed-math-inline" data-original-content="{}">{}</span>"#,
+                html_escape::encode_double_quoted_attribute(&content),
                 html_escape::encode_text(&content),
-                &content,
             );
             Event::Html(html.into())
         }
@@ -193,8 +436,8 @@ fn process_math_expressions<'a>(
             // Convert display math to custom HTML structure
             let html = format!(
                 r#"<div class="preprocessed-math-display" data-original-content="{}">{}</div>"#,
+                html_escape::encode_double_quoted_attribute(&content),
                 html_escape::encode_text(&content),
-                &content,
             );
             Event::Html(html.into())
         }
@@ -202,8 +445,51 @@ fn process_math_expressions<'a>(
     })
 }
 
+/// Element a page break marker is replaced with (styled by the print stylesheet)
+const PAGE_BREAK_HTML: &str = "<div class=\"page-break\"></div>\n";
+
+/// Check whether an HTML block is a `<!-- pagebreak -->` marker
+fn is_page_break_comment(html: &str) -> bool {
+    html.trim()
+        .strip_prefix("<!--")
+        .and_then(|rest| rest.strip_suffix("-->"))
+        .is_some_and(|comment| comment.trim().eq_ignore_ascii_case("pagebreak"))
+}
+
+/// Process page breaks (`<!-- pagebreak -->` blocks and `\newpage` paragraphs)
+fn process_page_breaks<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+) -> impl Iterator<Item = Event<'a>> {
+    // Events of a paragraph that may turn out to be `\newpage`
+    let mut pending: Vec<Event<'a>> = Vec::new();
+
+    parser.flat_map(move |event| {
+        match (pending.len(), &event) {
+            (0, Event::Html(html)) if is_page_break_comment(html) => {
+                return vec![Event::Html(PAGE_BREAK_HTML.into())];
+            }
+            (0, Event::Start(Tag::Paragraph)) => {
+                pending.push(event);
+                return vec![];
+            }
+            (1, Event::Text(text)) if text.trim() == "\\newpage" => {
+                pending.push(event);
+                return vec![];
+            }
+            (2, Event::End(TagEnd::Paragraph)) => {
+                pending.clear();
+                return vec![Event::Html(PAGE_BREAK_HTML.into())];
+            }
+            _ => {}
+        }
+        let mut events = std::mem::take(&mut pending);
+        events.push(event);
+        events
+    })
+}
+
 /// Infer MIME type from file extension
-fn get_mime_type(path: &Path) -> &'static str {
+pub fn get_mime_type(path: &Path) -> &'static str {
     match path.extension().and_then(|e| e.to_str()) {
         Some("png") => "image/png",
         Some("jpg") | Some("jpeg") => "image/jpeg",
@@ -212,64 +498,297 @@ fn get_mime_type(path: &Path) -> &'static str {
         Some("webp") => "image/webp",
         Some("bmp") => "image/bmp",
         Some("ico") => "image/x-icon",
+        Some("avif") => "image/avif",
         _ => "image/png", // Default
     }
 }
 
+/// Check if a link points to a local file or an in-document anchor
+///
+/// Links with a URL scheme (`https:`, `mailto:`, ...) or protocol-relative
+/// links (`//host/path`) are left untouched.
+fn is_local_link(href: &str) -> bool {
+    if href.is_empty() || href.starts_with("//") {
+        return false;
+    }
+    let has_scheme = href.split_once(':').is_some_and(|(scheme, _)| {
+        // A single letter is a Windows drive (e.g. "C:"), not a scheme
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    !has_scheme
+}
+
+/// Quote a string as a JavaScript string literal safe inside an HTML attribute
+///
+/// Anything other than plain path characters is written as a `\u{...}` escape,
+/// so quotes, backslashes and `&` cannot end the string or the attribute.
+fn js_string_literal(value: &str) -> String {
+    let mut literal = String::with_capacity(value.len() + 2);
+    literal.push('\'');
+    for c in value.chars() {
+        if c.is_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '#' | '~' | ' ' | '+' | ',') {
+            literal.push(c);
+        } else {
+            literal.push_str(&format!("\\u{{{:x}}}", c as u32));
+        }
+    }
+    literal.push('\'');
+    literal
+}
+
+/// Resolve a local link or image path against the document
+///
+/// Paths with a leading `/` are resolved against the document root (if any),
+/// other paths against the directory of the document.
+pub fn resolve_local_path(base_dir: &Path, document_root: Option<&Path>, path: &str) -> PathBuf {
+    match (path.strip_prefix('/'), document_root) {
+        (Some(root_relative), Some(root)) => root.join(root_relative),
+        _ => base_dir.join(path),
+    }
+}
+
+/// Load a local image as a data URL, enforcing the image access and size limit
+///
+/// Both paths are canonicalized, so symlinks pointing outside the root are rejected.
+fn load_image_data_url(
+    path: &Path,
+    base_dir: &Path,
+    render_options: &RenderOptions,
+) -> Result<String, ImageError> {
+    let canonical_path = path.canonicalize().map_err(|_| ImageError::NotFound)?;
+    let image_root = match &render_options.images {
+        ImageAccess::DocumentDirectory => Some(base_dir),
+        ImageAccess::Within(root) => Some(root.as_path()),
+        ImageAccess::Unrestricted => None,
+    };
+    if let Some(root) = image_root {
+        let canonical_root = root.canonicalize().map_err(|_| ImageError::OutsideRoot)?;
+        if !canonical_path.starts_with(&canonical_root) {
+            return Err(ImageError::OutsideRoot);
+        }
+    }
+    let metadata = std::fs::metadata(&canonical_path).map_err(|_| ImageError::NotFound)?;
+    if !metadata.is_file() {
+        return Err(ImageError::NotFound);
+    }
+    if let Some(max_size) = render_options.max_image_size {
+        if metadata.len() > max_size {
+            return Err(ImageError::TooLarge(metadata.len()));
+        }
+    }
+    let image_data = std::fs::read(&canonical_path).map_err(|_| ImageError::NotFound)?;
+    let mime_type = get_mime_type(&canonical_path);
+    let base64_data = general_purpose::STANDARD.encode(&image_data);
+    Ok(format!("data:{};base64,{}", mime_type, base64_data))
+}
+
+/// Build the placeholder shown instead of an image that could not be embedded
+fn broken_image_html(src: &str, alt: Option<&str>, error: ImageError) -> String {
+    let alt = alt
+        .filter(|alt| !alt.is_empty())
+        .map(|alt| {
+            format!(
+                r#"<span class="broken-image-alt">{}</span>"#,
+                html_escape::encode_text(alt)
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        r#"<span class="broken-image" title="{src_attr}"><span class="broken-image-message">{error}</span>{alt}<code class="broken-image-path">{src}</code></span>"#,
+        src_attr = html_escape::encode_double_quoted_attribute(src),
+        src = html_escape::encode_text(src),
+    )
+}
+
+/// Build the placeholder shown instead of a local image above the size limit
+pub fn too_large_image_html(src: &str, size: u64) -> String {
+    broken_image_html(src, None, ImageError::TooLarge(size))
+}
+
+/// Result of post-processing the rendered HTML
+struct ProcessedHtml {
+    html: String,
+    broken_links: Vec<String>,
+    broken_images: Vec<String>,
+    resources: Vec<PathBuf>,
+    links: Vec<(String, PathBuf)>,
+}
+
+/// Make a path absolute and drop its `.` and `..` components without touching the file system
+fn normalize_path(path: &Path) -> PathBuf {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Get the path of `to` relative to the directory `from_dir`, with `/` separators
+fn relative_path(from_dir: &Path, to: &Path) -> String {
+    let from_dir = normalize_path(from_dir);
+    let to = normalize_path(to);
+    let common = from_dir
+        .components()
+        .zip(to.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut parts = vec!["..".to_string(); from_dir.components().count() - common];
+    parts.extend(
+        to.components()
+            .skip(common)
+            .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+    );
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// Point a local link at the page it is exported to
+///
+/// Markdown files become their `.html` page and leading-slash paths are
+/// resolved like in the app (against the document root, or the file system
+/// root without one) and made relative to the document, so the links work
+/// from any location.
+fn static_link_href(
+    path: &str,
+    fragment: Option<&str>,
+    base_dir: &Path,
+    render_options: &RenderOptions,
+) -> String {
+    let mut href = if path.starts_with('/') {
+        let target = resolve_local_path(base_dir, render_options.document_root.as_deref(), path);
+        let mut href = relative_path(base_dir, &target);
+        if path.ends_with('/') {
+            href.push('/');
+        }
+        href
+    } else {
+        path.to_string()
+    };
+    let is_markdown = Path::new(&href)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext.to_ascii_lowercase().as_str(), "md" | "markdown"));
+    if is_markdown {
+        let stem_len = href.rfind('.').unwrap_or(href.len());
+        href.truncate(stem_len);
+        href.push_str(".html");
+    }
+    if let Some(fragment) = fragment {
+        href.push('#');
+        href.push_str(fragment);
+    }
+    href
+}
+
 /// Post-process HTML to handle img and anchor tags using lol_html
-fn post_process_html_tags(html_str: &str, base_dir: &Path) -> String {
-    let base_dir = base_dir.to_path_buf();
+fn post_process_html_tags(
+    html_str: &str,
+    base_dir: &Path,
+    render_options: &RenderOptions,
+) -> ProcessedHtml {
+    let broken_links = RefCell::new(Vec::new());
+    let broken_images = RefCell::new(Vec::new());
+    let resources = RefCell::new(Vec::new());
+    let links = RefCell::new(Vec::new());
     let mut output = Vec::new();
 
     let mut rewriter = HtmlRewriter::new(
         Settings {
             element_content_handlers: vec![
-                // Process img tags: convert relative paths to data URLs
-                element!("img[src]", move |el| {
-                    if let Some(src) = el.get_attribute("src") {
-                        if !src.starts_with("http://")
-                            && !src.starts_with("https://")
-                            && !src.starts_with("data:")
-                        {
-                            let absolute_path = base_dir.join(&src);
-                            if let Ok(canonical_path) = absolute_path.canonicalize() {
-                                if let Ok(image_data) = std::fs::read(&canonical_path) {
-                                    let mime_type = get_mime_type(&canonical_path);
-                                    let base64_data = general_purpose::STANDARD.encode(&image_data);
-                                    let data_url =
-                                        format!("data:{};base64,{}", mime_type, base64_data);
-                                    el.set_attribute("src", &data_url)?;
-                                }
+                // Process img tags: convert local paths to data URLs
+                element!("img[src]", |el| {
+                    let Some(src) = el.get_attribute("src") else {
+                        return Ok(());
+                    };
+                    if !is_local_link(&src) {
+                        return Ok(());
+                    }
+                    let src = html_escape::decode_html_entities(&src).into_owned();
+                    let absolute_path =
+                        resolve_local_path(base_dir, render_options.document_root.as_deref(), &src);
+                    match load_image_data_url(&absolute_path, base_dir, render_options) {
+                        Ok(data_url) => {
+                            el.set_attribute("src", &data_url)?;
+                            let path = absolute_path.canonicalize().unwrap_or(absolute_path);
+                            let mut resources = resources.borrow_mut();
+                            if !resources.contains(&path) {
+                                resources.push(path);
                             }
                         }
+                        Err(error) => {
+                            tracing::debug!(?absolute_path, %error, "Image not embedded");
+                            let alt = el
+                                .get_attribute("alt")
+                                .map(|alt| html_escape::decode_html_entities(&alt).into_owned());
+                            el.replace(
+                                &broken_image_html(&src, alt.as_deref(), error),
+                                ContentType::Html,
+                            );
+                            broken_images.borrow_mut().push(src);
+                        }
                     }
                     Ok(())
                 }),
-                // Process anchor tags: convert markdown links to spans
+                // Process anchor tags: convert local links and in-document anchors to spans
+                // (how the target is opened is decided when the link is clicked)
                 element!("a[href]", |el| {
                     if let Some(href) = el.get_attribute("href") {
-                        if !href.starts_with("http://") && !href.starts_with("https://") {
-                            if let Some(ext) = std::path::Path::new(&href)
-                                .extension()
-                                .and_then(|e| e.to_str())
-                            {
+                        if is_local_link(&href) {
+                            // The attribute value may contain entities
+                            let href = html_escape::decode_html_entities(&href);
+                            if render_options.static_links {
+                                let (path, fragment) = match href.split_once('#') {
+                                    Some((path, fragment)) => (path, Some(fragment)),
+                                    None => (href.as_ref(), None),
+                                };
+                                // In-document links (e.g. "#install") work as they are
+                                if !path.is_empty() {
+                                    let target = resolve_local_path(
+                                        base_dir,
+                                        render_options.document_root.as_deref(),
+                                        path,
+                                    );
+                                    match target.canonicalize() {
+                                        Ok(target) => {
+                                            links.borrow_mut().push((href.to_string(), target));
+                                        }
+                                        Err(_) => broken_links.borrow_mut().push(href.to_string()),
+                                    }
+                                    let static_href =
+                                        static_link_href(path, fragment, base_dir, render_options);
+                                    el.set_attribute("href", &static_href)?;
+                                }
+                            } else {
                                 // Replace with span element
-                                let escaped_href = href.replace('\'', "\\'");
+                                let href_literal = js_string_literal(&href);
                                 let onclick = indoc::formatdoc! {r#"
                                         if (event.button === 0 || event.button === 1) {{
                                             event.preventDefault();
-                                            window.handleMarkdownLinkClick('{escaped_href}', event.button); 
+                                            window.handleMarkdownLinkClick({href_literal}, event.button); 
                                         }}"#
                                 };
                                 el.set_tag_name("span")?;
                                 el.remove_attribute("href");
-                                if ext != "md" && ext != "markdown" {
-                                    el.set_attribute("class", "md-link md-link-invalid")?;
-                                } else {
-                                    el.set_attribute("class", "md-link")?;
-                                }
+                                el.set_attribute("class", "md-link")?;
                                 el.set_attribute("onmousedown", &onclick)?;
                             }
+                        } else if !is_safe_url(&href, LINK_SCHEMES) {
+                            // Script URLs can also come from Markdown link syntax
+                            el.remove_attribute("href");
                         }
                     }
                     Ok(())
@@ -284,7 +803,13 @@ fn post_process_html_tags(html_str: &str, base_dir: &Path) -> String {
 
     let _ = rewriter.write(html_str.as_bytes());
     let _ = rewriter.end();
-    String::from_utf8(output).unwrap_or_else(|_| html_str.to_string())
+    ProcessedHtml {
+        html: String::from_utf8(output).unwrap_or_else(|_| html_str.to_string()),
+        broken_links: broken_links.into_inner(),
+        broken_images: broken_images.into_inner(),
+        resources: resources.into_inner(),
+        links: links.into_inner(),
+    }
 }
 
 #[cfg(test)]
@@ -322,19 +847,27 @@ mod tests {
         );
     }
 
+    fn render_alerts(markdown: &str) -> String {
+        let parser = Parser::new_ext(markdown, Options::all());
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, process_github_alerts(parser));
+        html_output
+    }
+
     #[test]
     fn test_process_github_alerts_note() {
         let input = indoc! {"
             > [!NOTE]
             > This is a note
         "};
-        let result = process_github_alerts(input);
+        let result = render_alerts(input);
 
         assert!(result.contains(r#"<div class="markdown-alert markdown-alert-note""#));
         assert!(result.contains(r#"<p class="markdown-alert-title""#));
         assert!(result.contains("NOTE"));
         assert!(result.contains("This is a note"));
         assert!(result.contains("</div>"));
+        assert!(!result.contains("<blockquote"));
     }
 
     #[test]
@@ -343,7 +876,7 @@ mod tests {
             > [!WARNING]
             > Be careful!
         "};
-        let result = process_github_alerts(input);
+        let result = render_alerts(input);
 
         assert!(result.contains(r#"markdown-alert-warning"#));
         assert!(result.contains("WARNING"));
@@ -358,7 +891,7 @@ mod tests {
             > Second line
             > Third line
         "};
-        let result = process_github_alerts(input);
+        let result = render_alerts(input);
 
         assert!(result.contains(r#"markdown-alert-important"#));
         assert!(result.contains("First line"));
@@ -378,7 +911,7 @@ mod tests {
 
         for (alert_name, alert_class) in alert_types {
             let input = format!("> [!{}]\n> Test content", alert_name);
-            let result = process_github_alerts(&input);
+            let result = render_alerts(&input);
 
             assert!(
                 result.contains(&format!(r#"markdown-alert-{}"#, alert_class)),
@@ -396,10 +929,88 @@ mod tests {
     #[test]
     fn test_process_github_alerts_no_match() {
         let input = "Regular paragraph\n> Regular quote";
-        let result = process_github_alerts(input);
+        let result = render_alerts(input);
+
+        assert!(result.contains("<blockquote>"));
+        assert!(!result.contains("markdown-alert"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_case_insensitive() {
+        let result = render_alerts("> [!tip]\n> Lowercase works too");
+
+        assert!(result.contains("markdown-alert-tip"));
+        assert!(result.contains("Lowercase works too"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_ignored_in_code_block() {
+        let input = indoc! {"
+            ```markdown
+            > [!NOTE]
+            > Example
+            ```
+        "};
+        let result = render_alerts(input);
 
-        assert_eq!(result, input);
         assert!(!result.contains("markdown-alert"));
+        assert!(result.contains("&gt; [!NOTE]"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_nested_in_list() {
+        let input = indoc! {"
+            - Item
+
+              > [!CAUTION]
+              > Inside the list
+        "};
+        let result = render_alerts(input);
+
+        let item_start = result.find("<li>").unwrap();
+        let alert_start = result.find("markdown-alert-caution").unwrap();
+        let item_end = result.find("</li>").unwrap();
+        assert!(item_start < alert_start && alert_start < item_end);
+        assert!(result.contains("Inside the list"));
+    }
+
+    #[test]
+    fn test_process_github_alerts_share_link_references() {
+        let input = indoc! {"
+            > [!NOTE]
+            > See [the docs][docs].
+
+            [docs]: https://example.com/docs
+        "};
+        let result = render_alerts(input);
+
+        assert!(result.contains(r#"<a href="https://example.com/docs">the docs</a>"#));
+    }
+
+    #[test]
+    fn test_render_markdown_with_page_breaks() {
+        let markdown = indoc::indoc! {r#"
+            # One
+
+            <!-- pagebreak -->
+
+            # Two
+
+            \newpage
+
+            Not a \newpage, nor <!-- pagebreak -->
+        "#};
+        for trust_html in [false, true] {
+            let options = RenderOptions {
+                trust_html,
+                ..RenderOptions::default()
+            };
+            let html = render_markdown_with_options(markdown, "test.md", &options)
+                .unwrap()
+                .html;
+            assert_eq!(html.matches(r#"<div class="page-break"></div>"#).count(), 2);
+            assert!(html.contains("<p>Not a \\newpage, nor"));
+        }
     }
 
     #[test]
@@ -443,7 +1054,7 @@ mod tests {
         fs::write(&image_path, png_data).unwrap();
 
         let html = r#"<p><img src="test.png" alt="test" /></p>"#;
-        let result = post_process_html_tags(html, temp_dir.path());
+        let result = post_process_html_tags(html, temp_dir.path(), &RenderOptions::default()).html;
 
         assert!(
             result.contains("data:image/png;base64,"),
@@ -455,10 +1066,163 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_post_process_html_tags_reports_resources() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("a.png"), [0x89, 0x50]).unwrap();
+        fs::write(temp_dir.path().join("b.svg"), "<svg/>").unwrap();
+
+        let html =
+            r#"<img src="a.png"><img src="./a.png"><img src="b.svg"><img src="missing.png">"#;
+        let processed = post_process_html_tags(html, temp_dir.path(), &RenderOptions::default());
+
+        let dir = temp_dir.path().canonicalize().unwrap();
+        assert_eq!(
+            processed.resources,
+            vec![dir.join("a.png"), dir.join("b.svg")]
+        );
+        assert_eq!(processed.broken_images, vec!["missing.png".to_string()]);
+    }
+
+    #[test]
+    fn test_post_process_html_tags_root_relative_img() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("assets")).unwrap();
+        fs::create_dir_all(root.join("docs/guide")).unwrap();
+        fs::write(root.join("assets/logo.png"), [0x89, 0x50, 0x4E, 0x47]).unwrap();
+
+        let html = r#"<img src="/assets/logo.png" />"#;
+        let options = RenderOptions {
+            document_root: Some(root.to_path_buf()),
+            images: ImageAccess::Within(root.to_path_buf()),
+            ..RenderOptions::default()
+        };
+        let result = post_process_html_tags(html, &root.join("docs/guide"), &options).html;
+
+        assert!(
+            result.contains("data:image/png;base64,"),
+            "Should resolve leading-slash image against the document root"
+        );
+    }
+
+    #[test]
+    fn test_post_process_html_tags_img_outside_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("repo");
+        fs::create_dir_all(root.join("docs")).unwrap();
+        fs::write(temp_dir.path().join("secret.png"), [0x89, 0x50]).unwrap();
+        std::os::unix::fs::symlink(
+            temp_dir.path().join("secret.png"),
+            root.join("docs/link.png"),
+        )
+        .unwrap();
+
+        let html = r#"<img src="../../secret.png" alt="Secret"><img src="link.png">"#;
+        let options = RenderOptions {
+            images: ImageAccess::Within(root.clone()),
+            ..RenderOptions::default()
+        };
+        let result = post_process_html_tags(html, &root.join("docs"), &options).html;
+
+        assert!(!result.contains("data:image/png"));
+        assert!(!result.contains("<img"));
+        assert_eq!(result.matches("Image outside the document root").count(), 2);
+        assert!(result.contains(r#"<span class="broken-image-alt">Secret</span>"#));
+        assert!(result.contains(r#"<code class="broken-image-path">../../secret.png</code>"#));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_img_default_access() {
+        let temp_dir = TempDir::new().unwrap();
+        let docs = temp_dir.path().join("docs");
+        fs::create_dir_all(&docs).unwrap();
+        fs::write(docs.join("inside.png"), [0x89, 0x50]).unwrap();
+        fs::write(temp_dir.path().join("outside.png"), [0x89, 0x50]).unwrap();
+
+        // Only images next to the document are embedded by default
+        let html = r#"<img src="inside.png"><img src="../outside.png">"#;
+        let result = post_process_html_tags(html, &docs, &RenderOptions::default()).html;
+        assert_eq!(result.matches("data:image/png;base64,").count(), 1);
+        assert!(result.contains("Image outside the document root"));
+        assert_eq!(
+            RenderOptions::default().max_image_size,
+            Some(DEFAULT_MAX_IMAGE_SIZE)
+        );
+
+        let options = RenderOptions {
+            images: ImageAccess::Unrestricted,
+            ..RenderOptions::default()
+        };
+        let result = post_process_html_tags(html, &docs, &options).html;
+        assert_eq!(result.matches("data:image/png;base64,").count(), 2);
+    }
+
+    #[test]
+    fn test_post_process_html_tags_img_too_large() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("small.png"), [0x89; 16]).unwrap();
+        fs::write(temp_dir.path().join("large.png"), [0x89; 64]).unwrap();
+
+        let html = r#"<img src="small.png"><img src="large.png">"#;
+        let options = RenderOptions {
+            max_image_size: Some(32),
+            ..RenderOptions::default()
+        };
+        let result = post_process_html_tags(html, temp_dir.path(), &options).html;
+
+        assert_eq!(result.matches("data:image/png;base64,").count(), 1);
+        assert!(result.contains("Image too large to embed"));
+        assert!(result.contains(r#"title="large.png""#));
+    }
+
+    #[test]
+    fn test_too_large_image_html() {
+        let html = too_large_image_html("/photos/scan.png", 30 * 1024 * 1024);
+        assert!(html.contains("Image too large to embed (30.0 MB)"));
+        assert!(html.contains(r#"<code class="broken-image-path">/photos/scan.png</code>"#));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_img_missing() {
+        let html = r#"<img src="missing &amp; gone.png" alt="A &lt;b&gt;">"#;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
+
+        assert_eq!(
+            result,
+            concat!(
+                r#"<span class="broken-image" title="missing &amp; gone.png">"#,
+                r#"<span class="broken-image-message">Image not found</span>"#,
+                r#"<span class="broken-image-alt">A &lt;b&gt;</span>"#,
+                r#"<code class="broken-image-path">missing &amp; gone.png</code></span>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_resolve_local_path() {
+        let base_dir = Path::new("/repo/docs/guide");
+        let root = Path::new("/repo");
+
+        assert_eq!(
+            resolve_local_path(base_dir, Some(root), "/docs/setup.md"),
+            PathBuf::from("/repo/docs/setup.md")
+        );
+        assert_eq!(
+            resolve_local_path(base_dir, Some(root), "../setup.md"),
+            PathBuf::from("/repo/docs/guide/../setup.md")
+        );
+        // Without a document root, a leading slash is a filesystem path
+        assert_eq!(
+            resolve_local_path(base_dir, None, "/docs/setup.md"),
+            PathBuf::from("/docs/setup.md")
+        );
+    }
+
     #[test]
     fn test_post_process_html_tags_anchor() {
         let html = r#"<a href="doc.md">Link</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
 
         assert!(
             result.contains(r#"<span class="md-link""#),
@@ -475,7 +1239,7 @@ mod tests {
     fn test_post_process_html_tags_http_urls() {
         let html =
             r#"<img src="https://example.com/image.png" /><a href="https://example.com">Link</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
 
         assert!(
             result.contains(r#"src="https://example.com/image.png""#),
@@ -489,45 +1253,347 @@ mod tests {
 
     #[test]
     fn test_post_process_html_tags_non_md_local_file() {
-        let html = r#"<a href="file.txt">Text File</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+        let html = r#"<a href="report.pdf">Report</a>"#;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
 
         assert!(
-            result.contains(r#"<span class="md-link md-link-invalid""#),
-            "Should convert to span with md-link and md-link-invalid class"
+            result.contains(r#"<span class="md-link""#),
+            "Should convert to span with md-link class"
         );
         assert!(
-            result.contains("handleMarkdownLinkClick"),
+            result.contains("handleMarkdownLinkClick('report.pdf'"),
             "Should add click handler for local files"
         );
         assert!(!result.contains("<a "), "Should not contain anchor tag");
     }
 
     #[test]
-    fn test_post_process_html_tags_md_vs_other_files() {
-        let html = r#"<a href="doc.md">MD</a><a href="file.txt">TXT</a>"#;
-        let result = post_process_html_tags(html, Path::new("."));
+    fn test_post_process_html_tags_links_without_extension() {
+        let html = r#"<a href="LICENSE">License</a><a href="docs/">Docs</a>"#;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
 
-        // MD file should have only md-link class
-        assert!(
-            result.contains(r#"class="md-link""#),
-            "Should have md-link for .md file"
+        assert!(!result.contains("<a "), "Should not contain anchor tag");
+        assert!(result.contains("handleMarkdownLinkClick('LICENSE'"));
+        assert!(result.contains("handleMarkdownLinkClick('docs/'"));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_keeps_scheme_links() {
+        let html =
+            r#"<a href="mailto:me@example.com">Mail</a><a href="//example.com/x.md">CDN</a>"#;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
+
+        assert!(result.contains(r#"<a href="mailto:me@example.com""#));
+        assert!(result.contains(r#"<a href="//example.com/x.md""#));
+        assert!(!result.contains("handleMarkdownLinkClick"));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_escapes_link_handler() {
+        let html = r#"<a href="a&#39;);alert(1);//">x</a><a href="b\');alert(1);//">y</a>"#;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
+
+        assert!(result
+            .contains(r"handleMarkdownLinkClick('a\u{27}\u{29}\u{3b}alert\u{28}1\u{29}\u{3b}//'"));
+        assert!(result.contains(r"handleMarkdownLinkClick('b\u{5c}\u{27}"));
+        assert!(!result.contains("');alert"));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_removes_script_urls() {
+        let html = r#"<a href="javascript:alert(1)">x</a><a href="JavaScript:alert(1)">y</a>"#;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
+
+        assert_eq!(result, "<a>x</a><a>y</a>");
+    }
+
+    #[test]
+    fn test_is_local_link() {
+        assert!(is_local_link("doc.md"));
+        assert!(is_local_link("../src/lib.rs#L42"));
+        assert!(is_local_link("#install"));
+        assert!(is_local_link("LICENSE"));
+        assert!(is_local_link("C:/docs/readme.md"));
+        assert!(!is_local_link("https://example.com"));
+        assert!(!is_local_link("mailto:me@example.com"));
+        assert!(!is_local_link("//example.com"));
+        assert!(!is_local_link(""));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_fragment_links() {
+        let html = r##"<a href="#install">Install</a><a href="guide.md#setup">Guide</a>"##;
+        let result = post_process_html_tags(html, Path::new("."), &RenderOptions::default()).html;
+
+        assert!(!result.contains("<a "), "Should not contain anchor tag");
+        assert!(result.contains("handleMarkdownLinkClick('#install'"));
+        assert!(result.contains("handleMarkdownLinkClick('guide.md#setup'"));
+    }
+
+    #[test]
+    fn test_post_process_html_tags_static_links() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("docs/guide")).unwrap();
+        fs::write(root.join("README.md"), "# Readme").unwrap();
+        fs::write(root.join("docs/guide/setup.md"), "# Setup").unwrap();
+        fs::write(root.join("docs/guide/report.pdf"), "").unwrap();
+
+        let html = concat!(
+            r##"<a href="setup.md#install">Setup</a><a href="report.pdf">Report</a>"##,
+            r##"<a href="/README.md">Readme</a><a href="#top">Top</a>"##,
+            r##"<a href="missing.md">Missing</a><a href="https://example.com/a.md">Web</a>"##,
         );
+        let options = RenderOptions {
+            document_root: Some(root.to_path_buf()),
+            static_links: true,
+            ..RenderOptions::default()
+        };
+        let result = post_process_html_tags(html, &root.join("docs/guide"), &options);
+
+        assert!(!result.html.contains("handleMarkdownLinkClick"));
+        assert!(result.html.contains(r##"<a href="setup.html#install">"##));
+        assert!(result.html.contains(r#"<a href="report.pdf">"#));
+        assert!(result.html.contains(r#"<a href="../../README.html">"#));
+        assert!(result.html.contains(r##"<a href="#top">"##));
+        assert!(result.html.contains(r#"<a href="missing.html">"#));
+        assert!(result
+            .html
+            .contains(r#"<a href="https://example.com/a.md">"#));
+        assert_eq!(result.broken_links, vec!["missing.md"]);
+    }
 
-        // TXT file should have both md-link and md-link-invalid classes
-        assert!(
-            result.contains(r#"class="md-link md-link-invalid""#),
-            "Should have md-link and md-link-invalid for .txt file"
+    #[test]
+    fn test_static_link_href_without_document_root() {
+        // Leading-slash links resolve against the file system root and become relative
+        let options = RenderOptions {
+            static_links: true,
+            ..RenderOptions::default()
+        };
+        assert_eq!(
+            static_link_href(
+                "/other/notes.md",
+                Some("a"),
+                Path::new("/docs/guide"),
+                &options
+            ),
+            "../../other/notes.html#a"
+        );
+        assert_eq!(
+            static_link_href("/docs/", None, Path::new("/docs/guide"), &options),
+            "../"
         );
+    }
+
+    #[test]
+    fn test_render_markdown_reports_broken_images() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("doc.md");
+        let rendered = render_markdown_with_options(
+            "![A](missing.png) [B](missing.md)",
+            &file,
+            &RenderOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(rendered.broken_images, vec!["missing.png"]);
+        // Links are only checked for static pages
+        assert!(rendered.broken_links.is_empty());
+    }
+
+    #[test]
+    fn test_process_headings_assigns_ids() {
+        let markdown = indoc! {"
+            # Getting Started
+
+            ## What's `new`?
+
+            ## Getting Started
+
+            ### Custom {#my-id}
+        "};
+        let parser = Parser::new_ext(markdown, Options::all());
+        let mut headings = Vec::new();
+        let mut html_output = String::new();
+        html::push_html(&mut html_output, process_headings(parser, &mut headings));
+
+        assert!(html_output.contains(r#"<h1 id="getting-started">"#));
+        assert!(html_output.contains(r#"<h2 id="whats-new">"#));
+        assert!(html_output.contains(r#"<h2 id="getting-started-1">"#));
+        assert!(html_output.contains(r#"<h3 id="my-id">"#));
+        assert_eq!(headings.len(), 4);
+    }
+
+    #[test]
+    fn test_render_markdown_collects_headings() {
+        let markdown = indoc! {"
+            # Title
+
+            Intro paragraph.
+
+            ## Install `arto`
+
+            ```sh
+            # not a heading
+            ```
+
+            ### Nested
+
+            Setext Heading
+            --------------
+        "};
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let rendered =
+            render_markdown_with_options(markdown, &md_path, &RenderOptions::default()).unwrap();
 
-        // Both should have click handlers
-        let click_handler_count = result.matches("handleMarkdownLinkClick").count();
         assert_eq!(
-            click_handler_count, 2,
-            "Should have click handlers for both links"
+            rendered.headings,
+            vec![
+                Heading {
+                    level: 1,
+                    id: "title".to_string(),
+                    text: "Title".to_string(),
+                },
+                Heading {
+                    level: 2,
+                    id: "install-arto".to_string(),
+                    text: "Install arto".to_string(),
+                },
+                Heading {
+                    level: 3,
+                    id: "nested".to_string(),
+                    text: "Nested".to_string(),
+                },
+                Heading {
+                    level: 2,
+                    id: "setext-heading".to_string(),
+                    text: "Setext Heading".to_string(),
+                },
+            ]
         );
     }
 
+    #[test]
+    fn test_render_markdown_strips_front_matter() {
+        let markdown = indoc! {"
+            ---
+            title: Release Notes
+            ---
+            Body
+            ---
+        "};
+        let temp_dir = TempDir::new().unwrap();
+        let md_path = temp_dir.path().join("test.md");
+
+        let rendered =
+            render_markdown_with_options(markdown, &md_path, &RenderOptions::default()).unwrap();
+
+        let front_matter = rendered.front_matter.unwrap();
+        assert_eq!(front_matter.title(), Some("Release Notes"));
+        assert!(!rendered.html.contains("<hr"));
+        assert!(!rendered.html.contains("title:"));
+        // The body's own setext heading is still rendered
+        assert!(rendered.html.contains(r#"<h2 id="body">"#));
+    }
+
+    #[test]
+    fn test_render_markdown_sanitizes_raw_html() {
+        let markdown = indoc! {r#"
+            <div align="center" onclick="alert(1)">
+            <script>
+            alert(1)
+            </script>
+            </div>
+
+            Inline <img src="https://example.com/a.png" onerror="alert(1)"> image
+            and [link](javascript:alert(1)).
+
+            ```html
+            <script>kept as code</script>
+            ```
+        "#};
+        let rendered =
+            render_markdown_with_options(markdown, "test.md", &RenderOptions::default()).unwrap();
+
+        assert!(rendered.html.contains(r#"<div align="center">"#));
+        assert!(!rendered.html.contains("onclick"));
+        assert!(!rendered.html.contains("onerror"));
+        assert!(!rendered.html.contains("<script>"));
+        assert!(!rendered.html.contains("javascript:"));
+        assert!(rendered
+            .html
+            .contains(r#"<img src="https://example.com/a.png">"#));
+        assert!(rendered
+            .html
+            .contains("&lt;script&gt;kept as code&lt;/script&gt;"));
+    }
+
+    #[test]
+    fn test_render_markdown_escapes_diagram_and_math_blocks() {
+        let markdown = indoc! {r#"
+            ```mermaid
+            graph LR
+                A["<img src=x onerror=alert(1)>"]
+            ```
+
+            ```math
+            <iframe srcdoc="<script>alert(1)</script>"></iframe>
+            ```
+        "#};
+        let rendered =
+            render_markdown_with_options(markdown, "test.md", &RenderOptions::default()).unwrap();
+
+        assert!(!rendered.html.contains("<img"));
+        assert!(!rendered.html.contains("<iframe"));
+        assert!(!rendered.html.contains("<script>"));
+        assert!(rendered.html.contains("&lt;img src=x onerror=alert(1)&gt;"));
+        assert!(rendered.html.contains("&lt;iframe srcdoc="));
+    }
+
+    #[test]
+    fn test_render_markdown_escapes_math_expressions() {
+        let markdown = indoc! {r#"
+            Inline $<svg onload=alert(1)>$ math.
+
+            Quoted $x" onmouseover="alert(1)$ math.
+
+            $$
+            <svg onload=alert(1)>
+            $$
+        "#};
+        let rendered =
+            render_markdown_with_options(markdown, "test.md", &RenderOptions::default()).unwrap();
+
+        assert!(!rendered.html.contains("<svg"));
+        assert!(rendered.html.contains("&lt;svg onload=alert(1)&gt;"));
+        // The quote cannot close the attribute and add a handler
+        assert!(!rendered
+            .html
+            .contains(r#"data-original-content="x" onmouseover"#));
+        assert!(rendered
+            .html
+            .contains(r#"data-original-content="x&quot; onmouseover=&quot;alert(1)""#));
+    }
+
+    #[test]
+    fn test_render_markdown_trusted_html() {
+        let markdown = r#"<div class="banner" style="color: red">Hi</div>"#;
+        let options = RenderOptions {
+            trust_html: true,
+            ..RenderOptions::default()
+        };
+        let trusted = render_markdown_with_options(markdown, "test.md", &options).unwrap();
+        let untrusted =
+            render_markdown_with_options(markdown, "test.md", &RenderOptions::default()).unwrap();
+
+        assert!(trusted
+            .html
+            .contains(r#"<div class="banner" style="color: red">"#));
+        assert_eq!(untrusted.html, "<div>Hi</div>");
+    }
+
     #[test]
     fn test_render_to_html_basic() {
         let markdown = "# Hello\n\nThis is a test.";
@@ -536,7 +1602,7 @@ mod tests {
 
         let result = render_to_html(markdown, &md_path).unwrap();
 
-        assert!(result.contains("<h1>"));
+        assert!(result.contains(r#"<h1 id="hello">"#));
         assert!(result.contains("Hello"));
         assert!(result.contains("<p>"));
         assert!(result.contains("This is a test."));
@@ -794,7 +1860,10 @@ mod tests {
         let result = render_to_html(markdown, &md_path).unwrap();
 
         // Verify that all features are correctly integrated
-        assert!(result.contains("<h1>"), "Should render heading");
+        assert!(
+            result.contains(r#"<h1 id="test-document">"#),
+            "Should render heading with anchor id"
+        );
         assert!(
             result.contains("markdown-alert-warning"),
             "Should render alert"
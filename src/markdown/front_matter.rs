@@ -0,0 +1,245 @@
+use serde_json::{Map, Value};
+
+/// Syntax of a front matter block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrontMatterFormat {
+    /// YAML delimited by `---`
+    Yaml,
+    /// TOML delimited by `+++`
+    Toml,
+}
+
+/// Structured metadata declared at the top of a document
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontMatter {
+    pub format: FrontMatterFormat,
+    /// Top-level keys in declaration order
+    pub data: Map<String, Value>,
+}
+
+impl FrontMatter {
+    /// Get the document title declared by the `title` key
+    pub fn title(&self) -> Option<&str> {
+        self.data
+            .get("title")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+    }
+
+    /// Render the metadata as a GitHub-style table
+    pub fn to_html(&self) -> String {
+        if self.data.is_empty() {
+            return String::new();
+        }
+        format!(
+            r#"<div class="front-matter">{}</div>"#,
+            render_object(&self.data)
+        )
+    }
+}
+
+/// Split a leading front matter block from the document
+///
+/// Returns the parsed front matter (if any) and the remaining Markdown body.
+/// Blocks that fail to parse or are not key/value mappings are left in the body.
+pub fn extract_front_matter(markdown: &str) -> (Option<FrontMatter>, &str) {
+    let content = markdown.strip_prefix('\u{feff}').unwrap_or(markdown);
+
+    let Some((first_line, rest)) = split_line(content) else {
+        return (None, markdown);
+    };
+    let (format, closing): (FrontMatterFormat, &[&str]) = match first_line.trim_end() {
+        "---" => (FrontMatterFormat::Yaml, &["---", "..."]),
+        "+++" => (FrontMatterFormat::Toml, &["+++"]),
+        _ => return (None, markdown),
+    };
+
+    // Find the closing delimiter line
+    let mut offset = 0;
+    let mut remaining = rest;
+    let (raw, body) = loop {
+        let Some((line, next)) = split_line(remaining) else {
+            return (None, markdown);
+        };
+        if closing.contains(&line.trim_end()) {
+            break (&rest[..offset], next);
+        }
+        offset += remaining.len() - next.len();
+        remaining = next;
+    };
+
+    match parse(format, raw) {
+        Ok(data) => (Some(FrontMatter { format, data }), body),
+        Err(e) => {
+            tracing::warn!(?format, "Failed to parse front matter: {e}");
+            (None, markdown)
+        }
+    }
+}
+
+/// Split the first line (without its line ending) from the rest of the text
+fn split_line(text: &str) -> Option<(&str, &str)> {
+    if text.is_empty() {
+        return None;
+    }
+    match text.find('\n') {
+        Some(index) => {
+            let line = &text[..index];
+            Some((line.strip_suffix('\r').unwrap_or(line), &text[index + 1..]))
+        }
+        None => Some((text, "")),
+    }
+}
+
+fn parse(format: FrontMatterFormat, raw: &str) -> anyhow::Result<Map<String, Value>> {
+    if raw.trim().is_empty() {
+        return Ok(Map::new());
+    }
+    let value: Value = match format {
+        FrontMatterFormat::Yaml => serde_yaml::from_str(raw)?,
+        FrontMatterFormat::Toml => toml_to_json(toml::from_str(raw)?),
+    };
+    match value {
+        Value::Object(map) => Ok(map),
+        Value::Null => Ok(Map::new()),
+        other => anyhow::bail!("expected a mapping, found {other}"),
+    }
+}
+
+/// Convert a TOML value, rendering datetimes as their string form
+fn toml_to_json(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_json).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(key, value)| (key, toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn render_object(map: &Map<String, Value>) -> String {
+    let mut html = String::from("<table><thead><tr>");
+    for key in map.keys() {
+        html.push_str(&format!("<th>{}</th>", html_escape::encode_text(key)));
+    }
+    html.push_str("</tr></thead><tbody><tr>");
+    for value in map.values() {
+        html.push_str(&format!("<td>{}</td>", render_value(value)));
+    }
+    html.push_str("</tr></tbody></table>");
+    html
+}
+
+fn render_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => html_escape::encode_text(s).to_string(),
+        Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::Array(items) => {
+            let cells: String = items
+                .iter()
+                .map(|item| format!("<td>{}</td>", render_value(item)))
+                .collect();
+            format!("<table><tbody><tr>{cells}</tr></tbody></table>")
+        }
+        Value::Object(map) => render_object(map),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_extract_yaml_front_matter() {
+        let markdown = indoc! {"
+            ---
+            title: Hello
+            tags: [a, b]
+            ---
+            # Body
+        "};
+        let (front_matter, body) = extract_front_matter(markdown);
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.format, FrontMatterFormat::Yaml);
+        assert_eq!(front_matter.title(), Some("Hello"));
+        assert_eq!(body, "# Body\n");
+        // Keys keep their declaration order
+        let keys: Vec<_> = front_matter.data.keys().collect();
+        assert_eq!(keys, ["title", "tags"]);
+    }
+
+    #[test]
+    fn test_extract_toml_front_matter() {
+        let markdown =
+            "+++\r\ntitle = \"Hello\"\r\ndraft = true\r\ndate = 2024-01-02\r\n+++\r\nBody";
+        let (front_matter, body) = extract_front_matter(markdown);
+        let front_matter = front_matter.unwrap();
+
+        assert_eq!(front_matter.format, FrontMatterFormat::Toml);
+        assert_eq!(front_matter.title(), Some("Hello"));
+        assert_eq!(front_matter.data.get("draft"), Some(&Value::Bool(true)));
+        assert_eq!(
+            front_matter.data.get("date"),
+            Some(&Value::String("2024-01-02".to_string()))
+        );
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_extract_front_matter_requires_leading_block() {
+        let markdown = "Intro\n---\ntitle: Hello\n---\n";
+        let (front_matter, body) = extract_front_matter(markdown);
+        assert!(front_matter.is_none());
+        assert_eq!(body, markdown);
+    }
+
+    #[test]
+    fn test_extract_front_matter_unclosed_or_invalid() {
+        let unclosed = "---\ntitle: Hello\n";
+        assert_eq!(extract_front_matter(unclosed), (None, unclosed));
+
+        // A scalar is not metadata; leave it to the Markdown parser
+        let scalar = "---\njust text\n---\n";
+        assert_eq!(extract_front_matter(scalar), (None, scalar));
+    }
+
+    #[test]
+    fn test_extract_empty_front_matter() {
+        let (front_matter, body) = extract_front_matter("---\n---\nBody");
+        let front_matter = front_matter.unwrap();
+        assert!(front_matter.data.is_empty());
+        assert_eq!(front_matter.title(), None);
+        assert_eq!(front_matter.to_html(), "");
+        assert_eq!(body, "Body");
+    }
+
+    #[test]
+    fn test_front_matter_to_html() {
+        let (front_matter, _) = extract_front_matter(
+            "---\ntitle: <b>Hi</b>\ntags: [a, 1]\nmeta:\n  draft: true\n---\n",
+        );
+        let html = front_matter.unwrap().to_html();
+
+        assert_eq!(
+            html,
+            concat!(
+                r#"<div class="front-matter"><table><thead><tr><th>title</th><th>tags</th><th>meta</th></tr></thead>"#,
+                r#"<tbody><tr><td>&lt;b&gt;Hi&lt;/b&gt;</td>"#,
+                r#"<td><table><tbody><tr><td>a</td><td>1</td></tr></tbody></table></td>"#,
+                r#"<td><table><thead><tr><th>draft</th></tr></thead><tbody><tr><td>true</td></tr></tbody></table></td>"#,
+                r#"</tr></tbody></table></div>"#,
+            )
+        );
+    }
+}
@@ -0,0 +1,372 @@
+use lol_html::{doc_comments, element, HtmlRewriter, Settings};
+
+/// Elements allowed in raw HTML (GitHub's allowlist)
+const ALLOWED_ELEMENTS: &[&str] = &[
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "h7",
+    "h8",
+    "br",
+    "b",
+    "i",
+    "strong",
+    "em",
+    "a",
+    "pre",
+    "code",
+    "img",
+    "tt",
+    "div",
+    "ins",
+    "del",
+    "sup",
+    "sub",
+    "p",
+    "ol",
+    "ul",
+    "table",
+    "thead",
+    "tbody",
+    "tfoot",
+    "blockquote",
+    "dl",
+    "dt",
+    "dd",
+    "kbd",
+    "q",
+    "samp",
+    "var",
+    "hr",
+    "ruby",
+    "rt",
+    "rp",
+    "li",
+    "tr",
+    "td",
+    "th",
+    "s",
+    "strike",
+    "summary",
+    "details",
+    "caption",
+    "figure",
+    "figcaption",
+    "abbr",
+    "bdo",
+    "cite",
+    "dfn",
+    "mark",
+    "small",
+    "span",
+    "time",
+    "wbr",
+    "picture",
+    "source",
+];
+
+/// Elements removed together with their content (other disallowed elements keep their content)
+const REMOVED_ELEMENTS: &[&str] = &[
+    "script",
+    "style",
+    "iframe",
+    "frame",
+    "frameset",
+    "object",
+    "embed",
+    "applet",
+    "noscript",
+    "template",
+    "textarea",
+    "select",
+    "svg",
+    "math",
+    "title",
+    "xmp",
+    "plaintext",
+    "noembed",
+    "noframes",
+];
+
+/// Attributes allowed on every element (GitHub's allowlist)
+const GLOBAL_ATTRIBUTES: &[&str] = &[
+    "abbr",
+    "accept",
+    "accept-charset",
+    "accesskey",
+    "action",
+    "align",
+    "alt",
+    "aria-describedby",
+    "aria-hidden",
+    "aria-label",
+    "aria-labelledby",
+    "axis",
+    "border",
+    "cellpadding",
+    "cellspacing",
+    "char",
+    "charoff",
+    "charset",
+    "checked",
+    "clear",
+    "cols",
+    "colspan",
+    "color",
+    "compact",
+    "coords",
+    "datetime",
+    "dir",
+    "disabled",
+    "enctype",
+    "for",
+    "frame",
+    "headers",
+    "height",
+    "hreflang",
+    "hspace",
+    "id",
+    "ismap",
+    "itemprop",
+    "label",
+    "lang",
+    "maxlength",
+    "media",
+    "method",
+    "multiple",
+    "name",
+    "nohref",
+    "noshade",
+    "nowrap",
+    "open",
+    "progress",
+    "prompt",
+    "readonly",
+    "rel",
+    "rev",
+    "role",
+    "rows",
+    "rowspan",
+    "rules",
+    "scope",
+    "selected",
+    "shape",
+    "size",
+    "span",
+    "start",
+    "summary",
+    "tabindex",
+    "target",
+    "title",
+    "type",
+    "usemap",
+    "valign",
+    "value",
+    "vspace",
+    "width",
+];
+
+/// Attributes naming an anchor, prefixed so they cannot clash with the app's own ids
+const ANCHOR_ATTRIBUTES: &[&str] = &["id", "name"];
+
+/// Prefix of anchor names written in raw HTML (like on GitHub)
+const USER_CONTENT_PREFIX: &str = "user-content-";
+
+/// URL schemes allowed in links
+pub const LINK_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// URL schemes allowed in image and citation sources
+const SOURCE_SCHEMES: &[&str] = &["http", "https"];
+
+/// Attributes allowed on specific elements
+fn element_attributes(tag: &str) -> &'static [&'static str] {
+    match tag {
+        "a" => &["href"],
+        "img" => &["src", "longdesc"],
+        "div" => &["itemscope", "itemtype"],
+        "blockquote" | "del" | "ins" | "q" => &["cite"],
+        "source" => &["srcset"],
+        _ => &[],
+    }
+}
+
+/// Check whether a URL is relative or uses one of the given schemes
+///
+/// Entities are decoded and the characters browsers ignore are dropped first,
+/// so `java&#x09;script:` is recognized as `javascript:`.
+pub fn is_safe_url(url: &str, schemes: &[&str]) -> bool {
+    let url: String = html_escape::decode_html_entities(url)
+        .trim_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    match url.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => schemes
+            .iter()
+            .any(|allowed| scheme.eq_ignore_ascii_case(allowed)),
+        _ => true,
+    }
+}
+
+/// Check whether an attribute value is acceptable for the attribute
+fn is_safe_attribute_value(name: &str, value: &str) -> bool {
+    match name {
+        "href" => is_safe_url(value, LINK_SCHEMES),
+        "src" | "longdesc" | "cite" => is_safe_url(value, SOURCE_SCHEMES),
+        // Comma-separated candidates like `a.png 1x, b.png 2x`
+        "srcset" => value.split(',').all(|candidate| {
+            candidate
+                .split_whitespace()
+                .next()
+                .is_none_or(|url| is_safe_url(url, SOURCE_SCHEMES))
+        }),
+        _ => true,
+    }
+}
+
+/// Sanitize an HTML fragment with GitHub's element and attribute allowlist
+///
+/// Disallowed elements are unwrapped (or dropped with their content when they
+/// can execute or hide markup), and event handlers, inline styles and unsafe
+/// URLs are removed. Ids and names get the `user-content-` prefix.
+pub fn sanitize_html(html: &str) -> String {
+    let mut output = Vec::new();
+    let mut rewriter = HtmlRewriter::new(
+        Settings {
+            element_content_handlers: vec![element!("*", |el| {
+                let tag = el.tag_name();
+                if REMOVED_ELEMENTS.contains(&tag.as_str()) {
+                    el.remove();
+                    return Ok(());
+                }
+                if !ALLOWED_ELEMENTS.contains(&tag.as_str()) {
+                    el.remove_and_keep_content();
+                    return Ok(());
+                }
+
+                let allowed = element_attributes(&tag);
+                let rejected: Vec<String> = el
+                    .attributes()
+                    .iter()
+                    .filter(|attr| {
+                        let name = attr.name();
+                        let is_allowed = GLOBAL_ATTRIBUTES.contains(&name.as_str())
+                            || allowed.contains(&name.as_str());
+                        !is_allowed || !is_safe_attribute_value(&name, &attr.value())
+                    })
+                    .map(|attr| attr.name())
+                    .collect();
+                for name in rejected {
+                    el.remove_attribute(&name);
+                }
+                for name in ANCHOR_ATTRIBUTES {
+                    if let Some(value) = el.get_attribute(name) {
+                        if !value.starts_with(USER_CONTENT_PREFIX) {
+                            el.set_attribute(name, &format!("{USER_CONTENT_PREFIX}{value}"))?;
+                        }
+                    }
+                }
+                Ok(())
+            })],
+            document_content_handlers: vec![doc_comments!(|comment| {
+                comment.remove();
+                Ok(())
+            })],
+            ..Settings::default()
+        },
+        |chunk: &[u8]| output.extend_from_slice(chunk),
+    );
+
+    // Fail closed: drop the fragment if it cannot be rewritten
+    if rewriter.write(html.as_bytes()).is_err() || rewriter.end().is_err() {
+        return String::new();
+    }
+    String::from_utf8(output).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_html_removes_scripts() {
+        assert_eq!(
+            sanitize_html("<p>Hi<script>alert(1)</script></p>"),
+            "<p>Hi</p>"
+        );
+        assert_eq!(sanitize_html("<style>body { display: none }</style>"), "");
+        assert_eq!(sanitize_html("<!-- note --><b>Bold</b>"), "<b>Bold</b>");
+    }
+
+    #[test]
+    fn test_sanitize_html_unwraps_disallowed_elements() {
+        assert_eq!(
+            sanitize_html(r#"<form action="/x"><b>Kept</b></form>"#),
+            "<b>Kept</b>"
+        );
+        assert_eq!(sanitize_html("<marquee>Text</marquee>"), "Text");
+    }
+
+    #[test]
+    fn test_sanitize_html_filters_attributes() {
+        assert_eq!(
+            sanitize_html(r#"<img src="a.png" alt="A" onerror="alert(1)" style="width:0">"#),
+            r#"<img src="a.png" alt="A">"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<div align="center" class="x" itemscope>Hi</div>"#),
+            r#"<div align="center" itemscope>Hi</div>"#
+        );
+        // `href` is only allowed on anchors
+        assert_eq!(sanitize_html(r#"<p href="x.md">Hi</p>"#), "<p>Hi</p>");
+    }
+
+    #[test]
+    fn test_sanitize_html_prefixes_anchor_names() {
+        // Ids of the app (source lines, headings) cannot be clobbered
+        assert_eq!(
+            sanitize_html(r#"<div id="L1">Fake line</div>"#),
+            r#"<div id="user-content-L1">Fake line</div>"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<a name="install" id="user-content-x">x</a>"#),
+            r#"<a name="user-content-install" id="user-content-x">x</a>"#
+        );
+    }
+
+    #[test]
+    fn test_sanitize_html_removes_unsafe_urls() {
+        assert_eq!(
+            sanitize_html(r#"<a href="javascript:alert(1)">x</a>"#),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize_html(r#"<a href="java&#x09;script&colon;alert(1)">x</a>"#),
+            "<a>x</a>"
+        );
+        assert_eq!(sanitize_html(r#"<img src="data:text/html,<b>">"#), "<img>");
+        assert_eq!(
+            sanitize_html(r#"<source srcset="a.png 1x, javascript:x 2x">"#),
+            "<source>"
+        );
+        assert_eq!(
+            sanitize_html(r#"<a href="docs/guide.md#setup">x</a>"#),
+            r#"<a href="docs/guide.md#setup">x</a>"#
+        );
+    }
+
+    #[test]
+    fn test_is_safe_url() {
+        assert!(is_safe_url("https://example.com", LINK_SCHEMES));
+        assert!(is_safe_url("MAILTO:me@example.com", LINK_SCHEMES));
+        assert!(is_safe_url("../README.md", LINK_SCHEMES));
+        assert!(is_safe_url("#install", LINK_SCHEMES));
+        assert!(is_safe_url("path/with:colon", LINK_SCHEMES));
+        assert!(!is_safe_url(" javascript:alert(1)", LINK_SCHEMES));
+        assert!(!is_safe_url("vbscript:x", LINK_SCHEMES));
+        assert!(!is_safe_url("mailto:me@example.com", SOURCE_SCHEMES));
+    }
+}
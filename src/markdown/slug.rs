@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use unicode_normalization::char::is_combining_mark;
+
+/// Generates GitHub-compatible heading slugs
+///
+/// Mirrors the algorithm used by GitHub (github-slugger): the text is lowercased,
+/// punctuation and symbols are stripped, spaces become hyphens, and repeated slugs
+/// receive `-1`, `-2`, ... suffixes in document order.
+#[derive(Debug, Default)]
+pub struct Slugger {
+    occurrences: HashMap<String, usize>,
+}
+
+impl Slugger {
+    /// Generate a unique slug for the given heading text
+    pub fn slug(&mut self, text: &str) -> String {
+        let original = slugify(text);
+        let mut result = original.clone();
+
+        while self.occurrences.contains_key(&result) {
+            let count = self.occurrences.entry(original.clone()).or_insert(0);
+            *count += 1;
+            result = format!("{}-{}", original, count);
+        }
+
+        self.occurrences.insert(result.clone(), 0);
+        result
+    }
+}
+
+/// Convert heading text into a slug without deduplication
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|&c| c.is_alphanumeric() || is_combining_mark(c) || matches!(c, '-' | '_' | ' '))
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slugify_basic() {
+        assert_eq!(slugify("Installation"), "installation");
+        assert_eq!(slugify("Getting Started"), "getting-started");
+        assert_eq!(slugify("What's new?"), "whats-new");
+        assert_eq!(slugify("C++ & Rust: a comparison"), "c--rust-a-comparison");
+    }
+
+    #[test]
+    fn test_slugify_keeps_hyphens_underscores_and_unicode() {
+        assert_eq!(slugify("snake_case-name"), "snake_case-name");
+        assert_eq!(slugify("日本語 の 見出し"), "日本語-の-見出し");
+        assert_eq!(slugify("Ünïcödé"), "ünïcödé");
+    }
+
+    #[test]
+    fn test_slugify_keeps_combining_marks() {
+        // "é" written as "e" + U+0301 COMBINING ACUTE ACCENT
+        assert_eq!(slugify("Cafe\u{301} Menu"), "cafe\u{301}-menu");
+        assert_eq!(slugify("क्षेत्र"), "क्षेत्र");
+    }
+
+    #[test]
+    fn test_slugify_strips_emoji_and_keeps_spaces_as_hyphens() {
+        assert_eq!(slugify("🚀 Launch"), "-launch");
+        assert_eq!(slugify("  padded  "), "--padded--");
+    }
+
+    #[test]
+    fn test_slugger_deduplicates() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Example"), "example");
+        assert_eq!(slugger.slug("Example"), "example-1");
+        assert_eq!(slugger.slug("Example"), "example-2");
+        assert_eq!(slugger.slug("Other"), "other");
+    }
+
+    #[test]
+    fn test_slugger_avoids_collision_with_existing_suffix() {
+        let mut slugger = Slugger::default();
+        assert_eq!(slugger.slug("Foo 1"), "foo-1");
+        assert_eq!(slugger.slug("Foo"), "foo");
+        // "foo-1" is already taken by the first heading
+        assert_eq!(slugger.slug("Foo"), "foo-2");
+    }
+}
@@ -2,10 +2,16 @@ use dioxus::prelude::{spawn, ReadableExt, WritableExt};
 use dioxus_desktop::muda::accelerator::{Accelerator, Code, Modifiers};
 use dioxus_desktop::muda::{Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
 use dioxus_desktop::window;
-use std::path::PathBuf;
-
-use crate::components::content::set_preferences_tab_to_about;
-use crate::state::AppState;
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+use crate::components::content::{
+    capture_scroll_top, export_current_tab_as_html, print_current_tab,
+    set_preferences_tab_to_about, PrintDestination,
+};
+use crate::state::{
+    clear_recently_viewed, AppState, InlineDocument, InlineSource, TabContent, RECENTLY_VIEWED,
+};
 use crate::window::{self, CreateMainWindowConfigParams};
 
 /// Menu identifier enum
@@ -16,8 +22,16 @@ enum MenuId {
     NewTab,
     Open,
     OpenDirectory,
+    OpenFromClipboard,
+    ClearRecentlyViewed,
+    SaveAs,
+    ExportHtml,
+    Print,
+    SavePdf,
     CloseTab,
     CloseAllTabs,
+    ReopenClosedTab,
+    ReopenClosedWindow,
     CloseWindow,
     CloseAllChildWindows,
     CloseAllWindows,
@@ -28,6 +42,7 @@ enum MenuId {
     ZoomOut,
     GoBack,
     GoForward,
+    ShowHistory,
     GoToHomepage,
 }
 
@@ -40,8 +55,16 @@ impl MenuId {
             "file.new_tab" => Some(Self::NewTab),
             "file.open" => Some(Self::Open),
             "file.open_directory" => Some(Self::OpenDirectory),
+            "file.open_from_clipboard" => Some(Self::OpenFromClipboard),
+            "file.clear_recently_viewed" => Some(Self::ClearRecentlyViewed),
+            "file.save_as" => Some(Self::SaveAs),
+            "file.export_html" => Some(Self::ExportHtml),
+            "file.print" => Some(Self::Print),
+            "file.save_pdf" => Some(Self::SavePdf),
             "file.close_tab" => Some(Self::CloseTab),
             "file.close_all_tabs" => Some(Self::CloseAllTabs),
+            "file.reopen_closed_tab" => Some(Self::ReopenClosedTab),
+            "file.reopen_closed_window" => Some(Self::ReopenClosedWindow),
             "file.close_window" => Some(Self::CloseWindow),
             "window.close_all_child_windows" => Some(Self::CloseAllChildWindows),
             "window.close_all_windows" => Some(Self::CloseAllWindows),
@@ -52,6 +75,7 @@ impl MenuId {
             "view.zoom_out" => Some(Self::ZoomOut),
             "history.back" => Some(Self::GoBack),
             "history.forward" => Some(Self::GoForward),
+            "history.show_all" => Some(Self::ShowHistory),
             "help.homepage" => Some(Self::GoToHomepage),
             _ => None,
         }
@@ -65,8 +89,16 @@ impl MenuId {
             Self::NewTab => "file.new_tab",
             Self::Open => "file.open",
             Self::OpenDirectory => "file.open_directory",
+            Self::OpenFromClipboard => "file.open_from_clipboard",
+            Self::ClearRecentlyViewed => "file.clear_recently_viewed",
+            Self::SaveAs => "file.save_as",
+            Self::ExportHtml => "file.export_html",
+            Self::Print => "file.print",
+            Self::SavePdf => "file.save_pdf",
             Self::CloseTab => "file.close_tab",
             Self::CloseAllTabs => "file.close_all_tabs",
+            Self::ReopenClosedTab => "file.reopen_closed_tab",
+            Self::ReopenClosedWindow => "file.reopen_closed_window",
             Self::CloseWindow => "file.close_window",
             Self::CloseAllChildWindows => "window.close_all_child_windows",
             Self::CloseAllWindows => "window.close_all_windows",
@@ -77,11 +109,30 @@ impl MenuId {
             Self::ZoomOut => "view.zoom_out",
             Self::GoBack => "history.back",
             Self::GoForward => "history.forward",
+            Self::ShowHistory => "history.show_all",
             Self::GoToHomepage => "help.homepage",
         }
     }
 }
 
+/// Prefix of the IDs of File > Open Recent items (followed by the file path)
+const OPEN_RECENT_ID_PREFIX: &str = "file.open_recent:";
+
+/// Number of documents listed in File > Open Recent
+const OPEN_RECENT_LIMIT: usize = 15;
+
+thread_local! {
+    /// The File > Open Recent submenu (rebuilt whenever the history changes)
+    static OPEN_RECENT_MENU: RefCell<Option<Submenu>> = const { RefCell::new(None) };
+}
+
+/// Get the file of a File > Open Recent item
+fn open_recent_path(menu_id: &str) -> Option<PathBuf> {
+    menu_id
+        .strip_prefix(OPEN_RECENT_ID_PREFIX)
+        .map(PathBuf::from)
+}
+
 /// Helper to create a menu item with optional keyboard shortcut
 fn create_menu_item(
     id: MenuId,
@@ -133,6 +184,9 @@ fn add_app_menu(menu: &Menu) {
 
 fn add_file_menu(menu: &Menu) {
     let file_menu = Submenu::new("File", true);
+    let open_recent_menu = Submenu::new("Open Recent", true);
+    OPEN_RECENT_MENU.with(|cell| *cell.borrow_mut() = Some(open_recent_menu.clone()));
+    update_open_recent_menu();
 
     file_menu
         .append_items(&[
@@ -146,6 +200,33 @@ fn add_file_menu(menu: &Menu) {
                 Some(Code::KeyO),
                 Some(Modifiers::SHIFT),
             ),
+            &open_recent_menu,
+            &create_menu_item(
+                MenuId::OpenFromClipboard,
+                "Open from Clipboard",
+                Some(Code::KeyV),
+                Some(Modifiers::SHIFT),
+            ),
+            &PredefinedMenuItem::separator(),
+            &create_menu_item(
+                MenuId::SaveAs,
+                "Save As...",
+                Some(Code::KeyS),
+                Some(Modifiers::SHIFT),
+            ),
+            &create_menu_item(
+                MenuId::ExportHtml,
+                "Export as HTML...",
+                Some(Code::KeyE),
+                Some(Modifiers::SHIFT),
+            ),
+            &create_menu_item(
+                MenuId::SavePdf,
+                "Save as PDF...",
+                Some(Code::KeyP),
+                Some(Modifiers::SHIFT),
+            ),
+            &create_menu_item(MenuId::Print, "Print...", Some(Code::KeyP), None),
             &PredefinedMenuItem::separator(),
             &create_menu_item(MenuId::CloseTab, "Close Tab", Some(Code::KeyW), None),
             &create_menu_item(MenuId::CloseAllTabs, "Close All Tabs", None, None),
@@ -155,12 +236,66 @@ fn add_file_menu(menu: &Menu) {
                 Some(Code::KeyW),
                 Some(Modifiers::SHIFT),
             ),
+            &create_menu_item(
+                MenuId::ReopenClosedTab,
+                "Reopen Closed Tab",
+                Some(Code::KeyT),
+                Some(Modifiers::SHIFT),
+            ),
+            &create_menu_item(
+                MenuId::ReopenClosedWindow,
+                "Reopen Closed Window",
+                None,
+                None,
+            ),
         ])
         .unwrap();
 
     menu.append(&file_menu).unwrap();
 }
 
+/// Rebuild File > Open Recent from the recently viewed history
+pub fn update_open_recent_menu() {
+    OPEN_RECENT_MENU.with(|cell| {
+        let cell = cell.borrow();
+        let Some(open_recent_menu) = cell.as_ref() else {
+            return;
+        };
+        while open_recent_menu.remove_at(0).is_some() {}
+
+        let recent = RECENTLY_VIEWED.read();
+        let entries = &recent.entries()[..recent.entries().len().min(OPEN_RECENT_LIMIT)];
+        for entry in entries {
+            let title = entry.display_title();
+            // Tell documents with the same title apart by their directory
+            let is_ambiguous = entries
+                .iter()
+                .filter(|other| other.display_title() == title)
+                .count()
+                > 1;
+            let label = match entry.path.parent().and_then(Path::file_name) {
+                Some(dir) if is_ambiguous => format!("{title} — {}", dir.to_string_lossy()),
+                _ => title,
+            };
+            let id = format!("{OPEN_RECENT_ID_PREFIX}{}", entry.path.display());
+            open_recent_menu
+                .append(&MenuItem::with_id(id, label, true, None))
+                .unwrap();
+        }
+
+        let clear_item = create_menu_item(
+            MenuId::ClearRecentlyViewed,
+            "Clear Recently Viewed",
+            None,
+            None,
+        );
+        clear_item.set_enabled(!recent.entries().is_empty());
+        open_recent_menu
+            .append_items(&[&PredefinedMenuItem::separator(), &clear_item])
+            .unwrap();
+    });
+}
+
 fn add_edit_menu(menu: &Menu) {
     let edit_menu = Submenu::new("Edit", true);
 
@@ -210,6 +345,13 @@ fn add_history_menu(menu: &Menu) {
                 Some(Code::BracketRight),
                 None,
             ),
+            &PredefinedMenuItem::separator(),
+            &create_menu_item(
+                MenuId::ShowHistory,
+                "Show All History",
+                Some(Code::KeyY),
+                None,
+            ),
         ])
         .unwrap();
 
@@ -267,6 +409,18 @@ pub fn handle_menu_event_global(event: &MenuEvent) -> bool {
     let menu_id = event.id().0.as_ref();
     tracing::info!("Global menu event: {}", menu_id);
 
+    if let Some(file) = open_recent_path(menu_id) {
+        // Windows open the file themselves, see handle_menu_event_with_state
+        if window::has_any_main_windows() {
+            return false;
+        }
+        spawn(async move {
+            window::create_new_main_window_with_file(file, CreateMainWindowConfigParams::default())
+                .await;
+        });
+        return true;
+    }
+
     let id = match MenuId::from_str(menu_id) {
         Some(id) => id,
         None => return false,
@@ -291,6 +445,19 @@ pub fn handle_menu_event_global(event: &MenuEvent) -> bool {
             }
             return false;
         }
+        MenuId::ReopenClosedTab => {
+            // Without windows there are no closed tabs, so bring back the last window
+            if !window::has_any_main_windows() {
+                return window::reopen_closed_window();
+            }
+            return false;
+        }
+        MenuId::ReopenClosedWindow => {
+            window::reopen_closed_window();
+        }
+        MenuId::ClearRecentlyViewed => {
+            clear_recently_viewed();
+        }
         MenuId::Preferences => {
             // Preferences is now handled by state-based handler
             return false;
@@ -321,6 +488,11 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
     let menu_id = event.id().0.as_ref();
     tracing::debug!("State menu event (focused window): {}", menu_id);
 
+    if let Some(file) = open_recent_path(menu_id) {
+        state.open_file(file);
+        return true;
+    }
+
     let id = match MenuId::from_str(menu_id) {
         Some(id) => id,
         None => return false,
@@ -348,16 +520,42 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
                 state.set_root_directory(dir);
             }
         }
+        MenuId::OpenFromClipboard => match crate::utils::clipboard::read_text() {
+            Some(text) if !text.trim().is_empty() => {
+                // Relative links resolve against the directory shown in the sidebar
+                let base_dir = state.directory.read().clone();
+                state.open_inline_document(InlineDocument::new(
+                    text,
+                    InlineSource::Clipboard,
+                    base_dir,
+                ));
+            }
+            _ => tracing::info!("Clipboard has no text to open"),
+        },
+        MenuId::SaveAs => {
+            save_inline_tab_as(state);
+        }
+        MenuId::ExportHtml => {
+            spawn(export_current_tab_as_html(*state));
+        }
+        MenuId::Print => {
+            spawn(print_current_tab(*state, PrintDestination::Printer));
+        }
+        MenuId::SavePdf => {
+            spawn(print_current_tab(*state, PrintDestination::Pdf));
+        }
         MenuId::CloseTab => {
             let active_tab = *state.active_tab.read();
             state.close_tab(active_tab);
         }
         MenuId::CloseAllTabs => {
-            // Close all tabs except one, then clear it
-            let mut tabs = state.tabs.write();
-            tabs.clear();
-            tabs.push(crate::state::Tab::default());
-            state.active_tab.set(0);
+            state.close_all_tabs();
+        }
+        MenuId::ReopenClosedTab => {
+            // Fall back to the last closed window once this window has no closed tabs
+            if !state.reopen_closed_tab() {
+                window::reopen_closed_window();
+            }
         }
         MenuId::CloseWindow => {
             window().close();
@@ -379,19 +577,22 @@ pub fn handle_menu_event_with_state(event: &MenuEvent, state: &mut AppState) ->
             state.zoom_level.set((current - 0.1).max(0.1));
         }
         MenuId::GoBack => {
-            state.update_current_tab(|tab| {
-                if let Some(path) = tab.history.go_back() {
-                    tab.content = crate::state::TabContent::File(path.to_owned());
-                }
+            let mut state = *state;
+            spawn(async move {
+                capture_scroll_top(state).await;
+                state.go_back();
             });
         }
         MenuId::GoForward => {
-            state.update_current_tab(|tab| {
-                if let Some(path) = tab.history.go_forward() {
-                    tab.content = crate::state::TabContent::File(path.to_owned());
-                }
+            let mut state = *state;
+            spawn(async move {
+                capture_scroll_top(state).await;
+                state.go_forward();
             });
         }
+        MenuId::ShowHistory => {
+            state.open_history();
+        }
         _ => return false,
     }
 
@@ -415,6 +616,49 @@ fn pick_markdown_file() -> Option<PathBuf> {
     file
 }
 
+/// Save the markdown of the current inline tab to a file and show that file in the tab
+///
+/// Tabs that already show a file are left alone.
+fn save_inline_tab_as(state: &mut AppState) {
+    let Some(tab) = state.current_tab() else {
+        return;
+    };
+    let TabContent::Inline(document) = tab.content else {
+        tracing::debug!("Save As is only available for inline tabs");
+        return;
+    };
+    let file_name = tab
+        .title
+        .map(|title| format!("{}.md", title.replace(['/', ':'], "-")))
+        .unwrap_or_else(|| "Untitled.md".to_string());
+    let Some(file) = pick_save_file(&file_name, document.base_dir.as_deref()) else {
+        return;
+    };
+
+    match std::fs::write(&file, &document.markdown) {
+        Ok(()) => {
+            tracing::info!("Saved inline markdown to {:?}", file);
+            state.update_current_tab(|tab| tab.navigate_to(file));
+        }
+        Err(e) => tracing::error!("Failed to save {:?}: {}", file, e),
+    }
+}
+
+/// Show save dialog and return the selected file
+fn pick_save_file(file_name: &str, directory: Option<&Path>) -> Option<PathBuf> {
+    use rfd::FileDialog;
+
+    let directory = directory
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("/"));
+    FileDialog::new()
+        .add_filter("Markdown", &["md", "markdown"])
+        .set_directory(directory)
+        .set_file_name(file_name)
+        .save_file()
+}
+
 /// Show directory picker dialog and return selected directory
 fn pick_directory() -> Option<PathBuf> {
     use rfd::FileDialog;
@@ -0,0 +1,201 @@
+//! Single-instance support over a per-user Unix domain socket.
+//!
+//! The first process listens on the socket. Later invocations send their
+//! command-line arguments as a JSON line, wait for an acknowledgement and
+//! exit, so every file opens in the running application.
+
+use crate::cli::CliArgs;
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a client waits for the running instance
+const FORWARD_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Acknowledgement written back to the client once the request is queued
+const ACK: &str = "ok";
+
+/// Get the socket path of the current user
+///
+/// The socket lives in a directory of its own, so it can be made private before
+/// binding (the temporary directory may be shared with other users).
+/// Debug builds use their own socket so they do not forward to an installed release.
+pub fn socket_path() -> PathBuf {
+    let user = std::env::var("USER").unwrap_or_else(|_| "default".to_string());
+    let name = if cfg!(debug_assertions) {
+        format!("arto-dev-{user}")
+    } else {
+        format!("arto-{user}")
+    };
+    dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(name)
+        .join("arto.sock")
+}
+
+/// Create the directory of the socket, accessible to the current user only
+///
+/// An existing directory is made private too, which fails unless it belongs
+/// to the current user.
+fn create_private_dir(dir: &Path) -> anyhow::Result<()> {
+    match std::fs::DirBuilder::new().mode(0o700).create(dir) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            if !std::fs::symlink_metadata(dir)?.is_dir() {
+                anyhow::bail!("{} is not a directory", dir.display());
+            }
+            std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))?;
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Send the arguments to a running instance
+///
+/// Returns `false` when no instance is listening (or it did not acknowledge),
+/// in which case the caller should start the application itself.
+pub fn forward_to_running_instance(socket: &Path, args: &CliArgs) -> bool {
+    let Ok(mut stream) = UnixStream::connect(socket) else {
+        return false;
+    };
+    let result = (|| -> anyhow::Result<bool> {
+        stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+        stream.set_write_timeout(Some(FORWARD_TIMEOUT))?;
+        let mut request = serde_json::to_string(args)?;
+        request.push('\n');
+        stream.write_all(request.as_bytes())?;
+
+        let mut response = String::new();
+        BufReader::new(&stream).read_line(&mut response)?;
+        Ok(response.trim_end() == ACK)
+    })();
+    match result {
+        Ok(acknowledged) => acknowledged,
+        Err(e) => {
+            tracing::warn!(?socket, "Failed to forward to the running instance: {e}");
+            false
+        }
+    }
+}
+
+/// Listen for arguments forwarded by later invocations
+///
+/// Each request is passed to `on_request` on a background thread. A stale
+/// socket file left by a crashed process is replaced, but a socket another
+/// instance is still listening on is not. Call [`remove_socket`] when quitting.
+pub fn listen(socket: &Path, on_request: impl Fn(CliArgs) + Send + 'static) -> anyhow::Result<()> {
+    if let Some(dir) = socket.parent() {
+        create_private_dir(dir)?;
+    }
+    if socket.exists() {
+        if UnixStream::connect(socket).is_ok() {
+            anyhow::bail!("another instance is listening on {}", socket.display());
+        }
+        std::fs::remove_file(socket)?;
+    }
+    let listener = UnixListener::bind(socket)?;
+    std::fs::set_permissions(socket, std::fs::Permissions::from_mode(0o600))?;
+
+    std::thread::Builder::new()
+        .name("single-instance".to_string())
+        .spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream
+                    .map_err(anyhow::Error::from)
+                    .and_then(|stream| handle_client(stream, &on_request));
+                if let Err(e) = result {
+                    tracing::warn!("Failed to handle forwarded invocation: {e}");
+                }
+            }
+        })?;
+    Ok(())
+}
+
+/// Remove the socket of this instance so later invocations start a new one
+pub fn remove_socket(socket: &Path) {
+    if let Err(e) = std::fs::remove_file(socket) {
+        tracing::warn!(?socket, "Failed to remove the single-instance socket: {e}");
+    }
+}
+
+fn handle_client(stream: UnixStream, on_request: &impl Fn(CliArgs)) -> anyhow::Result<()> {
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT))?;
+    let mut request = String::new();
+    BufReader::new(&stream).read_line(&mut request)?;
+    let args: CliArgs = serde_json::from_str(&request)?;
+    tracing::debug!(?args, "Received forwarded invocation");
+    on_request(args);
+    (&stream).write_all(format!("{ACK}\n").as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::OpenTarget;
+    use std::sync::mpsc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_forward_without_instance() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("arto.sock");
+        assert!(!forward_to_running_instance(&socket, &CliArgs::default()));
+    }
+
+    #[test]
+    fn test_forward_to_listener() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("arto.sock");
+        // A stale socket file from a crashed process is replaced
+        std::fs::write(&socket, "").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        listen(&socket, move |args| tx.send(args).unwrap()).unwrap();
+
+        let args = CliArgs {
+            targets: vec![OpenTarget {
+                path: PathBuf::from("/notes/README.md"),
+                anchor: Some("L10".to_string()),
+            }],
+            new_window: true,
+            ..CliArgs::default()
+        };
+        assert!(forward_to_running_instance(&socket, &args));
+        assert_eq!(rx.recv_timeout(FORWARD_TIMEOUT).unwrap(), args);
+
+        // The socket is in use, so a second listener must not take it over
+        assert!(listen(&socket, |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_listen_in_private_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("arto-test");
+        // A directory left readable by others is made private again
+        std::fs::create_dir(&dir).unwrap();
+        std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let socket = dir.join("arto.sock");
+
+        listen(&socket, |_| {}).unwrap();
+        let mode = std::fs::metadata(&dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        remove_socket(&socket);
+        assert!(!socket.exists());
+        assert!(!forward_to_running_instance(&socket, &CliArgs::default()));
+    }
+
+    #[test]
+    fn test_listen_rejects_symlinked_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("target")).unwrap();
+        let dir = temp_dir.path().join("arto-test");
+        std::os::unix::fs::symlink(temp_dir.path().join("target"), &dir).unwrap();
+
+        assert!(listen(&dir.join("arto.sock"), |_| {}).is_err());
+    }
+}
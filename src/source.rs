@@ -0,0 +1,223 @@
+use std::path::Path;
+
+/// Maximum number of lines rendered in the source view
+const MAX_SOURCE_LINES: usize = 50_000;
+
+/// Detect the highlight.js language of a source file
+///
+/// The file name and extension are checked first, then the shebang line.
+pub fn detect_language(path: &Path, content: &str) -> Option<&'static str> {
+    language_from_path(path).or_else(|| language_from_shebang(content))
+}
+
+/// Detect the highlight.js language from a file name or extension
+pub fn language_from_path(path: &Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_str()?;
+    let language = match file_name {
+        "Makefile" | "makefile" | "GNUmakefile" => "makefile",
+        "Dockerfile" | "Containerfile" => "dockerfile",
+        "CMakeLists.txt" => "cmake",
+        "Gemfile" | "Rakefile" => "ruby",
+        ".bashrc" | ".bash_profile" | ".zshrc" | ".profile" => "bash",
+        ".gitignore" | ".gitattributes" | ".editorconfig" => "ini",
+        _ => {
+            let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+            language_from_extension(&ext)?
+        }
+    };
+    Some(language)
+}
+
+fn language_from_extension(ext: &str) -> Option<&'static str> {
+    let language = match ext {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" | "jsx" => "javascript",
+        "ts" | "mts" | "cts" | "tsx" => "typescript",
+        "json" | "jsonc" | "json5" => "json",
+        "toml" | "ini" | "cfg" | "conf" => "ini",
+        "yaml" | "yml" => "yaml",
+        "xml" | "svg" | "plist" | "html" | "htm" | "vue" | "svelte" => "xml",
+        "css" => "css",
+        "scss" => "scss",
+        "less" => "less",
+        "sh" | "bash" | "zsh" | "fish" => "bash",
+        "ps1" | "psm1" => "powershell",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" | "hxx" => "cpp",
+        "cs" => "csharp",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "scala" => "scala",
+        "swift" => "swift",
+        "m" | "mm" => "objectivec",
+        "rb" => "ruby",
+        "php" => "php",
+        "pl" | "pm" => "perl",
+        "lua" => "lua",
+        "r" => "r",
+        "dart" => "dart",
+        "ex" | "exs" => "elixir",
+        "erl" | "hrl" => "erlang",
+        "hs" => "haskell",
+        "ml" | "mli" => "ocaml",
+        "clj" | "cljs" | "edn" => "clojure",
+        "vim" => "vim",
+        "sql" => "sql",
+        "graphql" | "gql" => "graphql",
+        "proto" => "protobuf",
+        "nix" => "nix",
+        "zig" => "zig",
+        "diff" | "patch" => "diff",
+        "dockerfile" => "dockerfile",
+        "mk" => "makefile",
+        "cmake" => "cmake",
+        "tex" => "latex",
+        "txt" | "text" | "log" | "csv" | "tsv" => "plaintext",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Detect the highlight.js language from a `#!` interpreter line
+fn language_from_shebang(content: &str) -> Option<&'static str> {
+    let line = content.lines().next()?.strip_prefix("#!")?;
+    let mut parts = line.split_whitespace();
+    let mut interpreter = parts.next()?.rsplit('/').next()?;
+    if interpreter == "env" {
+        // Skip `env` options such as `-S`
+        interpreter = parts.find(|part| !part.starts_with('-'))?;
+    }
+    // Strip version suffixes (e.g. "python3.12" -> "python")
+    let name = interpreter.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    let language = match name {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" | "fish" => "bash",
+        "python" => "python",
+        "node" | "nodejs" => "javascript",
+        "deno" | "bun" | "ts-node" => "typescript",
+        "ruby" => "ruby",
+        "perl" => "perl",
+        "php" => "php",
+        "lua" => "lua",
+        "Rscript" => "r",
+        _ => return None,
+    };
+    Some(language)
+}
+
+/// Render a source file as a line-numbered table (highlighted by the frontend)
+pub fn render_source_html(content: &str, language: Option<&str>) -> String {
+    let language_attr = language
+        .map(|language| format!(r#" data-language="{}""#, language))
+        .unwrap_or_default();
+
+    let mut html = format!(
+        r#"<div class="source-viewer"{}><table class="source-table"><tbody>"#,
+        language_attr
+    );
+    let mut lines = content.lines();
+    for (index, line) in lines.by_ref().take(MAX_SOURCE_LINES).enumerate() {
+        let number = index + 1;
+        html.push_str(&format!(
+            r#"<tr class="source-line" id="L{number}"><td class="source-line-number" data-line-number="{number}"></td><td class="source-line-code">{}</td></tr>"#,
+            html_escape::encode_text(line)
+        ));
+    }
+    html.push_str("</tbody></table>");
+    if lines.next().is_some() {
+        html.push_str(&format!(
+            r#"<p class="source-truncated">Only the first {} lines are shown.</p>"#,
+            MAX_SOURCE_LINES
+        ));
+    }
+    html.push_str("</div>");
+    html
+}
+
+/// Parse a GitHub-style line fragment (`L10` or `L10-L20`) into an inclusive range
+pub fn parse_line_range(fragment: &str) -> Option<(usize, usize)> {
+    let (start, end) = match fragment.split_once('-') {
+        Some((start, end)) => (start, Some(end)),
+        None => (fragment, None),
+    };
+    let start: usize = start.strip_prefix('L')?.parse().ok()?;
+    let end: usize = match end {
+        Some(end) => end.strip_prefix('L').unwrap_or(end).parse().ok()?,
+        None => start,
+    };
+    if start == 0 || end == 0 {
+        return None;
+    }
+    Some((start.min(end), start.max(end)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_path() {
+        assert_eq!(language_from_path(Path::new("src/lib.rs")), Some("rust"));
+        assert_eq!(language_from_path(Path::new("config.YAML")), Some("yaml"));
+        assert_eq!(language_from_path(Path::new("Cargo.toml")), Some("ini"));
+        assert_eq!(language_from_path(Path::new("Makefile")), Some("makefile"));
+        assert_eq!(
+            language_from_path(Path::new("notes.txt")),
+            Some("plaintext")
+        );
+        assert_eq!(language_from_path(Path::new("README.md")), None);
+        assert_eq!(language_from_path(Path::new("script")), None);
+    }
+
+    #[test]
+    fn test_detect_language_from_shebang() {
+        let path = Path::new("bin/tool");
+        assert_eq!(detect_language(path, "#!/bin/bash\necho hi"), Some("bash"));
+        assert_eq!(
+            detect_language(path, "#!/usr/bin/env python3\n"),
+            Some("python")
+        );
+        assert_eq!(
+            detect_language(path, "#!/usr/bin/env -S deno run\n"),
+            Some("typescript")
+        );
+        assert_eq!(detect_language(path, "no shebang"), None);
+        // The extension takes precedence over the shebang
+        assert_eq!(
+            detect_language(Path::new("tool.rb"), "#!/bin/sh\n"),
+            Some("ruby")
+        );
+    }
+
+    #[test]
+    fn test_render_source_html() {
+        let html = render_source_html("fn main() {\n    println!(\"<hi>\");\n}\n", Some("rust"));
+
+        assert!(html.starts_with(r#"<div class="source-viewer" data-language="rust">"#));
+        assert!(html.contains(r#"<tr class="source-line" id="L1">"#));
+        assert!(html.contains(r#"<tr class="source-line" id="L3">"#));
+        assert!(!html.contains(r#"id="L4""#));
+        assert!(html.contains(r#"data-line-number="2""#));
+        assert!(html.contains("println!(\"&lt;hi&gt;\");"));
+        assert!(!html.contains("source-truncated"));
+    }
+
+    #[test]
+    fn test_render_source_html_without_language() {
+        let html = render_source_html("plain", None);
+        assert!(html.starts_with(r#"<div class="source-viewer">"#));
+    }
+
+    #[test]
+    fn test_parse_line_range() {
+        assert_eq!(parse_line_range("L10"), Some((10, 10)));
+        assert_eq!(parse_line_range("L10-L20"), Some((10, 20)));
+        assert_eq!(parse_line_range("L10-20"), Some((10, 20)));
+        assert_eq!(parse_line_range("L20-L10"), Some((10, 20)));
+        assert_eq!(parse_line_range("L0"), None);
+        assert_eq!(parse_line_range("installation"), None);
+        assert_eq!(parse_line_range("L10-"), None);
+        assert_eq!(parse_line_range("l10"), None);
+    }
+}
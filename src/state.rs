@@ -1,7 +1,18 @@
 // State module - manages application state
 
 mod app_state;
-pub use app_state::{AppState, Tab, TabContent};
+pub use app_state::{AppState, ClosedTabs, InlineDocument, InlineSource, Tab, TabContent};
 
 mod persistence;
 pub use persistence::{PersistedState, Position, Size, LAST_FOCUSED_STATE};
+
+mod recently_viewed;
+pub use recently_viewed::{
+    clear_recently_viewed, flush_recently_viewed, record_recent_visit, remove_recent_visit,
+    unix_now, RecentEntry, RECENTLY_VIEWED,
+};
+
+mod session;
+pub use session::{
+    end_session, remove_session_window, update_session_window, Session, SessionWindow,
+};
@@ -3,13 +3,16 @@ use dioxus::prelude::*;
 use std::path::PathBuf;
 
 use super::persistence::LAST_FOCUSED_STATE;
+use crate::markdown::Heading;
 use crate::theme::Theme;
 
+mod closed_tabs;
 mod sidebar;
 mod tabs;
 
+pub use closed_tabs::ClosedTabs;
 pub use sidebar::Sidebar;
-pub use tabs::{Tab, TabContent};
+pub use tabs::{InlineDocument, InlineSource, Tab, TabContent};
 
 /// Per-window application state.
 ///
@@ -36,10 +39,14 @@ pub use tabs::{Tab, TabContent};
 pub struct AppState {
     pub tabs: Signal<Vec<Tab>>,
     pub active_tab: Signal<usize>,
+    /// Recently closed tabs of this window, for "Reopen Closed Tab"
+    pub closed_tabs: Signal<ClosedTabs>,
     pub current_theme: Signal<Theme>,
     pub zoom_level: Signal<f64>,
     pub directory: Signal<Option<PathBuf>>,
     pub sidebar: Signal<Sidebar>,
+    /// Headings of the document shown in the active tab
+    pub outline: Signal<Vec<Heading>>,
     pub position: Signal<LogicalPosition<i32>>,
     pub size: Signal<LogicalSize<u32>>,
 }
@@ -49,10 +56,12 @@ impl Default for AppState {
         Self {
             tabs: Signal::new(vec![Tab::default()]),
             active_tab: Signal::new(0),
+            closed_tabs: Signal::new(ClosedTabs::default()),
             current_theme: Signal::new(LAST_FOCUSED_STATE.read().theme),
             zoom_level: Signal::new(1.0),
             directory: Signal::new(None),
             sidebar: Signal::new(Sidebar::default()),
+            outline: Signal::new(Vec::new()),
             position: Signal::new(Default::default()),
             size: Signal::new(Default::default()),
         }
@@ -0,0 +1,94 @@
+use super::{Tab, TabContent};
+use std::collections::VecDeque;
+
+/// Maximum number of closed tabs remembered per window
+pub const MAX_CLOSED_TABS: usize = 25;
+
+/// A closed tab and the index it was closed at
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClosedTab {
+    pub tab: Tab,
+    pub index: usize,
+}
+
+/// Bounded stack of recently closed tabs (the oldest entry is dropped first)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClosedTabs {
+    entries: VecDeque<ClosedTab>,
+}
+
+impl ClosedTabs {
+    /// Remember a closed tab (empty tabs are not worth reopening)
+    pub fn push(&mut self, tab: Tab, index: usize) {
+        if tab.content == TabContent::None {
+            return;
+        }
+        if self.entries.len() == MAX_CLOSED_TABS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(ClosedTab { tab, index });
+    }
+
+    /// Take the most recently closed tab
+    pub fn pop(&mut self) -> Option<ClosedTab> {
+        self.entries.pop_back()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_closed_tabs_pop_most_recent_first() {
+        let mut closed = ClosedTabs::default();
+        closed.push(Tab::new("/test/a.md"), 0);
+        closed.push(Tab::new("/test/b.md"), 3);
+
+        let entry = closed.pop().unwrap();
+        assert_eq!(entry.index, 3);
+        assert_eq!(entry.tab.file(), Some(std::path::Path::new("/test/b.md")));
+        assert_eq!(closed.pop().unwrap().index, 0);
+        assert!(closed.pop().is_none());
+    }
+
+    #[test]
+    fn test_closed_tabs_skip_empty_tabs() {
+        let mut closed = ClosedTabs::default();
+        closed.push(Tab::default(), 0);
+        assert!(closed.is_empty());
+    }
+
+    #[test]
+    fn test_closed_tabs_keep_history() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.navigate_to("/test/b.md");
+        tab.history.go_back();
+        tab.scroll_top = 420.0;
+
+        let mut closed = ClosedTabs::default();
+        closed.push(tab.clone(), 1);
+        let entry = closed.pop().unwrap();
+        assert_eq!(entry.tab, tab);
+        assert!(entry.tab.history.can_go_forward());
+    }
+
+    #[test]
+    fn test_closed_tabs_are_bounded() {
+        let mut closed = ClosedTabs::default();
+        for index in 0..MAX_CLOSED_TABS + 5 {
+            closed.push(Tab::new(format!("/test/{index}.md")), index);
+        }
+        let indices: Vec<_> = std::iter::from_fn(|| closed.pop())
+            .map(|entry| entry.index)
+            .collect();
+        assert_eq!(indices.len(), MAX_CLOSED_TABS);
+        assert_eq!(indices.first(), Some(&(MAX_CLOSED_TABS + 4)));
+        // The oldest entries were dropped
+        assert_eq!(indices.last(), Some(&5));
+    }
+}
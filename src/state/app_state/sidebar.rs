@@ -1,5 +1,6 @@
 use super::super::persistence::LAST_FOCUSED_STATE;
 use super::AppState;
+use crate::config::SidebarPanel;
 use dioxus::prelude::*;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
@@ -11,6 +12,9 @@ pub struct Sidebar {
     pub expanded_dirs: HashSet<PathBuf>,
     pub width: f64,
     pub show_all_files: bool,
+    pub panel: SidebarPanel,
+    /// Whether changed Markdown files under the root directory are opened automatically
+    pub follow: bool,
 }
 
 impl Default for Sidebar {
@@ -20,6 +24,8 @@ impl Default for Sidebar {
             expanded_dirs: HashSet::new(),
             width: 280.0,
             show_all_files: false,
+            panel: SidebarPanel::default(),
+            follow: false,
         }
     }
 }
@@ -44,6 +50,18 @@ impl AppState {
         LAST_FOCUSED_STATE.write().sidebar_open = sidebar.open;
     }
 
+    /// Switch the panel shown in the sidebar
+    pub fn set_sidebar_panel(&mut self, panel: SidebarPanel) {
+        self.sidebar.write().panel = panel;
+        LAST_FOCUSED_STATE.write().sidebar_panel = panel;
+    }
+
+    /// Toggle following the root directory
+    pub fn toggle_follow_directory(&mut self) {
+        let mut sidebar = self.sidebar.write();
+        sidebar.follow = !sidebar.follow;
+    }
+
     /// Toggle directory expansion state
     pub fn toggle_directory_expansion(&mut self, path: impl AsRef<Path>) {
         let mut sidebar = self.sidebar.write();
@@ -62,7 +80,9 @@ mod tests {
         assert!(!sidebar.open);
         assert_eq!(sidebar.width, 280.0);
         assert!(!sidebar.show_all_files);
+        assert_eq!(sidebar.panel, SidebarPanel::Explorer);
         assert!(sidebar.expanded_dirs.is_empty());
+        assert!(!sidebar.follow);
     }
 
     #[test]
@@ -1,22 +1,80 @@
+use super::closed_tabs::ClosedTab;
 use super::AppState;
 use crate::history::HistoryManager;
 use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Content source for a tab
-#[derive(Debug, Clone, PartialEq, Default)]
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum TabContent {
     /// No content (shows NoFile component)
     #[default]
     None,
     /// File from filesystem
     File(PathBuf),
-    /// Inline markdown content (for welcome screen)
-    Inline(String),
+    /// Markdown without a backing file (welcome screen, standard input or clipboard)
+    Inline(InlineDocument),
     /// File that cannot be opened (binary or error)
     FileError(PathBuf, String),
     /// Preferences page (browser-style settings)
     Preferences,
+    /// Recently viewed documents of all windows
+    History,
+}
+
+/// Where the markdown of an inline tab came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineSource {
+    #[default]
+    Welcome,
+    Stdin,
+    Clipboard,
+}
+
+impl InlineSource {
+    /// Name shown when the document has no title
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Welcome => "Welcome",
+            Self::Stdin => "Standard Input",
+            Self::Clipboard => "Clipboard",
+        }
+    }
+}
+
+/// Markdown shown in a tab without a backing file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct InlineDocument {
+    pub markdown: String,
+    pub source: InlineSource,
+    /// Directory relative links and images are resolved against
+    pub base_dir: Option<PathBuf>,
+}
+
+impl InlineDocument {
+    pub fn new(
+        markdown: impl Into<String>,
+        source: InlineSource,
+        base_dir: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            markdown: markdown.into(),
+            source,
+            base_dir,
+        }
+    }
+
+    /// Path the document is rendered as if it were a file in the base directory
+    pub fn base_path(&self) -> PathBuf {
+        self.base_dir
+            .as_deref()
+            .unwrap_or(Path::new("."))
+            .join("Untitled.md")
+    }
 }
 
 /// Represents a single tab with its content and navigation history
@@ -24,6 +82,22 @@ pub enum TabContent {
 pub struct Tab {
     pub content: TabContent,
     pub history: HistoryManager,
+    /// Heading anchor to scroll to once the file has been rendered
+    pub pending_anchor: Option<String>,
+    /// Title declared in the front matter of the rendered document
+    pub title: Option<String>,
+    /// Last known scroll offset of the document
+    pub scroll_top: f64,
+    /// Scroll offset to restore once the document has been rendered
+    pub pending_scroll: Option<f64>,
+    /// Whether showing the document counts as a visit in the recently viewed history
+    pub pending_visit: bool,
+    /// Whether changes to the file are left unseen until auto-reload is resumed
+    pub auto_reload_paused: bool,
+    /// Whether the tab shows the files changed in the followed directory
+    pub follows_directory: bool,
+    /// Whether the file was deleted while shown (its last content is kept)
+    pub file_deleted: bool,
 }
 
 impl Tab {
@@ -32,14 +106,36 @@ impl Tab {
         let mut history = HistoryManager::new();
         history.push(file.clone());
         let content = TabContent::File(file);
-        Self { content, history }
+        Self {
+            content,
+            history,
+            pending_anchor: None,
+            title: None,
+            scroll_top: 0.0,
+            pending_scroll: None,
+            pending_visit: true,
+            auto_reload_paused: false,
+            follows_directory: false,
+            file_deleted: false,
+        }
     }
 
     pub fn with_inline_content(content: impl Into<String>) -> Self {
-        let content = content.into();
+        Self::with_inline_document(InlineDocument::new(content, InlineSource::Welcome, None))
+    }
+
+    pub fn with_inline_document(document: InlineDocument) -> Self {
         Self {
-            content: TabContent::Inline(content),
+            content: TabContent::Inline(document),
             history: HistoryManager::new(),
+            pending_anchor: None,
+            title: None,
+            scroll_top: 0.0,
+            pending_scroll: None,
+            pending_visit: false,
+            auto_reload_paused: false,
+            follows_directory: false,
+            file_deleted: false,
         }
     }
 
@@ -51,19 +147,124 @@ impl Tab {
         }
     }
 
-    /// Check if this tab has no file (None, Inline, or FileError)
+    /// Check if this tab has no file (None, the welcome screen, or FileError)
+    ///
+    /// Markdown from standard input or the clipboard exists nowhere else, so
+    /// such tabs are never replaced.
     pub fn is_no_file(&self) -> bool {
-        matches!(
-            self.content,
-            TabContent::None | TabContent::Inline(_) | TabContent::FileError(_, _)
-        )
+        match &self.content {
+            TabContent::None | TabContent::FileError(_, _) => true,
+            TabContent::Inline(document) => document.source == InlineSource::Welcome,
+            TabContent::File(_) | TabContent::Preferences | TabContent::History => false,
+        }
+    }
+
+    /// Scroll back to the last known offset once the document is rendered again
+    pub fn restore_scroll(&mut self) {
+        if self.scroll_top > 0.0 {
+            self.pending_scroll = Some(self.scroll_top);
+        }
     }
 
     /// Navigate to a file in this tab
     pub fn navigate_to(&mut self, file: impl Into<PathBuf>) {
+        self.navigate_to_with_anchor(file, None);
+    }
+
+    /// Navigate to a file in this tab, scrolling to the anchor once it is rendered
+    pub fn navigate_to_with_anchor(&mut self, file: impl Into<PathBuf>, anchor: Option<String>) {
         let file = file.into();
-        self.history.push(file.clone());
+        self.remember_scroll();
+        self.history.push_with_anchor(file.clone(), anchor.clone());
         self.content = TabContent::File(file);
+        self.title = None;
+        self.file_deleted = false;
+        self.scroll_top = 0.0;
+        self.pending_anchor = anchor;
+        self.pending_scroll = None;
+        self.pending_visit = true;
+    }
+
+    /// Show a file changed in the followed directory unless it is already shown
+    pub fn follow(&mut self, file: &Path) {
+        if self.file() != Some(file) {
+            self.navigate_to(file);
+        }
+    }
+
+    /// Go back in history, returning to where the previous document was left
+    pub fn go_back(&mut self) -> bool {
+        self.remember_scroll();
+        if self.history.go_back().is_none() {
+            return false;
+        }
+        self.show_history_entry();
+        true
+    }
+
+    /// Go forward in history, returning to where the next document was left
+    pub fn go_forward(&mut self) -> bool {
+        self.remember_scroll();
+        if self.history.go_forward().is_none() {
+            return false;
+        }
+        self.show_history_entry();
+        true
+    }
+
+    /// Jump to a history entry, returning to where its document was left
+    pub fn go_to_history(&mut self, index: usize) -> bool {
+        if index == self.history.current_index() {
+            return false;
+        }
+        self.remember_scroll();
+        if self.history.go_to(index).is_none() {
+            return false;
+        }
+        self.show_history_entry();
+        true
+    }
+
+    /// Follow a file of this tab that was moved, keeping the scroll offset
+    pub fn retarget(&mut self, from: &Path, to: &Path) -> bool {
+        if self.file() != Some(from) {
+            return false;
+        }
+        self.history.rename(from, to);
+        self.content = TabContent::File(to.to_path_buf());
+        self.file_deleted = false;
+        self.restore_scroll();
+        true
+    }
+
+    /// Store the scroll offset in the history entry of the document shown
+    fn remember_scroll(&mut self) {
+        let shows_current_entry = matches!(
+            &self.content,
+            TabContent::File(path) if self.history.current() == Some(path.as_path())
+        );
+        if shows_current_entry {
+            self.history.set_scroll_top(self.scroll_top);
+        }
+    }
+
+    /// Show the current history entry at its scroll offset (or else at its anchor)
+    fn show_history_entry(&mut self) {
+        let Some(entry) = self.history.current_entry().cloned() else {
+            return;
+        };
+        self.content = TabContent::File(entry.path);
+        self.title = None;
+        self.file_deleted = false;
+        self.scroll_top = entry.scroll_top;
+        self.pending_visit = true;
+        if entry.scroll_top > 0.0 {
+            self.pending_scroll = Some(entry.scroll_top);
+            self.pending_anchor = None;
+        } else {
+            self.pending_scroll = None;
+            self.pending_anchor = entry.anchor;
+        }
     }
 }
 
@@ -96,17 +297,66 @@ impl AppState {
         }
     }
 
-    /// Close a tab at index
+    /// Follow a moved file in every tab showing it
+    pub fn retarget_tabs(&mut self, from: &Path, to: &Path) {
+        for tab in self.tabs.write().iter_mut() {
+            tab.retarget(from, to);
+        }
+    }
+
+    /// Mark whether the file shown by tabs was deleted (skips the write when unchanged)
+    pub fn set_file_deleted(&mut self, file: &Path, deleted: bool) {
+        let changed = self
+            .tabs
+            .peek()
+            .iter()
+            .any(|tab| tab.file() == Some(file) && tab.file_deleted != deleted);
+        if !changed {
+            return;
+        }
+        for tab in self.tabs.write().iter_mut() {
+            if tab.file() == Some(file) {
+                tab.file_deleted = deleted;
+            }
+        }
+    }
+
+    /// Close a tab at index and remember it for "Reopen Closed Tab"
     /// If all tabs are removed, automatically adds an empty tab to keep window open
     /// Returns true if tab was closed, false if index was invalid
     pub fn close_tab(&mut self, index: usize) -> bool {
+        match self.remove_tab(index) {
+            Some(tab) => {
+                self.closed_tabs.write().push(tab, index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Close all tabs, leaving a single empty tab
+    pub fn close_all_tabs(&mut self) {
+        let tabs = std::mem::replace(&mut *self.tabs.write(), vec![Tab::default()]);
+        self.active_tab.set(0);
+        // Remember the last tab first so that reopening restores them in order
+        let mut closed_tabs = self.closed_tabs.write();
+        for (index, tab) in tabs.into_iter().enumerate().rev() {
+            closed_tabs.push(tab, index);
+        }
+    }
+
+    /// Remove a tab at index without remembering it as closed
+    ///
+    /// Used when the tab lives on in another window (tab transfer or "Open in New Window").
+    /// If all tabs are removed, automatically adds an empty tab to keep window open
+    pub fn remove_tab(&mut self, index: usize) -> Option<Tab> {
         let mut tabs = self.tabs.write();
 
         if index >= tabs.len() {
-            return false;
+            return None;
         }
 
-        tabs.remove(index);
+        let tab = tabs.remove(index);
 
         // Update active tab index
         let current_active = *self.active_tab.read();
@@ -128,6 +378,30 @@ impl AppState {
             self.active_tab.set(0);
         }
 
+        Some(tab)
+    }
+
+    /// Reopen the most recently closed tab at its original index
+    ///
+    /// The tab keeps its history and scrolls back to where it was once rendered.
+    /// A lone empty tab is replaced. Returns false if there is no closed tab.
+    pub fn reopen_closed_tab(&mut self) -> bool {
+        let Some(ClosedTab { mut tab, index }) = self.closed_tabs.write().pop() else {
+            return false;
+        };
+        tab.restore_scroll();
+
+        let replace_empty = {
+            let tabs = self.tabs.read();
+            tabs.len() == 1 && tabs[0].content == TabContent::None
+        };
+        if replace_empty {
+            self.tabs.write()[0] = tab;
+            self.active_tab.set(0);
+        } else {
+            let index = self.insert_tab(tab, index);
+            self.switch_to_tab(index);
+        }
         true
     }
 
@@ -168,8 +442,8 @@ impl AppState {
         }
     }
 
-    /// Check if the current active tab has no file (NoFile tab, Inline content, or FileError)
-    /// None, Inline content, and FileError can be replaced when opening a file
+    /// Check if the current active tab has no file (NoFile tab, welcome screen, or FileError)
+    /// None, the welcome screen, and FileError can be replaced when opening a file
     pub fn is_current_tab_no_file(&self) -> bool {
         self.current_tab()
             .map(|tab| tab.is_no_file())
@@ -203,43 +477,197 @@ impl AppState {
         }
     }
 
+    /// Open a file changed in the followed directory
+    /// With a dedicated tab, the tab following the directory is retargeted instead of opening a new one
+    pub fn follow_file(&mut self, file: impl AsRef<Path>, dedicated_tab: bool) {
+        let file = file.as_ref();
+        if !dedicated_tab {
+            self.open_file(file);
+            return;
+        }
+        let follow_index = self
+            .tabs
+            .read()
+            .iter()
+            .position(|tab| tab.follows_directory);
+        match follow_index {
+            Some(index) => {
+                if let Some(tab) = self.tabs.write().get_mut(index) {
+                    tab.follow(file);
+                }
+                self.switch_to_tab(index);
+            }
+            None => {
+                let mut tab = Tab::new(file);
+                tab.follows_directory = true;
+                self.add_tab(tab, true);
+            }
+        }
+    }
+
+    /// Open inline markdown, reusing the current tab if it has no file
+    pub fn open_inline_document(&mut self, document: InlineDocument) {
+        if self.is_current_tab_no_file() {
+            self.update_current_tab(|tab| {
+                *tab = Tab::with_inline_document(document);
+            });
+        } else {
+            self.add_tab(Tab::with_inline_document(document), true);
+        }
+    }
+
+    /// Go back in the history of the current tab
+    pub fn go_back(&mut self) {
+        self.update_current_tab(|tab| {
+            tab.go_back();
+        });
+    }
+
+    /// Go forward in the history of the current tab
+    pub fn go_forward(&mut self) {
+        self.update_current_tab(|tab| {
+            tab.go_forward();
+        });
+    }
+
+    /// Jump to an entry in the history of the current tab
+    pub fn go_to_history(&mut self, index: usize) {
+        self.update_current_tab(|tab| {
+            tab.go_to_history(index);
+        });
+    }
+
+    /// Open a file like `open_file`, scrolling to the anchor once it is rendered
+    pub fn open_file_with_anchor(&mut self, file: impl AsRef<Path>, anchor: Option<String>) {
+        self.open_file(file);
+        if anchor.is_some() {
+            self.update_current_tab(|tab| {
+                tab.pending_anchor = anchor;
+            });
+        }
+    }
+
     /// Navigate to a file in the current tab (for in-tab navigation like markdown links)
-    /// Always opens in current tab regardless of whether file is open elsewhere
-    pub fn navigate_to_file(&mut self, file: impl Into<PathBuf>) {
+    /// Always opens in current tab regardless of whether file is open elsewhere.
+    /// The anchor (if any) is scrolled to once the file has been rendered.
+    pub fn navigate_to_file_with_anchor(
+        &mut self,
+        file: impl Into<PathBuf>,
+        anchor: Option<String>,
+    ) {
+        self.update_current_tab(|tab| {
+            tab.navigate_to_with_anchor(file, anchor);
+        });
+    }
+
+    /// Take the pending anchor of the current tab, if any
+    pub fn take_pending_anchor(&mut self) -> Option<String> {
+        let has_anchor = self
+            .current_tab()
+            .is_some_and(|tab| tab.pending_anchor.is_some());
+        if !has_anchor {
+            // Avoid writing to the tabs signal (and re-rendering) when there is nothing to take
+            return None;
+        }
+        let mut anchor = None;
         self.update_current_tab(|tab| {
-            tab.navigate_to(file);
+            anchor = tab.pending_anchor.take();
+        });
+        anchor
+    }
+
+    /// Take the pending scroll offset of the current tab, if any
+    pub fn take_pending_scroll(&mut self) -> Option<f64> {
+        let has_scroll = self
+            .current_tab()
+            .is_some_and(|tab| tab.pending_scroll.is_some());
+        if !has_scroll {
+            return None;
+        }
+        let mut scroll = None;
+        self.update_current_tab(|tab| {
+            scroll = tab.pending_scroll.take();
+        });
+        scroll
+    }
+
+    /// Take whether showing the current tab's document counts as a new visit
+    pub fn take_pending_visit(&mut self) -> bool {
+        let has_visit = self.current_tab().is_some_and(|tab| tab.pending_visit);
+        if has_visit {
+            self.update_current_tab(|tab| {
+                tab.pending_visit = false;
+            });
+        }
+        has_visit
+    }
+
+    /// Pause or resume reloading the document of the current tab when it changes
+    pub fn toggle_auto_reload(&mut self) {
+        self.update_current_tab(|tab| {
+            tab.auto_reload_paused = !tab.auto_reload_paused;
+        });
+    }
+
+    /// Remember the scroll offset of the current tab (skips the write when unchanged)
+    pub fn set_current_tab_scroll(&mut self, scroll_top: f64) {
+        let unchanged = self
+            .current_tab()
+            .is_some_and(|tab| (tab.scroll_top - scroll_top).abs() < 1.0);
+        if unchanged {
+            return;
+        }
+        self.update_current_tab(|tab| {
+            tab.scroll_top = scroll_top;
+        });
+    }
+
+    /// Set the document title of the current tab (skips the write when unchanged)
+    pub fn set_current_tab_title(&mut self, title: Option<String>) {
+        let unchanged = self.current_tab().is_some_and(|tab| tab.title == title);
+        if unchanged {
+            return;
+        }
+        self.update_current_tab(|tab| {
+            tab.title = title;
         });
     }
 
     /// Open preferences in a tab. Reuses existing preferences tab if found.
     pub fn open_preferences(&mut self) {
-        // Check if preferences tab already exists
+        self.open_page(TabContent::Preferences);
+    }
+
+    /// Open the recently viewed history in a tab. Reuses existing history tab if found.
+    pub fn open_history(&mut self) {
+        self.open_page(TabContent::History);
+    }
+
+    /// Open a page that exists once per window (switches to it if already open)
+    fn open_page(&mut self, content: TabContent) {
+        // Check if the page is already open in a tab
         let tabs = self.tabs.read();
-        if let Some(index) = tabs
-            .iter()
-            .position(|tab| matches!(tab.content, TabContent::Preferences))
-        {
+        if let Some(index) = tabs.iter().position(|tab| tab.content == content) {
             drop(tabs);
             self.switch_to_tab(index);
             return;
         }
         drop(tabs);
 
-        // Check if current tab is empty (None, Inline, or FileError) - reuse it
+        // Check if current tab is empty (None, welcome screen, or FileError) - reuse it
         if self.is_current_tab_no_file() {
             self.update_current_tab(|tab| {
-                tab.content = TabContent::Preferences;
+                tab.content = content;
             });
         } else {
-            // Create new tab with preferences
-            let mut tabs = self.tabs.write();
-            tabs.push(Tab {
-                content: TabContent::Preferences,
-                history: HistoryManager::new(),
-            });
-            let new_index = tabs.len() - 1;
-            drop(tabs);
-            self.active_tab.set(new_index);
+            // Create new tab with the page
+            self.add_tab(
+                Tab {
+                    content,
+                    ..Default::default()
+                },
+                true,
+            );
         }
     }
 }
@@ -263,6 +691,8 @@ mod tests {
         assert_eq!(tab.content, TabContent::File(path.clone()));
         assert_eq!(tab.file(), Some(path.as_path()));
         assert!(!tab.is_no_file());
+        assert_eq!(tab.pending_anchor, None);
+        assert_eq!(tab.title, None);
     }
 
     #[test]
@@ -270,7 +700,10 @@ mod tests {
         let content = "# Welcome".to_string();
         let tab = Tab::with_inline_content(content.clone());
 
-        assert_eq!(tab.content, TabContent::Inline(content));
+        assert_eq!(
+            tab.content,
+            TabContent::Inline(InlineDocument::new(content, InlineSource::Welcome, None))
+        );
         assert!(tab.is_no_file());
         assert_eq!(tab.file(), None);
     }
@@ -297,6 +730,15 @@ mod tests {
             ..Default::default()
         };
         assert!(!tab.is_no_file());
+
+        let tab = Tab {
+            content: TabContent::History,
+            ..Default::default()
+        };
+        assert!(!tab.is_no_file());
+
+        let document = InlineDocument::new("# Piped", InlineSource::Stdin, None);
+        assert!(!Tab::with_inline_document(document).is_no_file());
     }
 
     #[test]
@@ -310,6 +752,128 @@ mod tests {
         assert_eq!(tab.file(), Some(path.as_path()));
     }
 
+    #[test]
+    fn test_tab_navigate_from_inline_document() {
+        // Saving an inline document turns its tab into a file tab
+        let document = InlineDocument::new("# Notes", InlineSource::Clipboard, None);
+        let mut tab = Tab::with_inline_document(document);
+        tab.title = Some("Notes".to_string());
+        let path = PathBuf::from("/test/notes.md");
+
+        tab.navigate_to(path.clone());
+
+        assert_eq!(tab.content, TabContent::File(path.clone()));
+        assert_eq!(tab.title, None);
+        assert_eq!(tab.history.current(), Some(path.as_path()));
+    }
+
+    #[test]
+    fn test_tab_restore_scroll() {
+        let mut tab = Tab::new("/test/file.md");
+        tab.restore_scroll();
+        assert_eq!(tab.pending_scroll, None);
+
+        tab.scroll_top = 512.0;
+        tab.restore_scroll();
+        assert_eq!(tab.pending_scroll, Some(512.0));
+    }
+
+    #[test]
+    fn test_tab_go_back_restores_scroll() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.scroll_top = 640.0;
+        tab.navigate_to_with_anchor("/test/b.md", Some("usage".to_string()));
+        assert_eq!(tab.scroll_top, 0.0);
+        assert_eq!(tab.pending_anchor.as_deref(), Some("usage"));
+        tab.scroll_top = 90.0;
+
+        assert!(tab.go_back());
+        assert_eq!(tab.content, TabContent::File(PathBuf::from("/test/a.md")));
+        assert_eq!(tab.pending_scroll, Some(640.0));
+        assert_eq!(tab.pending_anchor, None);
+
+        assert!(tab.go_forward());
+        assert_eq!(tab.content, TabContent::File(PathBuf::from("/test/b.md")));
+        assert_eq!(tab.pending_scroll, Some(90.0));
+        assert!(!tab.go_forward());
+    }
+
+    #[test]
+    fn test_tab_go_back_falls_back_to_anchor() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.navigate_to_with_anchor("/test/b.md", Some("usage".to_string()));
+        tab.navigate_to("/test/c.md");
+
+        assert!(tab.go_back());
+        // Never scrolled: the document opens at the anchor it was linked with
+        assert_eq!(tab.pending_scroll, None);
+        assert_eq!(tab.pending_anchor.as_deref(), Some("usage"));
+    }
+
+    #[test]
+    fn test_tab_go_to_history() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.navigate_to("/test/b.md");
+        tab.navigate_to("/test/c.md");
+        tab.scroll_top = 300.0;
+        tab.pending_visit = false;
+
+        assert!(tab.go_to_history(0));
+        assert_eq!(tab.content, TabContent::File(PathBuf::from("/test/a.md")));
+        assert!(tab.pending_visit);
+        assert!(tab.history.can_go_forward());
+
+        assert!(tab.go_to_history(2));
+        assert_eq!(tab.pending_scroll, Some(300.0));
+        // The current entry and unknown entries are no-ops
+        assert!(!tab.go_to_history(2));
+        assert!(!tab.go_to_history(5));
+    }
+
+    #[test]
+    fn test_tab_retarget() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.navigate_to("/test/b.md");
+        tab.scroll_top = 120.0;
+        tab.pending_visit = false;
+
+        assert!(!tab.retarget(Path::new("/test/a.md"), Path::new("/test/c.md")));
+        assert!(tab.retarget(Path::new("/test/b.md"), Path::new("/test/c.md")));
+        assert_eq!(tab.content, TabContent::File(PathBuf::from("/test/c.md")));
+        assert_eq!(tab.history.current(), Some(Path::new("/test/c.md")));
+        assert_eq!(tab.pending_scroll, Some(120.0));
+        // Following a move is not a new visit
+        assert!(!tab.pending_visit);
+
+        tab.file_deleted = true;
+        tab.navigate_to("/test/d.md");
+        assert!(!tab.file_deleted);
+    }
+
+    #[test]
+    fn test_tab_follow() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.follow(Path::new("/test/b.md"));
+        assert_eq!(tab.content, TabContent::File(PathBuf::from("/test/b.md")));
+        assert_eq!(tab.history.current(), Some(Path::new("/test/b.md")));
+
+        // Changes to the file already shown leave the history alone
+        tab.follow(Path::new("/test/b.md"));
+        assert!(tab.go_back());
+        assert_eq!(tab.history.current(), Some(Path::new("/test/a.md")));
+        assert!(!tab.go_back());
+    }
+
+    #[test]
+    fn test_inline_document_base_path() {
+        let document = InlineDocument::new("", InlineSource::Stdin, Some(PathBuf::from("/work")));
+        assert_eq!(document.base_path().parent(), Some(Path::new("/work")));
+        assert_eq!(
+            InlineDocument::default().base_path().parent(),
+            Some(Path::new("."))
+        );
+    }
+
     #[test]
     fn test_tab_file() {
         let path = PathBuf::from("/test/file.md");
@@ -323,7 +887,7 @@ mod tests {
         tab.content = TabContent::None;
         assert_eq!(tab.file(), None);
 
-        tab.content = TabContent::Inline("test".to_string());
+        tab.content = TabContent::Inline(InlineDocument::default());
         assert_eq!(tab.file(), None);
 
         tab.content = TabContent::Preferences;
@@ -6,6 +6,7 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::LazyLock;
 
+use crate::config::SidebarPanel;
 use crate::state::AppState;
 use crate::theme::Theme;
 
@@ -53,6 +54,7 @@ pub struct PersistedState {
     pub sidebar_open: bool,
     pub sidebar_width: f64,
     pub sidebar_show_all_files: bool,
+    pub sidebar_panel: SidebarPanel,
     pub window_position: Position,
     pub window_size: Size,
 }
@@ -65,6 +67,7 @@ impl Default for PersistedState {
             sidebar_open: false,
             sidebar_width: 280.0,
             sidebar_show_all_files: false,
+            sidebar_panel: SidebarPanel::default(),
             window_position: Position::default(),
             window_size: Size::default(),
         }
@@ -80,6 +83,7 @@ impl From<&AppState> for PersistedState {
             sidebar_open: sidebar.open,
             sidebar_width: sidebar.width,
             sidebar_show_all_files: sidebar.show_all_files,
+            sidebar_panel: sidebar.panel,
             window_position: (*state.position.read()).into(),
             window_size: (*state.size.read()).into(),
         }
@@ -132,6 +136,7 @@ impl PersistedState {
             sidebar_open = self.sidebar_open,
             sidebar_width = self.sidebar_width,
             sidebar_show_all_files = self.sidebar_show_all_files,
+            sidebar_panel = ?self.sidebar_panel,
             "Saving persisted state"
         );
 
@@ -0,0 +1,328 @@
+use dioxus::core::spawn_forever;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use super::persistence::PersistedState;
+use crate::config::CONFIG;
+use crate::events::RECENTLY_VIEWED_CHANGED;
+
+/// Maximum number of documents remembered in the recently viewed history
+const MAX_RECENTLY_VIEWED: usize = 500;
+
+/// Delay before history.json is written after a change
+const HISTORY_SAVE_DEBOUNCE_MS: u64 = 1000;
+
+/// A document in the recently viewed history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentEntry {
+    pub path: PathBuf,
+    /// Title declared in the front matter of the document
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Time of the last visit in seconds since the UNIX epoch
+    pub last_visited: u64,
+    pub visit_count: u32,
+}
+
+impl RecentEntry {
+    /// Get the title of the document, or its file name without one
+    pub fn display_title(&self) -> String {
+        self.title.clone().unwrap_or_else(|| {
+            self.path
+                .file_name()
+                .unwrap_or(self.path.as_os_str())
+                .to_string_lossy()
+                .to_string()
+        })
+    }
+
+    /// Check whether the title or path contains the query (case-insensitive)
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        query.is_empty()
+            || self.display_title().to_lowercase().contains(&query)
+            || self.path.to_string_lossy().to_lowercase().contains(&query)
+    }
+
+    /// Describe how long ago the document was last viewed (e.g. "5 minutes ago")
+    pub fn last_visited_label(&self, now: u64) -> String {
+        let elapsed = now.saturating_sub(self.last_visited);
+        let (count, unit) = match elapsed {
+            0..60 => return "Just now".to_string(),
+            60..3600 => (elapsed / 60, "minute"),
+            3600..86400 => (elapsed / 3600, "hour"),
+            86400..2_592_000 => (elapsed / 86400, "day"),
+            2_592_000..31_536_000 => (elapsed / 2_592_000, "month"),
+            _ => (elapsed / 31_536_000, "year"),
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        format!("{count} {unit}{plural} ago")
+    }
+}
+
+/// Documents viewed in any window, most recently viewed first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RecentlyViewed {
+    entries: Vec<RecentEntry>,
+}
+
+impl RecentlyViewed {
+    /// Get the history file path (history.json next to state.json)
+    pub fn path() -> PathBuf {
+        PersistedState::path().with_file_name("history.json")
+    }
+
+    /// Load the history from file
+    pub fn load() -> Self {
+        let path = Self::path();
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!(?e, path = %path.display(), "Ignoring unreadable history file");
+            Self::default()
+        })
+    }
+
+    /// Save the history to file
+    pub fn save(&self) {
+        let path = Self::path();
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                tracing::error!(?e, "Failed to create history directory");
+                return;
+            }
+        }
+
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                if let Err(e) = fs::write(&path, content) {
+                    tracing::error!(?e, "Failed to save history");
+                }
+            }
+            Err(e) => {
+                tracing::error!(?e, "Failed to serialize history");
+            }
+        }
+    }
+
+    /// Get all entries, most recently viewed first
+    pub fn entries(&self) -> &[RecentEntry] {
+        &self.entries
+    }
+
+    /// Record a visit to a document, moving it to the top
+    pub fn record_visit(&mut self, path: &Path, title: Option<String>, now: u64) {
+        let mut entry = match self.entries.iter().position(|entry| entry.path == path) {
+            Some(index) => self.entries.remove(index),
+            None => RecentEntry {
+                path: path.to_path_buf(),
+                title: None,
+                last_visited: now,
+                visit_count: 0,
+            },
+        };
+        entry.title = title;
+        entry.last_visited = now;
+        entry.visit_count += 1;
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_RECENTLY_VIEWED);
+    }
+
+    /// Update the title of a remembered document (returns false if nothing changed)
+    pub fn update_title(&mut self, path: &Path, title: Option<String>) -> bool {
+        match self.entries.iter_mut().find(|entry| entry.path == path) {
+            Some(entry) if entry.title != title => {
+                entry.title = title;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Forget a document (returns false if it was not remembered)
+    pub fn remove(&mut self, path: &Path) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.path != path);
+        self.entries.len() != len
+    }
+
+    /// Forget every document
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Recently viewed documents of all windows (loaded from history.json)
+pub static RECENTLY_VIEWED: LazyLock<RwLock<RecentlyViewed>> =
+    LazyLock::new(|| RwLock::new(RecentlyViewed::load()));
+
+/// Record that a document was shown
+///
+/// A new visit moves the document to the top and counts it; otherwise (e.g. a
+/// reload) only its title is refreshed. Excluded documents are never recorded.
+pub fn record_recent_visit(path: &Path, title: Option<String>, is_new_visit: bool) {
+    if !CONFIG.read().history.records(path) {
+        return;
+    }
+    let changed = {
+        let mut recent = RECENTLY_VIEWED.write();
+        if is_new_visit {
+            recent.record_visit(path, title, unix_now());
+            true
+        } else {
+            recent.update_title(path, title)
+        }
+    };
+    if changed {
+        save_recently_viewed();
+    }
+}
+
+/// Forget a document of the recently viewed history
+pub fn remove_recent_visit(path: &Path) {
+    if RECENTLY_VIEWED.write().remove(path) {
+        save_recently_viewed();
+    }
+}
+
+/// Forget every document of the recently viewed history
+pub fn clear_recently_viewed() {
+    RECENTLY_VIEWED.write().clear();
+    save_recently_viewed();
+}
+
+/// Changes of the recently viewed history, so that only the last of a burst is saved
+static HISTORY_SAVE_TOKEN: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the recently viewed history has changes not written to file yet
+static HISTORY_SAVE_PENDING: AtomicBool = AtomicBool::new(false);
+
+fn save_recently_viewed() {
+    HISTORY_SAVE_PENDING.store(true, Ordering::SeqCst);
+    let token = HISTORY_SAVE_TOKEN.fetch_add(1, Ordering::SeqCst) + 1;
+    spawn_forever(async move {
+        tokio::time::sleep(Duration::from_millis(HISTORY_SAVE_DEBOUNCE_MS)).await;
+        if HISTORY_SAVE_TOKEN.load(Ordering::SeqCst) == token {
+            flush_recently_viewed();
+        }
+    });
+    // Nobody listening (e.g. while quitting) is fine
+    let _ = RECENTLY_VIEWED_CHANGED.send(());
+}
+
+/// Write pending changes of the recently viewed history right away (e.g. as the application quits)
+pub fn flush_recently_viewed() {
+    if HISTORY_SAVE_PENDING.swap(false, Ordering::SeqCst) {
+        RECENTLY_VIEWED.read().save();
+    }
+}
+
+/// Get the current time in seconds since the UNIX epoch
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_visit_moves_to_top() {
+        let mut recent = RecentlyViewed::default();
+        recent.record_visit(Path::new("/test/a.md"), None, 100);
+        recent.record_visit(Path::new("/test/b.md"), None, 200);
+        recent.record_visit(Path::new("/test/a.md"), Some("A".to_string()), 300);
+
+        let entries = recent.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].path, PathBuf::from("/test/a.md"));
+        assert_eq!(entries[0].title.as_deref(), Some("A"));
+        assert_eq!(entries[0].last_visited, 300);
+        assert_eq!(entries[0].visit_count, 2);
+        assert_eq!(entries[1].visit_count, 1);
+    }
+
+    #[test]
+    fn test_record_visit_is_bounded() {
+        let mut recent = RecentlyViewed::default();
+        for index in 0..MAX_RECENTLY_VIEWED + 3 {
+            recent.record_visit(Path::new(&format!("/test/{index}.md")), None, index as u64);
+        }
+        assert_eq!(recent.entries().len(), MAX_RECENTLY_VIEWED);
+        // The least recently viewed documents were dropped
+        assert_eq!(
+            recent.entries().last().unwrap().path,
+            PathBuf::from("/test/3.md")
+        );
+    }
+
+    #[test]
+    fn test_update_title_and_remove() {
+        let mut recent = RecentlyViewed::default();
+        recent.record_visit(Path::new("/test/a.md"), None, 100);
+
+        assert!(recent.update_title(Path::new("/test/a.md"), Some("A".to_string())));
+        assert!(!recent.update_title(Path::new("/test/a.md"), Some("A".to_string())));
+        assert!(!recent.update_title(Path::new("/test/b.md"), Some("B".to_string())));
+        assert_eq!(recent.entries()[0].visit_count, 1);
+
+        assert!(recent.remove(Path::new("/test/a.md")));
+        assert!(!recent.remove(Path::new("/test/a.md")));
+        assert!(recent.entries().is_empty());
+    }
+
+    #[test]
+    fn test_entry_matches_title_and_path() {
+        let entry = RecentEntry {
+            path: PathBuf::from("/docs/Guide/install.md"),
+            title: Some("Installation".to_string()),
+            last_visited: 0,
+            visit_count: 1,
+        };
+        assert!(entry.matches(""));
+        assert!(entry.matches("installation"));
+        assert!(entry.matches("guide/"));
+        assert!(!entry.matches("usage"));
+    }
+
+    #[test]
+    fn test_entry_last_visited_label() {
+        let entry = RecentEntry {
+            path: PathBuf::from("/docs/a.md"),
+            title: None,
+            last_visited: 1_000_000,
+            visit_count: 1,
+        };
+        assert_eq!(entry.display_title(), "a.md");
+        assert_eq!(entry.last_visited_label(1_000_030), "Just now");
+        assert_eq!(entry.last_visited_label(1_000_060), "1 minute ago");
+        assert_eq!(entry.last_visited_label(1_007_200), "2 hours ago");
+        assert_eq!(
+            entry.last_visited_label(1_000_000 + 3 * 86400),
+            "3 days ago"
+        );
+    }
+
+    #[test]
+    fn test_recently_viewed_roundtrip() {
+        let mut recent = RecentlyViewed::default();
+        recent.record_visit(Path::new("/test/a.md"), Some("A".to_string()), 100);
+
+        let json = serde_json::to_string(&recent).unwrap();
+        let parsed: RecentlyViewed = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, recent);
+    }
+}
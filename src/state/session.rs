@@ -0,0 +1,314 @@
+use dioxus::core::spawn_forever;
+use dioxus::desktop::tao::window::WindowId;
+use dioxus::prelude::ReadableExt;
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use super::persistence::PersistedState;
+use super::{AppState, InlineSource, Tab, TabContent};
+use crate::config::CONFIG;
+use crate::history::HistoryManager;
+
+/// Delay before the session file is written after a change
+const SESSION_SAVE_DEBOUNCE_MS: u64 = 1000;
+
+/// A tab of a saved session
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionTab {
+    pub content: TabContent,
+    pub history: HistoryManager,
+    pub scroll_top: f64,
+}
+
+impl From<&Tab> for SessionTab {
+    fn from(tab: &Tab) -> Self {
+        Self {
+            content: tab.content.clone(),
+            history: tab.history.clone(),
+            scroll_top: tab.scroll_top,
+        }
+    }
+}
+
+impl SessionTab {
+    /// Turn into a tab, showing files that no longer exist as errors
+    pub fn into_tab(self) -> Tab {
+        let content = match self.content {
+            TabContent::File(path) if !path.exists() => {
+                TabContent::FileError(path, "File not found".to_string())
+            }
+            content => content,
+        };
+        Tab {
+            content,
+            history: self.history,
+            scroll_top: self.scroll_top,
+            ..Default::default()
+        }
+    }
+}
+
+/// A main window of a saved session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SessionWindow {
+    /// Geometry, theme, sidebar and root directory of the window
+    #[serde(flatten)]
+    pub layout: PersistedState,
+    pub expanded_dirs: Vec<PathBuf>,
+    pub tabs: Vec<SessionTab>,
+    pub active_tab: usize,
+}
+
+impl From<&AppState> for SessionWindow {
+    fn from(state: &AppState) -> Self {
+        let mut expanded_dirs: Vec<_> =
+            state.sidebar.read().expanded_dirs.iter().cloned().collect();
+        expanded_dirs.sort();
+        let (tabs, active_tab) = session_tabs(&state.tabs.read(), *state.active_tab.read());
+        Self {
+            layout: PersistedState::from(state),
+            expanded_dirs,
+            tabs,
+            active_tab,
+        }
+    }
+}
+
+/// Get the tabs of a window to restore, and the index of the active one among them
+///
+/// Piped and pasted documents exist nowhere else, so they are not saved (the
+/// tab after a skipped active tab becomes the active one).
+fn session_tabs(tabs: &[Tab], active_tab: usize) -> (Vec<SessionTab>, usize) {
+    let is_saved = |tab: &&Tab| {
+        !matches!(
+            &tab.content,
+            TabContent::Inline(document) if document.source != InlineSource::Welcome
+        )
+    };
+    let saved: Vec<SessionTab> = tabs.iter().filter(is_saved).map(SessionTab::from).collect();
+    let active_tab = tabs[..active_tab.min(tabs.len())]
+        .iter()
+        .filter(is_saved)
+        .count()
+        .min(saved.len().saturating_sub(1));
+    (saved, active_tab)
+}
+
+/// Every main window of the application, restored on startup when enabled
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Session {
+    pub windows: Vec<SessionWindow>,
+}
+
+impl Session {
+    /// Get the session file path (session.json next to state.json)
+    pub fn path() -> PathBuf {
+        PersistedState::path().with_file_name("session.json")
+    }
+
+    /// Load the saved session (None if there is no window to restore)
+    pub fn load() -> Option<Self> {
+        let path = Self::path();
+        let content = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<Self>(&content) {
+            Ok(session) if !session.windows.is_empty() => Some(session),
+            Ok(_) => None,
+            Err(e) => {
+                tracing::warn!(?e, path = %path.display(), "Ignoring unreadable session file");
+                None
+            }
+        }
+    }
+
+    /// Save the session to file
+    pub fn save(&self) {
+        let path = Self::path();
+
+        tracing::debug!(
+            path = %path.display(),
+            windows = self.windows.len(),
+            "Saving session"
+        );
+
+        if let Some(parent) = path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                tracing::error!(?e, "Failed to create session directory");
+                return;
+            }
+        }
+
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                if let Err(e) = fs::write(&path, content) {
+                    tracing::error!(?e, "Failed to save session");
+                }
+            }
+            Err(e) => {
+                tracing::error!(?e, "Failed to serialize session");
+            }
+        }
+    }
+}
+
+thread_local! {
+    /// Current state of every open main window (in the order they were opened)
+    static SESSION_WINDOWS: RefCell<Vec<(WindowId, SessionWindow)>> = const { RefCell::new(Vec::new()) };
+    static SESSION_SAVE_TOKEN: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Set once the application quits, so that windows closed afterwards stay in the session
+static SESSION_ENDED: AtomicBool = AtomicBool::new(false);
+
+/// Record the current state of a main window
+pub fn update_session_window(window_id: WindowId, window: SessionWindow) {
+    SESSION_WINDOWS.with(|windows| {
+        let mut windows = windows.borrow_mut();
+        match windows.iter_mut().find(|(id, _)| *id == window_id) {
+            Some((_, entry)) => *entry = window,
+            None => windows.push((window_id, window)),
+        }
+    });
+    schedule_session_save();
+}
+
+/// Forget a main window closed by the user
+pub fn remove_session_window(window_id: WindowId) {
+    if SESSION_ENDED.load(Ordering::SeqCst) {
+        return;
+    }
+    SESSION_WINDOWS.with(|windows| {
+        windows.borrow_mut().retain(|(id, _)| *id != window_id);
+    });
+    schedule_session_save();
+}
+
+/// Save the session right away as the application quits
+pub fn end_session() {
+    if SESSION_ENDED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    if CONFIG.read().session.restores_session() {
+        current_session().save();
+    }
+}
+
+fn current_session() -> Session {
+    SESSION_WINDOWS.with(|windows| Session {
+        windows: windows
+            .borrow()
+            .iter()
+            .map(|(_, window)| window.clone())
+            .collect(),
+    })
+}
+
+fn schedule_session_save() {
+    if SESSION_ENDED.load(Ordering::SeqCst) || !CONFIG.read().session.restores_session() {
+        return;
+    }
+    let token = SESSION_SAVE_TOKEN.with(|token| {
+        token.set(token.get().wrapping_add(1));
+        token.get()
+    });
+    spawn_forever(async move {
+        tokio::time::sleep(Duration::from_millis(SESSION_SAVE_DEBOUNCE_MS)).await;
+        let is_latest = SESSION_SAVE_TOKEN.with(|current| current.get() == token);
+        if is_latest && !SESSION_ENDED.load(Ordering::SeqCst) {
+            current_session().save();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::InlineDocument;
+
+    #[test]
+    fn test_session_roundtrip() {
+        let mut tab = Tab::new("/test/a.md");
+        tab.navigate_to("/test/b.md");
+        tab.scroll_top = 240.0;
+        let session = Session {
+            windows: vec![SessionWindow {
+                expanded_dirs: vec![PathBuf::from("/test/docs")],
+                tabs: vec![SessionTab::from(&tab), SessionTab::default()],
+                active_tab: 1,
+                ..Default::default()
+            }],
+        };
+
+        let json = serde_json::to_string(&session).unwrap();
+        let parsed: Session = serde_json::from_str(&json).unwrap();
+
+        let window = &parsed.windows[0];
+        assert_eq!(window.active_tab, 1);
+        assert_eq!(window.expanded_dirs, vec![PathBuf::from("/test/docs")]);
+        assert_eq!(window.tabs[0], SessionTab::from(&tab));
+        assert_eq!(window.tabs[1].content, TabContent::None);
+    }
+
+    #[test]
+    fn test_session_tabs_skip_inline_documents() {
+        let stdin = InlineDocument::new("# Piped", InlineSource::Stdin, None);
+        let clipboard = InlineDocument::new("# Pasted", InlineSource::Clipboard, None);
+        let tabs = vec![
+            Tab::with_inline_content("# Welcome"),
+            Tab::with_inline_document(stdin),
+            Tab::new("/test/a.md"),
+            Tab::with_inline_document(clipboard),
+        ];
+
+        let (saved, active_tab) = session_tabs(&tabs, 2);
+        assert_eq!(saved.len(), 2);
+        assert_eq!(saved[1], SessionTab::from(&tabs[2]));
+        assert_eq!(active_tab, 1);
+
+        // A skipped active tab hands over to the nearest saved tab
+        assert_eq!(session_tabs(&tabs, 1).1, 1);
+        assert_eq!(session_tabs(&tabs, 3).1, 1);
+        assert_eq!(session_tabs(&tabs[1..2], 0), (Vec::new(), 0));
+    }
+
+    #[test]
+    fn test_session_tab_missing_file_becomes_error() {
+        let path = PathBuf::from("/nonexistent/arto/session-test.md");
+        let session_tab = SessionTab::from(&Tab::new(path.clone()));
+
+        let tab = session_tab.into_tab();
+
+        assert!(matches!(tab.content, TabContent::FileError(ref p, _) if *p == path));
+        // The history survives so that the user can go back to other documents
+        assert_eq!(tab.history.current(), Some(path.as_path()));
+    }
+
+    #[test]
+    fn test_session_tab_keeps_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notes.md");
+        std::fs::write(&path, "# Notes").unwrap();
+        let mut tab = Tab::new(path.clone());
+        tab.scroll_top = 80.0;
+
+        let restored = SessionTab::from(&tab).into_tab();
+
+        assert_eq!(restored.content, TabContent::File(path));
+        assert_eq!(restored.scroll_top, 80.0);
+    }
+
+    #[test]
+    fn test_session_window_layout_is_flattened() {
+        let json = r#"{"directory":"/test","sidebarOpen":true,"tabs":[{"content":"preferences"}]}"#;
+        let window: SessionWindow = serde_json::from_str(json).unwrap();
+        assert_eq!(window.layout.directory, Some(PathBuf::from("/test")));
+        assert!(window.layout.sidebar_open);
+        assert_eq!(window.tabs[0].content, TabContent::Preferences);
+    }
+}
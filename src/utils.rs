@@ -1,3 +1,7 @@
+pub mod clipboard;
+pub mod dialog;
 pub mod file;
+pub mod glob;
+pub mod print;
 pub mod screen;
 pub mod window_title;
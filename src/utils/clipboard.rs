@@ -0,0 +1,11 @@
+use objc2_app_kit::{NSPasteboard, NSPasteboardTypeString};
+
+/// Read plain text from the general pasteboard
+///
+/// Returns `None` when the clipboard holds no text (e.g. only an image).
+pub fn read_text() -> Option<String> {
+    let pasteboard = NSPasteboard::generalPasteboard();
+    // SAFETY: `NSPasteboardTypeString` is an immutable constant provided by AppKit
+    let text = pasteboard.stringForType(unsafe { NSPasteboardTypeString })?;
+    Some(text.to_string())
+}
@@ -0,0 +1,12 @@
+use rfd::{AsyncMessageDialog, MessageButtons, MessageLevel};
+
+/// Tell the user that an action failed (async to prevent UI freeze)
+pub async fn show_error(title: impl Into<String>, description: impl Into<String>) {
+    AsyncMessageDialog::new()
+        .set_level(MessageLevel::Error)
+        .set_title(title)
+        .set_description(description)
+        .set_buttons(MessageButtons::Ok)
+        .show()
+        .await;
+}
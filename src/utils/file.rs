@@ -1,4 +1,17 @@
-use std::path::Path;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Extensions of image files shown in the image viewer
+const IMAGE_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "svg", "webp", "bmp", "ico", "avif",
+];
+
+/// File names (lowercase) shown for a directory, in order of preference (like GitHub)
+const DIRECTORY_INDEX_NAMES: &[&str] =
+    &["readme.md", "readme.markdown", "index.md", "index.markdown"];
+
+/// Number of leading bytes inspected to decide whether a file is text
+const TEXT_SNIFF_LENGTH: usize = 8192;
 
 /// Check if a file path has a markdown extension (.md or .markdown)
 pub fn is_markdown_file(path: impl AsRef<Path>) -> bool {
@@ -8,3 +21,262 @@ pub fn is_markdown_file(path: impl AsRef<Path>) -> bool {
         .map(|ext| ext == "md" || ext == "markdown")
         .unwrap_or(false)
 }
+
+/// Check if a file path has an image extension
+pub fn is_image_file(path: impl AsRef<Path>) -> bool {
+    extension_matches(path.as_ref(), IMAGE_EXTENSIONS)
+}
+
+/// Check if a file looks like UTF-8 text by inspecting its first bytes
+pub fn is_text_file(path: impl AsRef<Path>) -> bool {
+    let Ok(file) = std::fs::File::open(path.as_ref()) else {
+        return false;
+    };
+    let mut buffer = Vec::with_capacity(TEXT_SNIFF_LENGTH);
+    if file
+        .take(TEXT_SNIFF_LENGTH as u64)
+        .read_to_end(&mut buffer)
+        .is_err()
+    {
+        return false;
+    }
+    if buffer.contains(&0) {
+        return false;
+    }
+    match std::str::from_utf8(&buffer) {
+        Ok(_) => true,
+        // A multi-byte character cut off at the end of the buffer is still text
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+/// Find the README (or index) file of a directory, matching names case-insensitively
+pub fn find_directory_index(dir: impl AsRef<Path>) -> Option<PathBuf> {
+    let files: Vec<PathBuf> = std::fs::read_dir(dir.as_ref())
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect();
+
+    DIRECTORY_INDEX_NAMES.iter().find_map(|name| {
+        files
+            .iter()
+            .find(|path| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.eq_ignore_ascii_case(name))
+            })
+            .cloned()
+    })
+}
+
+/// Find the root of the Git repository containing a path
+///
+/// `.git` may be a directory or a file (worktrees and submodules).
+pub fn find_git_root(path: impl AsRef<Path>) -> Option<PathBuf> {
+    path.as_ref()
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// How a local file referenced by a link is opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkTarget {
+    /// Directory (shown in the file explorer)
+    Directory,
+    /// Markdown, text or image file shown in a tab
+    Viewable,
+    /// File handed to the system opener
+    External,
+    /// File that cannot be opened
+    Unsupported,
+}
+
+/// Decide how a linked file is opened
+///
+/// Extensions listed in `external_extensions` always go to the system opener,
+/// even if the file could be shown in a tab.
+pub fn classify_link_target(path: impl AsRef<Path>, external_extensions: &[String]) -> LinkTarget {
+    let path = path.as_ref();
+    if path.is_dir() {
+        return LinkTarget::Directory;
+    }
+    let is_external = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            external_extensions
+                .iter()
+                .any(|allowed| allowed.trim_start_matches('.').eq_ignore_ascii_case(ext))
+        });
+    if is_external {
+        LinkTarget::External
+    } else if is_markdown_file(path) || is_image_file(path) || is_text_file(path) {
+        LinkTarget::Viewable
+    } else {
+        LinkTarget::Unsupported
+    }
+}
+
+/// Get the file that was modified last among paths (directories and missing paths are skipped)
+pub fn most_recently_modified(paths: &[PathBuf]) -> Option<PathBuf> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let metadata = std::fs::metadata(path).ok()?;
+            let modified = metadata.modified().ok()?;
+            metadata.is_file().then_some((modified, path))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path.clone())
+}
+
+fn extension_matches(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_image_file() {
+        assert!(is_image_file("diagram.png"));
+        assert!(is_image_file("photo.JPG"));
+        assert!(!is_image_file("schema.json"));
+        assert!(!is_image_file("LICENSE"));
+    }
+
+    #[test]
+    fn test_is_text_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let text = temp_dir.path().join("LICENSE");
+        std::fs::write(&text, "MIT License\n").unwrap();
+        let binary = temp_dir.path().join("blob.bin");
+        std::fs::write(&binary, [0x89, b'P', b'N', b'G', 0x00, 0x01]).unwrap();
+
+        assert!(is_text_file(&text));
+        assert!(!is_text_file(&binary));
+        assert!(!is_text_file(temp_dir.path().join("missing")));
+    }
+
+    #[test]
+    fn test_is_text_file_with_truncated_multibyte_char() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("japanese.txt");
+        // "あ" is three bytes; place one across the sniff boundary
+        let mut content = "a".repeat(TEXT_SNIFF_LENGTH - 1);
+        content.push('あ');
+        std::fs::write(&path, content).unwrap();
+
+        assert!(is_text_file(&path));
+    }
+
+    #[test]
+    fn test_find_directory_index() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        assert_eq!(find_directory_index(dir), None);
+
+        std::fs::write(dir.join("index.md"), "# Index").unwrap();
+        assert_eq!(find_directory_index(dir), Some(dir.join("index.md")));
+
+        // README takes precedence over index, case-insensitively
+        std::fs::write(dir.join("ReadMe.markdown"), "# Readme").unwrap();
+        assert_eq!(find_directory_index(dir), Some(dir.join("ReadMe.markdown")));
+
+        // Directories are never index files
+        std::fs::create_dir(dir.join("README.md")).unwrap();
+        assert_eq!(find_directory_index(dir), Some(dir.join("ReadMe.markdown")));
+    }
+
+    #[test]
+    fn test_find_git_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = temp_dir.path().join("repo");
+        std::fs::create_dir_all(repo.join(".git")).unwrap();
+        std::fs::create_dir_all(repo.join("docs/guide")).unwrap();
+        std::fs::create_dir_all(repo.join("vendor/lib")).unwrap();
+        // Submodules use a `.git` file
+        std::fs::write(
+            repo.join("vendor/lib/.git"),
+            "gitdir: ../../.git/modules/lib",
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_git_root(repo.join("docs/guide/setup.md")),
+            Some(repo.clone())
+        );
+        assert_eq!(
+            find_git_root(repo.join("vendor/lib/README.md")),
+            Some(repo.join("vendor/lib"))
+        );
+    }
+
+    #[test]
+    fn test_classify_link_target() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        std::fs::create_dir(dir.join("docs")).unwrap();
+        std::fs::write(dir.join("README.md"), "# Readme").unwrap();
+        std::fs::write(dir.join("schema.json"), "{}").unwrap();
+        std::fs::write(dir.join("diagram.png"), [0x89, b'P', b'N', b'G', 0x00]).unwrap();
+        std::fs::write(dir.join("report.pdf"), [b'%', b'P', b'D', b'F', 0x00]).unwrap();
+        std::fs::write(dir.join("archive.bin"), [0x00, 0x01]).unwrap();
+        let external = vec!["pdf".to_string(), ".json".to_string()];
+
+        assert_eq!(
+            classify_link_target(dir.join("docs"), &external),
+            LinkTarget::Directory
+        );
+        assert_eq!(
+            classify_link_target(dir.join("README.md"), &external),
+            LinkTarget::Viewable
+        );
+        assert_eq!(
+            classify_link_target(dir.join("diagram.png"), &external),
+            LinkTarget::Viewable
+        );
+        assert_eq!(
+            classify_link_target(dir.join("report.pdf"), &external),
+            LinkTarget::External
+        );
+        // The allowlist takes precedence over the text viewer
+        assert_eq!(
+            classify_link_target(dir.join("schema.json"), &external),
+            LinkTarget::External
+        );
+        assert_eq!(
+            classify_link_target(dir.join("schema.json"), &[]),
+            LinkTarget::Viewable
+        );
+        assert_eq!(
+            classify_link_target(dir.join("archive.bin"), &external),
+            LinkTarget::Unsupported
+        );
+    }
+
+    #[test]
+    fn test_most_recently_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let now = std::time::SystemTime::now();
+        for (name, age) in [("old.md", 60), ("new.md", 10), ("older.md", 120)] {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(age))
+                .unwrap();
+        }
+        std::fs::create_dir(dir.join("dir.md")).unwrap();
+
+        let paths =
+            ["old.md", "new.md", "older.md", "dir.md", "missing.md"].map(|name| dir.join(name));
+        assert_eq!(most_recently_modified(&paths), Some(dir.join("new.md")));
+        assert_eq!(most_recently_modified(&paths[3..]), None);
+    }
+}
@@ -0,0 +1,178 @@
+use std::path::Path;
+
+/// Check whether a path matches any of the glob patterns
+pub fn matches_any(patterns: &[String], path: &Path) -> bool {
+    patterns.iter().any(|pattern| path_matches(pattern, path))
+}
+
+/// Check whether a path matches a glob pattern
+///
+/// - `*` matches anything but `/`, `**` matches across directories and `?` matches one character
+/// - A pattern without `/` matches any component of the path (e.g. `*.draft.md`, `node_modules`)
+/// - A pattern with `/` matches the whole path or one of its parent directories
+///   (a leading `~/` is the home directory)
+pub fn path_matches(pattern: &str, path: &Path) -> bool {
+    let pattern = pattern.trim();
+    if pattern.is_empty() {
+        return false;
+    }
+
+    if !pattern.contains('/') {
+        return path
+            .components()
+            .any(|component| glob_match(pattern, &component.as_os_str().to_string_lossy()));
+    }
+
+    let pattern = match (pattern.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => format!("{}/{}", home.display(), rest),
+        _ => pattern.to_string(),
+    };
+    let pattern = pattern.trim_end_matches('/');
+    path.ancestors()
+        .any(|ancestor| glob_match(pattern, &ancestor.to_string_lossy()))
+}
+
+/// A part of a glob pattern
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token {
+    Char(char),
+    /// `?`
+    AnyChar,
+    /// `*`
+    Star,
+    /// `**`
+    DoubleStar,
+}
+
+fn tokenize(pattern: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        tokens.push(match c {
+            '*' if chars.next_if_eq(&'*').is_some() => Token::DoubleStar,
+            '*' => Token::Star,
+            '?' => Token::AnyChar,
+            c => Token::Char(c),
+        });
+    }
+    tokens
+}
+
+/// Match a text against a glob pattern
+///
+/// Every position of the pattern the text may have reached is tracked at
+/// once, so the time is linear in the text whatever the wildcards.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let tokens = tokenize(pattern);
+    let mut states = vec![false; tokens.len() + 1];
+    states[0] = true;
+    skip_empty_matches(&tokens, &mut states);
+
+    for c in text.chars() {
+        let mut next = vec![false; tokens.len() + 1];
+        for (index, token) in tokens.iter().enumerate() {
+            if !states[index] {
+                continue;
+            }
+            match token {
+                Token::Char(expected) if *expected == c => next[index + 1] = true,
+                Token::AnyChar if c != '/' => next[index + 1] = true,
+                // A single star stays within a directory
+                Token::Star if c != '/' => next[index] = true,
+                Token::DoubleStar => next[index] = true,
+                _ => {}
+            }
+        }
+        skip_empty_matches(&tokens, &mut next);
+        if !next.contains(&true) {
+            return false;
+        }
+        states = next;
+    }
+    states[tokens.len()]
+}
+
+/// Add the positions reached by wildcards matching nothing
+fn skip_empty_matches(tokens: &[Token], states: &mut [bool]) {
+    for index in 0..tokens.len() {
+        if !states[index] {
+            continue;
+        }
+        if matches!(tokens[index], Token::Star | Token::DoubleStar) {
+            states[index + 1] = true;
+        }
+        // `**/` also matches no directory at all
+        if tokens[index] == Token::DoubleStar && tokens.get(index + 1) == Some(&Token::Char('/')) {
+            states[index + 2] = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match_wildcards() {
+        assert!(glob_match("*.md", "notes.md"));
+        assert!(!glob_match("*.md", "notes.txt"));
+        assert!(glob_match("note?.md", "note1.md"));
+        assert!(!glob_match("note?.md", "note12.md"));
+        // A single star stays within a directory
+        assert!(!glob_match("/docs/*.md", "/docs/drafts/a.md"));
+        assert!(glob_match("/docs/**.md", "/docs/drafts/a.md"));
+        assert!(glob_match("/docs/**/a.md", "/docs/a.md"));
+        assert!(glob_match("/docs/**/a.md", "/docs/x/y/a.md"));
+        assert!(glob_match("**", "/any/path"));
+        assert!(!glob_match("*", "a/b"));
+    }
+
+    #[test]
+    fn test_glob_match_many_wildcards() {
+        // Would take exponential time with backtracking
+        let text = "a".repeat(200);
+        assert!(!glob_match(&format!("{}b", "*a".repeat(30)), &text));
+        assert!(!glob_match(&format!("{}b", "**a".repeat(30)), &text));
+        assert!(glob_match(&"*a".repeat(30), &text));
+    }
+
+    #[test]
+    fn test_path_matches_component_pattern() {
+        let path = Path::new("/project/node_modules/pkg/README.md");
+        assert!(path_matches("node_modules", path));
+        assert!(path_matches("*.md", path));
+        assert!(!path_matches("*.txt", path));
+        assert!(!path_matches("", path));
+    }
+
+    #[test]
+    fn test_path_matches_path_pattern() {
+        let path = Path::new("/private/tmp/scratch/notes.md");
+        // Patterns matching a parent directory match everything inside it
+        assert!(path_matches("/private/tmp", path));
+        assert!(path_matches("/private/tmp/", path));
+        assert!(path_matches("/private/*/scratch", path));
+        assert!(path_matches("**/scratch/*.md", path));
+        assert!(!path_matches("/tmp", path));
+    }
+
+    #[test]
+    fn test_path_matches_home_pattern() {
+        let Some(home) = dirs::home_dir() else {
+            return;
+        };
+        let path = home.join("Private/notes.md");
+        assert!(path_matches("~/Private", &path));
+        assert!(path_matches("~/Private/", &path));
+        assert!(!path_matches("~/Public/", &path));
+    }
+
+    #[test]
+    fn test_matches_any() {
+        let patterns = vec!["*.secret.md".to_string(), "/private/**".to_string()];
+        assert!(matches_any(&patterns, Path::new("/a/b.secret.md")));
+        assert!(matches_any(&patterns, Path::new("/private/a.md")));
+        assert!(!matches_any(&patterns, Path::new("/public/a.md")));
+        assert!(!matches_any(&[], Path::new("/public/a.md")));
+    }
+}
@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use dioxus_desktop::wry::{WebView, WebViewExtMacOS};
+use objc2::rc::Retained;
+use objc2::runtime::{Bool, NSObject};
+use objc2::{define_class, msg_send, sel, DefinedClass, MainThreadMarker, MainThreadOnly};
+use objc2_app_kit::{
+    NSPrintInfo, NSPrintJobSavingURL, NSPrintOperation, NSPrintSaveJob, NSPrintingPaginationMode,
+};
+use objc2_foundation::{NSCopying, NSSize, NSURL};
+use std::cell::Cell;
+use std::ffi::c_void;
+use std::path::Path;
+use tokio::sync::oneshot;
+
+use crate::config::PrintConfig;
+
+struct PrintDelegateIvars {
+    finished: Cell<Option<oneshot::Sender<bool>>>,
+}
+
+define_class!(
+    /// Receives the end of a print operation run as a sheet
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "ArtoPrintOperationDelegate"]
+    #[ivars = PrintDelegateIvars]
+    struct PrintDelegate;
+
+    impl PrintDelegate {
+        #[unsafe(method(printOperationDidRun:success:contextInfo:))]
+        fn print_operation_did_run(
+            &self,
+            _operation: &NSPrintOperation,
+            success: Bool,
+            _context_info: *mut c_void,
+        ) {
+            if let Some(finished) = self.ivars().finished.take() {
+                let _ = finished.send(success.as_bool());
+            }
+        }
+    }
+);
+
+impl PrintDelegate {
+    fn new(mtm: MainThreadMarker, finished: oneshot::Sender<bool>) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(PrintDelegateIvars {
+            finished: Cell::new(Some(finished)),
+        });
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+/// Print the content of a webview with the paper size and margins of the configuration
+///
+/// The print panel is shown as a sheet of the window, unless `pdf_file` is
+/// given, in which case the pages are saved there without a panel. Resolves
+/// once the operation is over, with `false` when it was cancelled or failed.
+pub async fn print_webview(
+    webview: &WebView,
+    config: &PrintConfig,
+    pdf_file: Option<&Path>,
+) -> Result<bool> {
+    let mtm = MainThreadMarker::new().context("printing must run on the main thread")?;
+    let webview = webview.webview();
+    let window = webview.window().context("the webview is not in a window")?;
+
+    let print_info = NSPrintInfo::sharedPrintInfo().copy();
+    let (width, height) = config.paper_size.size_in_points();
+    print_info.setPaperSize(NSSize::new(width, height));
+    let margin = config.margin_in_points();
+    print_info.setTopMargin(margin);
+    print_info.setBottomMargin(margin);
+    print_info.setLeftMargin(margin);
+    print_info.setRightMargin(margin);
+    print_info.setHorizontalPagination(NSPrintingPaginationMode::Fit);
+    print_info.setVerticallyCentered(false);
+    if let Some(file) = pdf_file {
+        let url = NSURL::from_file_path(file).context("invalid PDF file path")?;
+        // SAFETY: the job disposition and the saving URL key are constants provided by AppKit
+        unsafe {
+            print_info.setJobDisposition(NSPrintSaveJob);
+            print_info.dictionary().insert(NSPrintJobSavingURL, &url);
+        }
+    }
+
+    // SAFETY: the print info is a valid copy owned by this function
+    let operation = unsafe { webview.printOperationWithPrintInfo(&print_info) };
+    operation.setShowsPrintPanel(pdf_file.is_none());
+    operation.setShowsProgressPanel(true);
+    // WebKit only lays out the pages of a print view with a frame (they are blank otherwise)
+    if let Some(view) = operation.view() {
+        view.setFrame(webview.bounds());
+    }
+
+    let (finished, result) = oneshot::channel();
+    let delegate = PrintDelegate::new(mtm, finished);
+    // SAFETY: the selector matches the method of the delegate, which outlives the
+    // operation as it is kept until the result is received
+    unsafe {
+        operation.runOperationModalForWindow_delegate_didRunSelector_contextInfo(
+            &window,
+            Some(&delegate),
+            Some(sel!(printOperationDidRun:success:contextInfo:)),
+            std::ptr::null_mut(),
+        );
+    }
+    let success = result.await.unwrap_or(false);
+    drop(delegate);
+    Ok(success)
+}
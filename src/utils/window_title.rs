@@ -1,4 +1,4 @@
-use crate::state::TabContent;
+use crate::state::{Tab, TabContent};
 use std::path::Path;
 
 /// Extract filename from path, returning "Unknown" if unavailable
@@ -9,11 +9,17 @@ fn extract_filename(path: &Path) -> &str {
 }
 
 /// Generate window title based on active tab content
-pub fn generate_window_title(tab_content: &TabContent) -> String {
-    match tab_content {
+///
+/// A title declared in the document's front matter takes precedence over the filename.
+pub fn generate_window_title(tab: &Tab) -> String {
+    if let (TabContent::File(_) | TabContent::Inline(_), Some(title)) = (&tab.content, &tab.title) {
+        return format!("Arto - {}", title);
+    }
+    match &tab.content {
         TabContent::File(path) => format!("Arto - {}", extract_filename(path)),
-        TabContent::Inline(_) => "Arto - Welcome".to_string(),
+        TabContent::Inline(document) => format!("Arto - {}", document.source.label()),
         TabContent::Preferences => "Arto - Preferences".to_string(),
+        TabContent::History => "Arto - History".to_string(),
         TabContent::FileError(path, _) => format!("Arto - {} (Error)", extract_filename(path)),
         TabContent::None => "Arto".to_string(),
     }
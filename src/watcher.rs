@@ -1,149 +1,1001 @@
 use notify_debouncer_full::{
-    new_debouncer, notify::RecursiveMode, DebounceEventResult, Debouncer, RecommendedCache,
+    new_debouncer, new_debouncer_opt,
+    notify::{
+        event::{ModifyKind, RenameMode},
+        Config as NotifyConfig, Event, EventKind, PollWatcher, RecommendedWatcher, RecursiveMode,
+    },
+    DebounceEventResult, Debouncer, FileIdMap, RecommendedCache,
 };
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, LazyLock, Mutex};
-use std::time::Duration;
 use thiserror::Error;
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::oneshot;
+
+use crate::config::{WatcherConfig, CONFIG};
 
 #[derive(Debug, Error)]
 pub enum WatcherError {
     #[error("Failed to send watcher command")]
     CommandFailed,
+    #[error("{0:?} contains more than {MAX_TREE_DIRECTORIES} directories to watch")]
+    TreeTooLarge(PathBuf),
 }
 
+/// Most directories a watched tree may contain, leaving the excluded ones aside
+const MAX_TREE_DIRECTORIES: usize = 10_000;
+
 type WatcherResult<T> = Result<T, WatcherError>;
 
+/// What happened to a watched file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileEvent {
+    /// The file was written, or replaced by another file (e.g. an atomic save)
+    Modified,
+    /// The file was deleted
+    Removed,
+    /// The file was moved to another path
+    Renamed(PathBuf),
+}
+
 /// Global file watcher that manages file change notifications
 pub struct FileWatcher {
     command_tx: Sender<FileWatcherCommand>,
 }
 
 enum FileWatcherCommand {
-    Watch(PathBuf, Sender<()>),
-    Unwatch(PathBuf),
+    Watch(PathBuf, Sender<FileEvent>),
+    WatchDirectory(PathBuf, Sender<Vec<PathBuf>>),
+    WatchTree(
+        PathBuf,
+        Sender<Vec<PathBuf>>,
+        oneshot::Sender<WatcherResult<()>>,
+    ),
+    /// Apply the watcher configuration again
+    Reconfigure,
+    /// Plan the watches of the trees again after directories were added or removed
+    Rescan,
+    /// Stop watching the paths whose receivers are gone
+    Prune,
+}
+
+/// Receiving end of a watch, releasing the watch as soon as it is dropped
+pub struct Watch<T> {
+    rx: Receiver<T>,
+    command_tx: Sender<FileWatcherCommand>,
+}
+
+impl<T> Deref for Watch<T> {
+    type Target = Receiver<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.rx
+    }
+}
+
+impl<T> DerefMut for Watch<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.rx
+    }
+}
+
+impl<T> Drop for Watch<T> {
+    fn drop(&mut self) {
+        // Closing first lets the watcher thread see the channel as gone
+        self.rx.close();
+        let _ = self.command_tx.try_send(FileWatcherCommand::Prune);
+    }
+}
+
+/// Notification channels of the watched files and directories
+#[derive(Default)]
+struct Subscribers {
+    /// Files notified when they are modified, removed or renamed
+    files: HashMap<PathBuf, Vec<Sender<FileEvent>>>,
+    /// Directories notified with their entries that were added, removed or changed
+    directories: HashMap<PathBuf, Vec<Sender<Vec<PathBuf>>>>,
+    /// Directories notified with the paths that changed anywhere inside them
+    trees: HashMap<PathBuf, Vec<Sender<Vec<PathBuf>>>>,
+    /// Directories watching each tree without its excluded directories
+    tree_plans: HashMap<PathBuf, HashMap<PathBuf, RecursiveMode>>,
+}
+
+impl Subscribers {
+    /// Get the directories to watch and whether their subdirectories are watched too
+    ///
+    /// Files are watched through their parent directory so that the watch
+    /// survives the file being replaced, and renames can be followed.
+    /// Excluded paths keep their subscribers but are not watched, so that
+    /// they are watched again once the exclusion is removed.
+    fn watched_roots(&self, config: &WatcherConfig) -> HashMap<PathBuf, RecursiveMode> {
+        let files = self
+            .files
+            .keys()
+            .filter(|file| !config.excludes(file))
+            .map(|file| file.parent().unwrap_or(file).to_path_buf());
+        let mut roots: HashMap<PathBuf, RecursiveMode> = self
+            .directories
+            .keys()
+            .filter(|dir| !config.excludes(dir))
+            .cloned()
+            .chain(files)
+            .map(|root| (root, RecursiveMode::NonRecursive))
+            .collect();
+        for tree in self.trees.keys().filter(|tree| !config.excludes(tree)) {
+            let whole_tree = [(tree.clone(), RecursiveMode::Recursive)].into();
+            let plan = self.tree_plans.get(tree).unwrap_or(&whole_tree);
+            for (dir, &mode) in plan {
+                let watched = roots.entry(dir.clone()).or_insert(mode);
+                if mode == RecursiveMode::Recursive {
+                    *watched = mode;
+                }
+            }
+        }
+        roots
+    }
+
+    /// Plan the watches of every tree, ending the watches of the trees that are too large
+    fn plan_trees(&mut self, config: &WatcherConfig) {
+        let trees: Vec<PathBuf> = self.trees.keys().cloned().collect();
+        for tree in trees {
+            match plan_tree(&tree, config) {
+                Ok(plan) => {
+                    self.tree_plans.insert(tree, plan);
+                }
+                Err(e) => {
+                    // Dropping the senders ends the watches
+                    tracing::warn!("Stopped watching directory tree: {}", e);
+                    self.trees.remove(&tree);
+                    self.tree_plans.remove(&tree);
+                }
+            }
+        }
+    }
+
+    /// Drop the channels whose receivers are gone
+    fn prune_closed(&mut self) {
+        self.files.retain(|_, senders| {
+            senders.retain(|sender| !sender.is_closed());
+            !senders.is_empty()
+        });
+        self.directories.retain(|_, senders| {
+            senders.retain(|sender| !sender.is_closed());
+            !senders.is_empty()
+        });
+        self.trees.retain(|_, senders| {
+            senders.retain(|sender| !sender.is_closed());
+            !senders.is_empty()
+        });
+        self.tree_plans
+            .retain(|tree, _| self.trees.contains_key(tree));
+    }
+
+    /// Notify the subscribers of a batch of events, except those of excluded paths
+    ///
+    /// Returns whether directories were added to or removed from the planned
+    /// watches of a tree, so that it is planned again.
+    fn notify(&self, events: &[Event], config: &WatcherConfig) -> bool {
+        for (file, senders) in &self.files {
+            if config.excludes(file) {
+                continue;
+            }
+            if let Some(file_event) = classify_file_events(file, events, |path| path.exists()) {
+                tracing::debug!("File event: {:?} {:?}", file, file_event);
+                for sender in senders {
+                    let _ = sender.blocking_send(file_event.clone());
+                }
+            }
+        }
+
+        let changed_paths: HashSet<PathBuf> = events
+            .iter()
+            .filter(|event| !event.kind.is_access())
+            .flat_map(|event| event.paths.iter().cloned())
+            .collect();
+        for (dir, entries) in directory_changes(&changed_paths) {
+            if config.excludes(&dir) {
+                continue;
+            }
+            if let Some(senders) = self.directories.get(&dir) {
+                tracing::debug!("Directory changed: {:?} ({} entries)", dir, entries.len());
+                for sender in senders {
+                    let _ = sender.blocking_send(entries.clone());
+                }
+            }
+        }
+        for (tree, senders) in &self.trees {
+            if config.excludes(tree) {
+                continue;
+            }
+            let paths: Vec<PathBuf> = changed_paths
+                .iter()
+                .filter(|path| path.starts_with(tree))
+                .cloned()
+                .collect();
+            if paths.is_empty() {
+                continue;
+            }
+            tracing::debug!("Tree changed: {:?} ({} paths)", tree, paths.len());
+            for sender in senders {
+                let _ = sender.blocking_send(paths.clone());
+            }
+        }
+
+        self.tree_plans.values().any(|plan| {
+            changed_paths
+                .iter()
+                .any(|path| changes_plan(plan, path, |path| path.is_dir()))
+        })
+    }
+}
+
+/// Plan the directories watching a tree without its excluded directories
+///
+/// Subtrees without excluded directories are watched recursively, while the
+/// directories containing one are watched alone with their subdirectories
+/// planned in turn. Symbolic links are not followed.
+fn plan_tree(
+    tree: &Path,
+    config: &WatcherConfig,
+) -> WatcherResult<HashMap<PathBuf, RecursiveMode>> {
+    fn walk(
+        dir: &Path,
+        config: &WatcherConfig,
+        budget: &mut usize,
+        plan: &mut HashMap<PathBuf, RecursiveMode>,
+    ) -> Option<bool> {
+        *budget = budget.checked_sub(1)?;
+        let mut is_clean = true;
+        let mut subplan = HashMap::new();
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            if !entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                continue;
+            }
+            let path = entry.path();
+            if config.excludes(&path) {
+                is_clean = false;
+            } else {
+                is_clean &= walk(&path, config, budget, &mut subplan)?;
+            }
+        }
+        if is_clean {
+            plan.insert(dir.to_path_buf(), RecursiveMode::Recursive);
+        } else {
+            plan.insert(dir.to_path_buf(), RecursiveMode::NonRecursive);
+            plan.extend(subplan);
+        }
+        Some(is_clean)
+    }
+
+    let mut budget = MAX_TREE_DIRECTORIES;
+    let mut plan = HashMap::new();
+    walk(tree, config, &mut budget, &mut plan)
+        .ok_or_else(|| WatcherError::TreeTooLarge(tree.to_path_buf()))?;
+    Ok(plan)
+}
+
+/// Check whether a changed path adds a directory to a plan or removes one from it
+///
+/// Directories added inside recursively watched ones are already watched.
+fn changes_plan(
+    plan: &HashMap<PathBuf, RecursiveMode>,
+    path: &Path,
+    is_dir: impl Fn(&Path) -> bool,
+) -> bool {
+    if plan.contains_key(path) {
+        return !is_dir(path);
+    }
+    path.parent()
+        .is_some_and(|parent| plan.get(parent) == Some(&RecursiveMode::NonRecursive))
+        && is_dir(path)
+}
+
+/// Work out what a batch of events did to a file (`None` if it was untouched)
+///
+/// A file that still exists after the batch was modified, even if it was
+/// removed or renamed on the way (editors saving through a temporary file).
+/// Otherwise it was renamed if it was moved to a path that exists, or removed.
+fn classify_file_events(
+    file: &Path,
+    events: &[Event],
+    exists: impl Fn(&Path) -> bool,
+) -> Option<FileEvent> {
+    let mut touched = false;
+    let mut renamed_to = None;
+    for event in events {
+        if event.kind.is_access() || !event.paths.iter().any(|path| path == file) {
+            continue;
+        }
+        touched = true;
+        if let EventKind::Modify(ModifyKind::Name(RenameMode::Both)) = event.kind {
+            if let [from, to] = event.paths.as_slice() {
+                if from == file {
+                    renamed_to = Some(to.clone());
+                }
+            }
+        }
+    }
+
+    if !touched {
+        None
+    } else if exists(file) {
+        Some(FileEvent::Modified)
+    } else {
+        match renamed_to {
+            Some(to) if exists(&to) => Some(FileEvent::Renamed(to)),
+            _ => Some(FileEvent::Removed),
+        }
+    }
+}
+
+/// Group changed paths by the directory containing them
+fn directory_changes(changed_paths: &HashSet<PathBuf>) -> HashMap<PathBuf, Vec<PathBuf>> {
+    let mut changes: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for path in changed_paths {
+        if let Some(parent) = path.parent() {
+            changes
+                .entry(parent.to_path_buf())
+                .or_default()
+                .push(path.clone());
+        }
+    }
+    changes
+}
+
+/// File systems that are watched by polling even if they report themselves as local
+#[cfg(target_os = "macos")]
+const REMOTE_FS_TYPES: &[&str] = &[
+    "macfuse", "osxfuse", "fuse", "nfs", "smbfs", "afpfs", "webdav",
+];
+
+/// Check whether a path is on a network or other non-local file system
+///
+/// File system events are not delivered reliably for such paths (network
+/// mounts, FUSE), so they are polled instead.
+#[cfg(target_os = "macos")]
+fn is_remote_filesystem(path: &Path) -> bool {
+    use std::ffi::{CStr, CString};
+    use std::mem::MaybeUninit;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat = MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: the path is a valid C string and the buffer is only read once filled by statfs
+    let stat = unsafe {
+        if libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return false;
+        }
+        stat.assume_init()
+    };
+    // SAFETY: statfs stores the file system type as a NUL-terminated string
+    let fs_type = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) }.to_string_lossy();
+    stat.f_flags & libc::MNT_LOCAL as u32 == 0
+        || REMOTE_FS_TYPES
+            .iter()
+            .any(|remote| fs_type.starts_with(remote))
+}
+
+/// Magic numbers of the file systems that are polled (see statfs(2))
+#[cfg(target_os = "linux")]
+const REMOTE_FS_MAGICS: &[u32] = &[
+    0x6969,     // NFS
+    0x517B,     // SMB
+    0xFE534D42, // SMB2
+    0xFF534D42, // CIFS
+    0x65735546, // FUSE (also virtiofs, e.g. Docker Desktop bind mounts)
+    0x01021997, // 9P (e.g. Windows drives in WSL 2)
+    0x5346414F, // AFS
+    0x73757245, // Coda
+    0x00C36400, // Ceph
+];
+
+/// Check whether a path is on a network or other non-local file system
+///
+/// File system events are not delivered for changes made by other machines
+/// or by the host of a container or WSL, so such paths are polled instead.
+#[cfg(target_os = "linux")]
+fn is_remote_filesystem(path: &Path) -> bool {
+    use std::ffi::CString;
+    use std::mem::MaybeUninit;
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(c_path) = CString::new(path.as_os_str().as_bytes()) else {
+        return false;
+    };
+    let mut stat = MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: the path is a valid C string and the buffer is only read once filled by statfs
+    let stat = unsafe {
+        if libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return false;
+        }
+        stat.assume_init()
+    };
+    // The type of f_type differs between architectures; the magic numbers fit in 32 bits
+    #[allow(clippy::unnecessary_cast)]
+    let fs_type = stat.f_type as u32;
+    REMOTE_FS_MAGICS.contains(&fs_type)
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn is_remote_filesystem(_path: &Path) -> bool {
+    false
+}
+
+/// Build the handler notifying the subscribers of the debounced events
+fn event_handler(
+    subscribers: Arc<Mutex<Subscribers>>,
+    config: WatcherConfig,
+    command_tx: Sender<FileWatcherCommand>,
+) -> impl FnMut(DebounceEventResult) + Send + 'static {
+    move |result: DebounceEventResult| match result {
+        Ok(events) => {
+            let events: Vec<Event> = events.into_iter().map(|event| event.event).collect();
+            // Notify all watchers for changed files and directories
+            if subscribers.lock().unwrap().notify(&events, &config) {
+                let _ = command_tx.try_send(FileWatcherCommand::Rescan);
+            }
+        }
+        Err(errors) => {
+            for error in errors {
+                tracing::error!("File watcher error: {:?}", error);
+            }
+        }
+    }
+}
+
+/// Debouncers watching the roots with file system events or by polling
+///
+/// Each debouncer is created the first time a root needs it.
+struct Backends {
+    config: WatcherConfig,
+    subscribers: Arc<Mutex<Subscribers>>,
+    /// Commands sent by the handlers, e.g. to plan the trees again
+    command_tx: Sender<FileWatcherCommand>,
+    native: Option<Debouncer<RecommendedWatcher, RecommendedCache>>,
+    polling: Option<Debouncer<PollWatcher, FileIdMap>>,
+    /// Watched roots, whether they are polled and whether their subdirectories are watched
+    roots: HashMap<PathBuf, (bool, RecursiveMode)>,
+}
+
+impl Backends {
+    fn new(
+        config: WatcherConfig,
+        subscribers: Arc<Mutex<Subscribers>>,
+        command_tx: Sender<FileWatcherCommand>,
+    ) -> Self {
+        Self {
+            config,
+            subscribers,
+            command_tx,
+            native: None,
+            polling: None,
+            roots: HashMap::new(),
+        }
+    }
+
+    fn native(&mut self) -> Option<&mut Debouncer<RecommendedWatcher, RecommendedCache>> {
+        if self.native.is_none() {
+            match new_debouncer(
+                self.config.debounce(),
+                None,
+                event_handler(
+                    self.subscribers.clone(),
+                    self.config.clone(),
+                    self.command_tx.clone(),
+                ),
+            ) {
+                Ok(debouncer) => self.native = Some(debouncer),
+                Err(e) => tracing::error!("Failed to create file watcher: {:?}", e),
+            }
+        }
+        self.native.as_mut()
+    }
+
+    fn polling(&mut self) -> Option<&mut Debouncer<PollWatcher, FileIdMap>> {
+        if self.polling.is_none() {
+            match new_debouncer_opt(
+                self.config.debounce(),
+                None,
+                event_handler(
+                    self.subscribers.clone(),
+                    self.config.clone(),
+                    self.command_tx.clone(),
+                ),
+                FileIdMap::new(),
+                NotifyConfig::default().with_poll_interval(self.config.poll_interval()),
+            ) {
+                Ok(debouncer) => self.polling = Some(debouncer),
+                Err(e) => tracing::error!("Failed to create polling file watcher: {:?}", e),
+            }
+        }
+        self.polling.as_mut()
+    }
+
+    /// Watch the wanted roots and stop watching the others
+    ///
+    /// Roots that could not be watched (e.g. missing directories) are tried again next time.
+    fn sync(&mut self, wanted: &HashMap<PathBuf, RecursiveMode>) {
+        let unwanted: Vec<PathBuf> = self
+            .roots
+            .iter()
+            .filter(|(root, (_, mode))| wanted.get(*root) != Some(mode))
+            .map(|(root, _)| root.clone())
+            .collect();
+        for root in unwanted {
+            let Some((polled, _)) = self.roots.remove(&root) else {
+                continue;
+            };
+            let result = if polled {
+                self.polling
+                    .as_mut()
+                    .map(|debouncer| debouncer.unwatch(&root))
+            } else {
+                self.native
+                    .as_mut()
+                    .map(|debouncer| debouncer.unwatch(&root))
+            };
+            match result {
+                Some(Err(e)) => tracing::error!("Failed to unwatch {:?}: {:?}", root, e),
+                _ => tracing::info!("Stopped watching: {:?}", root),
+            }
+        }
+
+        for (root, &mode) in wanted {
+            if self.roots.contains_key(root) {
+                continue;
+            }
+            let polled = self.config.polls(is_remote_filesystem(root));
+            let result = if polled {
+                self.polling().map(|debouncer| debouncer.watch(root, mode))
+            } else {
+                self.native().map(|debouncer| debouncer.watch(root, mode))
+            };
+            match result {
+                Some(Ok(())) => {
+                    let how = if polled { "polling" } else { "watching" };
+                    tracing::info!("Started {} ({:?}): {:?}", how, mode, root);
+                    self.roots.insert(root.clone(), (polled, mode));
+                }
+                Some(Err(e)) => tracing::error!("Failed to watch {:?}: {:?}", root, e),
+                None => {}
+            }
+        }
+    }
+
+    /// Apply a new configuration, so that the next sync watches every root again if it changed
+    ///
+    /// The debouncers are rebuilt too, so that their handlers use the new exclusions.
+    /// Returns whether the configuration changed.
+    fn reconfigure(&mut self, config: WatcherConfig) -> bool {
+        if config == self.config {
+            return false;
+        }
+        // Dropping the debouncers stops them; they are rebuilt with the new timings
+        self.native = None;
+        self.polling = None;
+        self.roots.clear();
+        self.config = config;
+        true
+    }
 }
 
 impl FileWatcher {
     fn new() -> Self {
         let (command_tx, mut command_rx) = mpsc::channel::<FileWatcherCommand>(100);
+        let handler_tx = command_tx.clone();
 
         // Spawn a dedicated thread for the file watcher
         std::thread::spawn(move || {
-            // Map of file paths to their notification channels
-            let watchers: Arc<Mutex<HashMap<PathBuf, Vec<Sender<()>>>>> =
-                Arc::new(Mutex::new(HashMap::new()));
-            let watchers_clone = watchers.clone();
-
-            // Create a debouncer with 500ms delay
-            let mut debouncer: Debouncer<
-                notify_debouncer_full::notify::RecommendedWatcher,
-                RecommendedCache,
-            > = match new_debouncer(
-                Duration::from_millis(500),
-                None,
-                move |result: DebounceEventResult| match result {
-                    Ok(events) => {
-                        // Collect unique paths that changed
-                        let mut changed_paths = std::collections::HashSet::new();
-                        for event in events {
-                            for path in &event.paths {
-                                changed_paths.insert(path.clone());
-                            }
-                        }
-
-                        // Notify all watchers for changed files
-                        let watchers = watchers_clone.lock().unwrap();
-                        for path in changed_paths {
-                            if let Some(senders) = watchers.get(&path) {
-                                tracing::debug!("File changed: {:?}", path);
-                                for sender in senders {
-                                    let _ = sender.blocking_send(());
-                                }
-                            }
-                        }
-                    }
-                    Err(errors) => {
-                        for error in errors {
-                            tracing::error!("File watcher error: {:?}", error);
-                        }
-                    }
-                },
-            ) {
-                Ok(d) => d,
-                Err(e) => {
-                    tracing::error!("Failed to create file watcher: {:?}", e);
-                    return;
-                }
-            };
+            // Notification channels of the watched paths
+            let subscribers: Arc<Mutex<Subscribers>> = Arc::default();
+            let mut backends = Backends::new(
+                CONFIG.read().watcher.clone(),
+                subscribers.clone(),
+                handler_tx,
+            );
 
             tracing::info!("Global file watcher started");
 
             // Process commands
             loop {
-                match command_rx.blocking_recv() {
-                    Some(FileWatcherCommand::Watch(path, tx)) => {
-                        let mut watchers = watchers.lock().unwrap();
-                        let is_first = !watchers.contains_key(&path);
+                let command = command_rx.blocking_recv();
+                let mut subscribers = subscribers.lock().unwrap();
 
-                        watchers.entry(path.clone()).or_default().push(tx);
+                // Forget the channels whose receivers were all dropped
+                subscribers.prune_closed();
 
-                        // Only start watching if this is the first watcher for this file
-                        if is_first {
-                            if let Err(e) = debouncer.watch(&path, RecursiveMode::NonRecursive) {
-                                tracing::error!("Failed to watch file {:?}: {:?}", path, e);
-                            } else {
-                                tracing::info!("Started watching file: {:?}", path);
+                match command {
+                    Some(FileWatcherCommand::Watch(path, tx)) => {
+                        tracing::info!("Watching file: {:?}", path);
+                        subscribers.files.entry(path).or_default().push(tx);
+                    }
+                    Some(FileWatcherCommand::WatchDirectory(path, tx)) => {
+                        tracing::info!("Watching directory: {:?}", path);
+                        subscribers.directories.entry(path).or_default().push(tx);
+                    }
+                    Some(FileWatcherCommand::WatchTree(path, tx, reply)) => {
+                        // Excluded directories are left out before the tree is watched
+                        match plan_tree(&path, &backends.config) {
+                            Ok(plan) => {
+                                tracing::info!("Watching directory tree: {:?}", path);
+                                subscribers.tree_plans.insert(path.clone(), plan);
+                                subscribers.trees.entry(path).or_default().push(tx);
+                                let _ = reply.send(Ok(()));
+                            }
+                            Err(e) => {
+                                tracing::warn!("Not watching directory tree: {}", e);
+                                let _ = reply.send(Err(e));
                             }
                         }
                     }
-                    Some(FileWatcherCommand::Unwatch(path)) => {
-                        let mut watchers = watchers.lock().unwrap();
-                        if let Some(senders) = watchers.get_mut(&path) {
-                            senders.pop();
-                            // If no more watchers for this file, stop watching
-                            if senders.is_empty() {
-                                watchers.remove(&path);
-                                if let Err(e) = debouncer.unwatch(&path) {
-                                    tracing::error!("Failed to unwatch file {:?}: {:?}", path, e);
-                                } else {
-                                    tracing::info!("Stopped watching file: {:?}", path);
-                                }
-                            }
+                    Some(FileWatcherCommand::Reconfigure) => {
+                        // Excluded paths stay subscribed and are watched again once allowed
+                        if backends.reconfigure(CONFIG.read().watcher.clone()) {
+                            subscribers.plan_trees(&backends.config);
                         }
                     }
+                    Some(FileWatcherCommand::Rescan) => {
+                        subscribers.plan_trees(&backends.config);
+                    }
+                    Some(FileWatcherCommand::Prune) => {}
                     None => {
                         tracing::info!("File watcher command channel closed");
                         break;
                     }
                 }
+
+                let roots = subscribers.watched_roots(&backends.config);
+                // The handlers of the debouncers lock the subscribers
+                drop(subscribers);
+                backends.sync(&roots);
             }
         });
 
         Self { command_tx }
     }
 
-    /// Watch a file and receive notifications when it changes
-    pub async fn watch(&self, path: impl Into<PathBuf>) -> WatcherResult<Receiver<()>> {
+    /// Watch a file and receive what happens to it
+    ///
+    /// The watch follows the path: it survives the file being replaced
+    /// (atomic saves) and ends when the receiver is dropped.
+    pub async fn watch(&self, path: impl Into<PathBuf>) -> WatcherResult<Watch<FileEvent>> {
         let path = path.into();
         let (tx, rx) = mpsc::channel(100);
         self.command_tx
             .send(FileWatcherCommand::Watch(path, tx))
             .await
             .map_err(|_| WatcherError::CommandFailed)?;
-        Ok(rx)
+        Ok(self.watch_of(rx))
+    }
+
+    /// Watch several files and receive what happens to any of them
+    ///
+    /// The watch of every file ends when the receiver is dropped.
+    pub async fn watch_files(
+        &self,
+        paths: impl IntoIterator<Item = PathBuf>,
+    ) -> WatcherResult<Watch<FileEvent>> {
+        let (tx, rx) = mpsc::channel(100);
+        for path in paths {
+            self.command_tx
+                .send(FileWatcherCommand::Watch(path, tx.clone()))
+                .await
+                .map_err(|_| WatcherError::CommandFailed)?;
+        }
+        Ok(self.watch_of(rx))
     }
 
-    /// Stop watching a file
-    pub async fn unwatch(&self, path: impl Into<PathBuf>) -> WatcherResult<()> {
+    /// Watch a directory and all its subdirectories, except the excluded ones
+    ///
+    /// Each notification lists the paths that were added, removed, renamed or
+    /// modified anywhere inside. The watch ends when the receiver is dropped,
+    /// or when the tree grows beyond the directories that can be watched.
+    /// Trees that are already too large (e.g. a home directory) are refused.
+    pub async fn watch_tree(&self, path: impl Into<PathBuf>) -> WatcherResult<Watch<Vec<PathBuf>>> {
         let path = path.into();
+        let (tx, rx) = mpsc::channel(100);
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.command_tx
+            .send(FileWatcherCommand::WatchTree(path, tx, reply_tx))
+            .await
+            .map_err(|_| WatcherError::CommandFailed)?;
+        reply_rx.await.map_err(|_| WatcherError::CommandFailed)??;
+        Ok(self.watch_of(rx))
+    }
+
+    /// Apply the watcher configuration after it changed
+    pub async fn reconfigure(&self) -> WatcherResult<()> {
         self.command_tx
-            .send(FileWatcherCommand::Unwatch(path))
+            .send(FileWatcherCommand::Reconfigure)
             .await
             .map_err(|_| WatcherError::CommandFailed)
     }
+
+    /// Watch the entries of a directory (not recursively)
+    ///
+    /// Each notification lists the entries that were added, removed, renamed or
+    /// modified. The watch ends when the receiver is dropped.
+    pub async fn watch_directory(
+        &self,
+        path: impl Into<PathBuf>,
+    ) -> WatcherResult<Watch<Vec<PathBuf>>> {
+        let path = path.into();
+        let (tx, rx) = mpsc::channel(100);
+        self.command_tx
+            .send(FileWatcherCommand::WatchDirectory(path, tx))
+            .await
+            .map_err(|_| WatcherError::CommandFailed)?;
+        Ok(self.watch_of(rx))
+    }
+
+    fn watch_of<T>(&self, rx: Receiver<T>) -> Watch<T> {
+        Watch {
+            rx,
+            command_tx: self.command_tx.clone(),
+        }
+    }
 }
 
 pub static FILE_WATCHER: LazyLock<FileWatcher> = LazyLock::new(FileWatcher::new);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_changes_groups_by_parent() {
+        let changed: HashSet<PathBuf> = [
+            "/docs/a.md",
+            "/docs/b.md",
+            "/docs/guide/c.md",
+            "/docs/guide",
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let changes = directory_changes(&changed);
+
+        let mut docs = changes[Path::new("/docs")].clone();
+        docs.sort();
+        assert_eq!(
+            docs,
+            vec![
+                PathBuf::from("/docs/a.md"),
+                PathBuf::from("/docs/b.md"),
+                PathBuf::from("/docs/guide"),
+            ]
+        );
+        assert_eq!(
+            changes[Path::new("/docs/guide")],
+            vec![PathBuf::from("/docs/guide/c.md")]
+        );
+    }
+
+    #[test]
+    fn test_subscribers_watched_roots() {
+        let config = WatcherConfig::default();
+        let mut subscribers = Subscribers::default();
+        let (file_tx, file_rx) = mpsc::channel(1);
+        let (dir_tx, dir_rx) = mpsc::channel(1);
+        let (shared_tx, _shared_rx) = mpsc::channel(1);
+        subscribers
+            .files
+            .insert(PathBuf::from("/docs/a.md"), vec![file_tx]);
+        subscribers
+            .directories
+            .insert(PathBuf::from("/notes"), vec![dir_tx]);
+        subscribers
+            .files
+            .insert(PathBuf::from("/notes/b.md"), vec![shared_tx]);
+
+        // Files are watched through their directory
+        let expected: HashMap<PathBuf, RecursiveMode> = [
+            (PathBuf::from("/docs"), RecursiveMode::NonRecursive),
+            (PathBuf::from("/notes"), RecursiveMode::NonRecursive),
+        ]
+        .into();
+        assert_eq!(subscribers.watched_roots(&config), expected);
+
+        // Trees are watched recursively
+        let (tree_tx, tree_rx) = mpsc::channel(1);
+        subscribers
+            .trees
+            .insert(PathBuf::from("/docs"), vec![tree_tx]);
+        assert_eq!(
+            subscribers.watched_roots(&config)[Path::new("/docs")],
+            RecursiveMode::Recursive
+        );
+        drop(tree_rx);
+
+        drop(file_rx);
+        drop(dir_rx);
+        subscribers.prune_closed();
+        // /notes is still needed by the watch of /notes/b.md
+        let expected: HashMap<PathBuf, RecursiveMode> =
+            [(PathBuf::from("/notes"), RecursiveMode::NonRecursive)].into();
+        assert_eq!(subscribers.watched_roots(&config), expected);
+        assert!(subscribers.directories.is_empty());
+        assert!(subscribers.trees.is_empty());
+    }
+
+    #[test]
+    fn test_subscribers_keep_excluded_paths() {
+        let mut subscribers = Subscribers::default();
+        let (file_tx, _file_rx) = mpsc::channel(1);
+        let (tree_tx, _tree_rx) = mpsc::channel(1);
+        subscribers
+            .files
+            .insert(PathBuf::from("/docs/a.md"), vec![file_tx]);
+        subscribers
+            .trees
+            .insert(PathBuf::from("/work/node_modules"), vec![tree_tx]);
+
+        let excluding = WatcherConfig {
+            exclude: vec!["node_modules".to_string()],
+            ..WatcherConfig::default()
+        };
+        let expected: HashMap<PathBuf, RecursiveMode> =
+            [(PathBuf::from("/docs"), RecursiveMode::NonRecursive)].into();
+        assert_eq!(subscribers.watched_roots(&excluding), expected);
+
+        // Removing the exclusion watches the path again
+        subscribers.prune_closed();
+        assert_eq!(
+            subscribers.watched_roots(&WatcherConfig::default())[Path::new("/work/node_modules")],
+            RecursiveMode::Recursive
+        );
+    }
+
+    #[test]
+    fn test_plan_tree_leaves_out_excluded_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in ["docs/guide", "web/node_modules/pkg", "web/src"] {
+            std::fs::create_dir_all(root.join(path)).unwrap();
+        }
+        let config = WatcherConfig {
+            exclude: vec!["node_modules".to_string()],
+            ..WatcherConfig::default()
+        };
+
+        let expected: HashMap<PathBuf, RecursiveMode> = [
+            (root.to_path_buf(), RecursiveMode::NonRecursive),
+            (root.join("docs"), RecursiveMode::Recursive),
+            (root.join("web"), RecursiveMode::NonRecursive),
+            (root.join("web/src"), RecursiveMode::Recursive),
+        ]
+        .into();
+        assert_eq!(plan_tree(root, &config).unwrap(), expected);
+
+        // Without exclusions the whole tree is watched at once
+        let expected: HashMap<PathBuf, RecursiveMode> =
+            [(root.to_path_buf(), RecursiveMode::Recursive)].into();
+        assert_eq!(
+            plan_tree(root, &WatcherConfig::default()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_plan_tree_refuses_large_trees() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..MAX_TREE_DIRECTORIES {
+            std::fs::create_dir(dir.path().join(i.to_string())).unwrap();
+        }
+        assert!(matches!(
+            plan_tree(dir.path(), &WatcherConfig::default()),
+            Err(WatcherError::TreeTooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_changes_plan() {
+        let plan: HashMap<PathBuf, RecursiveMode> = [
+            (PathBuf::from("/work"), RecursiveMode::NonRecursive),
+            (PathBuf::from("/work/docs"), RecursiveMode::Recursive),
+        ]
+        .into();
+        let dirs = ["/work", "/work/docs", "/work/web", "/work/docs/guide"];
+        let is_dir = |path: &Path| dirs.iter().any(|dir| path == Path::new(dir));
+
+        // A directory added next to an excluded one needs its own watch
+        assert!(changes_plan(&plan, Path::new("/work/web"), is_dir));
+        // Directories inside a recursive watch and files are already watched
+        assert!(!changes_plan(&plan, Path::new("/work/docs/guide"), is_dir));
+        assert!(!changes_plan(&plan, Path::new("/work/a.md"), is_dir));
+        // A removed directory is no longer watched
+        assert!(changes_plan(&plan, Path::new("/work/docs"), |_| false));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_is_remote_filesystem_on_linux() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_remote_filesystem(dir.path()));
+        assert!(!is_remote_filesystem(&dir.path().join("missing")));
+        // WSL 2 mounts Windows drives over 9P
+        assert!(REMOTE_FS_MAGICS.contains(&0x01021997));
+    }
+
+    #[test]
+    fn test_dropping_watch_releases_it() {
+        let (command_tx, mut command_rx) = mpsc::channel(1);
+        let (tx, rx) = mpsc::channel::<FileEvent>(1);
+
+        drop(Watch { rx, command_tx });
+
+        assert!(tx.is_closed());
+        assert!(matches!(
+            command_rx.try_recv(),
+            Ok(FileWatcherCommand::Prune)
+        ));
+    }
+
+    fn event(kind: EventKind, paths: &[&str]) -> Event {
+        paths
+            .iter()
+            .fold(Event::new(kind), |event, path| event.add_path(path.into()))
+    }
+
+    #[test]
+    fn test_classify_file_events() {
+        use notify_debouncer_full::notify::event::{AccessKind, CreateKind, RemoveKind};
+
+        let file = Path::new("/docs/a.md");
+        let rename = EventKind::Modify(ModifyKind::Name(RenameMode::Both));
+        let exists = |paths: &'static [&'static str]| {
+            move |path: &Path| paths.iter().any(|p| Path::new(p) == path)
+        };
+
+        // Untouched or only read
+        let other = [event(EventKind::Create(CreateKind::File), &["/docs/b.md"])];
+        assert_eq!(
+            classify_file_events(file, &other, exists(&["/docs/a.md"])),
+            None
+        );
+        let read = [event(EventKind::Access(AccessKind::Any), &["/docs/a.md"])];
+        assert_eq!(
+            classify_file_events(file, &read, exists(&["/docs/a.md"])),
+            None
+        );
+
+        // Atomic save: a temporary file is renamed over the file
+        let atomic = [
+            event(EventKind::Create(CreateKind::File), &["/docs/.a.md.tmp"]),
+            event(rename, &["/docs/.a.md.tmp", "/docs/a.md"]),
+        ];
+        assert_eq!(
+            classify_file_events(file, &atomic, exists(&["/docs/a.md"])),
+            Some(FileEvent::Modified)
+        );
+
+        // Backup save: the file is moved aside and written again
+        let backup = [
+            event(rename, &["/docs/a.md", "/docs/a.md~"]),
+            event(EventKind::Create(CreateKind::File), &["/docs/a.md"]),
+        ];
+        assert_eq!(
+            classify_file_events(file, &backup, exists(&["/docs/a.md", "/docs/a.md~"])),
+            Some(FileEvent::Modified)
+        );
+
+        // Moved
+        let moved = [event(rename, &["/docs/a.md", "/docs/b.md"])];
+        assert_eq!(
+            classify_file_events(file, &moved, exists(&["/docs/b.md"])),
+            Some(FileEvent::Renamed(PathBuf::from("/docs/b.md")))
+        );
+
+        // Deleted
+        let removed = [event(EventKind::Remove(RemoveKind::File), &["/docs/a.md"])];
+        assert_eq!(
+            classify_file_events(file, &removed, exists(&[])),
+            Some(FileEvent::Removed)
+        );
+    }
+}
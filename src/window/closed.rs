@@ -0,0 +1,68 @@
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+
+use crate::state::{AppState, ClosedTabs, PersistedState, Tab, TabContent};
+
+use super::main::{restore_main_window, WindowContents};
+
+/// Maximum number of closed windows remembered
+const MAX_CLOSED_WINDOWS: usize = 10;
+
+/// Tabs and layout of a closed main window
+struct ClosedWindow {
+    tabs: Vec<Tab>,
+    active_tab: usize,
+    closed_tabs: ClosedTabs,
+    expanded_dirs: HashSet<PathBuf>,
+    state: PersistedState,
+}
+
+thread_local! {
+    static CLOSED_WINDOWS: RefCell<VecDeque<ClosedWindow>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Remember a closing main window for "Reopen Closed Window"
+///
+/// Windows with nothing to reopen (only empty tabs) are skipped.
+pub fn record_closed_window(state: &AppState, persisted: PersistedState) {
+    let tabs = state.tabs.read().clone();
+    let closed_tabs = state.closed_tabs.read().clone();
+    if closed_tabs.is_empty() && tabs.iter().all(|tab| tab.content == TabContent::None) {
+        return;
+    }
+    let window = ClosedWindow {
+        tabs,
+        active_tab: *state.active_tab.read(),
+        closed_tabs,
+        expanded_dirs: state.sidebar.read().expanded_dirs.clone(),
+        state: persisted,
+    };
+    CLOSED_WINDOWS.with(|windows| {
+        let mut windows = windows.borrow_mut();
+        if windows.len() == MAX_CLOSED_WINDOWS {
+            windows.pop_front();
+        }
+        windows.push_back(window);
+    });
+}
+
+/// Reopen the most recently closed main window with its tabs and layout
+///
+/// Returns false if no window has been closed.
+pub fn reopen_closed_window() -> bool {
+    let Some(window) = CLOSED_WINDOWS.with(|windows| windows.borrow_mut().pop_back()) else {
+        return false;
+    };
+    let contents = WindowContents {
+        tabs: window.tabs,
+        active_tab: window.active_tab,
+        closed_tabs: window.closed_tabs,
+        expanded_dirs: window.expanded_dirs,
+    };
+    spawn(async move {
+        restore_main_window(contents, window.state.into()).await;
+    });
+    true
+}
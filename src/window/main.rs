@@ -4,12 +4,13 @@ use dioxus::desktop::{window, Config, WeakDesktopContext, WindowBuilder};
 use dioxus::prelude::*;
 
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 use crate::assets::MAIN_STYLE;
 use crate::components::app::{App, AppProps};
-use crate::config::{WindowPositionOffset, CONFIG};
-use crate::state::{Tab, LAST_FOCUSED_STATE};
+use crate::config::{SidebarPanel, WindowPositionOffset, CONFIG};
+use crate::state::{ClosedTabs, PersistedState, Tab, LAST_FOCUSED_STATE};
 use crate::theme::Theme;
 use crate::utils::screen::get_current_display_bounds;
 
@@ -45,6 +46,7 @@ pub struct CreateMainWindowConfigParams {
     pub sidebar_open: bool,
     pub sidebar_width: f64,
     pub sidebar_show_all_files: bool,
+    pub sidebar_panel: SidebarPanel,
     pub size: LogicalSize<u32>,
     pub position: LogicalPosition<i32>,
 }
@@ -65,6 +67,7 @@ impl CreateMainWindowConfigParams {
             sidebar_open: sidebar_pref.open,
             sidebar_width: sidebar_pref.width,
             sidebar_show_all_files: sidebar_pref.show_all_files,
+            sidebar_panel: sidebar_pref.panel,
             size: size_pref.size,
             position: position_pref.position,
         }
@@ -78,6 +81,31 @@ impl Default for CreateMainWindowConfigParams {
     }
 }
 
+/// Reuse the layout of a closed (or saved) window
+impl From<PersistedState> for CreateMainWindowConfigParams {
+    fn from(state: PersistedState) -> Self {
+        Self {
+            directory: state.directory,
+            theme: state.theme,
+            sidebar_open: state.sidebar_open,
+            sidebar_width: state.sidebar_width,
+            sidebar_show_all_files: state.sidebar_show_all_files,
+            sidebar_panel: state.sidebar_panel,
+            size: LogicalSize::new(state.window_size.width, state.window_size.height),
+            position: LogicalPosition::new(state.window_position.x, state.window_position.y),
+        }
+    }
+}
+
+/// Tabs and sidebar state of a window being reopened or restored
+#[derive(Default)]
+pub struct WindowContents {
+    pub tabs: Vec<Tab>,
+    pub active_tab: usize,
+    pub closed_tabs: ClosedTabs,
+    pub expanded_dirs: HashSet<PathBuf>,
+}
+
 thread_local! {
     static MAIN_WINDOWS: RefCell<Vec<WeakDesktopContext>> = const { RefCell::new(Vec::new()) };
     static LAST_FOCUSED_WINDOW: RefCell<Option<WindowId>> = const { RefCell::new(None) };
@@ -160,6 +188,13 @@ pub fn focus_last_focused_main_window() -> bool {
     }
 }
 
+/// Check whether a window is the main window that had focus last
+///
+/// Unlike `is_focused`, this still holds while another application is in front.
+pub fn is_last_focused_main_window(window_id: WindowId) -> bool {
+    get_last_focused_window().map(child::resolve_to_parent_window) == Some(window_id)
+}
+
 pub fn close_all_main_windows() {
     let windows = list_main_window_contexts();
     windows.iter().for_each(|w| w.close());
@@ -176,8 +211,41 @@ pub fn close_all_main_windows() {
 /// 4. "/" (final fallback - always succeeds)
 pub(crate) async fn create_new_main_window(
     tab: Tab,
+    params: CreateMainWindowConfigParams,
+) -> WindowId {
+    create_new_main_window_with_tabs(vec![tab], params).await
+}
+
+/// Create window with several tabs (the first one is active; an empty tab if none)
+pub async fn create_new_main_window_with_tabs(
+    tabs: Vec<Tab>,
+    params: CreateMainWindowConfigParams,
+) -> WindowId {
+    let contents = WindowContents {
+        tabs,
+        ..Default::default()
+    };
+    restore_main_window(contents, params).await
+}
+
+/// Create window with the tabs of a closed (or saved) window, keeping its active tab,
+/// closed tabs and expanded directories
+pub async fn restore_main_window(
+    contents: WindowContents,
     mut params: CreateMainWindowConfigParams,
 ) -> WindowId {
+    let WindowContents {
+        mut tabs,
+        active_tab,
+        closed_tabs,
+        expanded_dirs,
+    } = contents;
+    let tab = if tabs.is_empty() {
+        Tab::default()
+    } else {
+        tabs.remove(0)
+    };
+
     // Resolve directory: params → tab parent → home dir → root (guaranteed to succeed)
     let directory = params
         .directory
@@ -212,11 +280,16 @@ pub(crate) async fn create_new_main_window(
         App,
         AppProps {
             tab,
+            additional_tabs: tabs,
+            active_tab,
+            closed_tabs,
+            expanded_dirs,
             directory,
             theme: params.theme,
             sidebar_open: params.sidebar_open,
             sidebar_width: params.sidebar_width,
             sidebar_show_all_files: params.sidebar_show_all_files,
+            sidebar_panel: params.sidebar_panel,
         },
     );
 
@@ -3,8 +3,8 @@ use mouse_position::mouse_position::Mouse;
 use std::path::PathBuf;
 
 use crate::config::{
-    NewWindowBehavior, StartupBehavior, WindowDimension, WindowDimensionUnit, WindowPosition,
-    WindowPositionMode, WindowSize, CONFIG,
+    NewWindowBehavior, SidebarPanel, StartupBehavior, WindowDimension, WindowDimensionUnit,
+    WindowPosition, WindowPositionMode, WindowSize, CONFIG,
 };
 use crate::state::{Position, Size, LAST_FOCUSED_STATE};
 use crate::theme::Theme;
@@ -28,6 +28,7 @@ pub struct SidebarPreference {
     pub open: bool,
     pub width: f64,
     pub show_all_files: bool,
+    pub panel: SidebarPanel,
 }
 
 pub struct WindowSizePreference {
@@ -232,6 +233,7 @@ pub fn get_sidebar_preference(is_first_window: bool) -> SidebarPreference {
             open: cfg.sidebar.default_open,
             width: cfg.sidebar.default_width,
             show_all_files: cfg.sidebar.default_show_all_files,
+            panel: cfg.sidebar.default_panel,
         },
         || {
             let state = LAST_FOCUSED_STATE.read();
@@ -239,6 +241,7 @@ pub fn get_sidebar_preference(is_first_window: bool) -> SidebarPreference {
                 open: state.sidebar_open,
                 width: state.sidebar_width,
                 show_all_files: state.sidebar_show_all_files,
+                panel: state.sidebar_panel,
             }
         },
     )
@@ -35,6 +35,15 @@ const icons = [
   "info-circle",
   "brand-github",
   "bug",
+  "folders",
+  "list-tree",
+  "markdown",
+  "printer",
+  "restore",
+  "history",
+  "player-pause",
+  "player-play",
+  "live-view",
 ];
 const iconsDir = path.join(__dirname, "../node_modules/@tabler/icons/icons/outline");
 const outputPath = path.join(__dirname, "../public/icons/tabler-sprite.svg");
@@ -3,6 +3,7 @@ import "../style/main.css";
 import { type Theme, getSystemTheme } from "./theme";
 import * as markdownViewer from "./markdown-viewer";
 import * as syntaxHighlighter from "./syntax-highlighter";
+import * as sourceViewer from "./source-viewer";
 import * as mermaidRenderer from "./mermaid-renderer";
 import { renderCoordinator } from "./render-coordinator";
 
@@ -25,9 +26,40 @@ export function setCurrentTheme(theme: Theme) {
   renderCoordinator.forceRenderMermaid();
 }
 
+export type PrintOptions = {
+  forceLightTheme: boolean;
+};
+
+// Theme to restore once printing is over
+let themeBeforePrint: Theme | null = null;
+
+/** Get the document ready to be printed, with every diagram and formula rendered */
+export async function preparePrint({ forceLightTheme }: PrintOptions): Promise<void> {
+  const theme = getCurrentTheme();
+  if (forceLightTheme && theme !== "light") {
+    themeBeforePrint = theme;
+    setCurrentTheme("light");
+  }
+  await renderCoordinator.whenRendered();
+}
+
+/** Restore what `preparePrint` changed */
+export function finishPrint(): void {
+  if (themeBeforePrint !== null) {
+    setCurrentTheme(themeBeforePrint);
+    themeBeforePrint = null;
+  }
+}
+
+/** Wait until diagrams, math and code are rendered */
+export function whenRendered(): Promise<void> {
+  return renderCoordinator.whenRendered();
+}
+
 export function init(): void {
   markdownViewer.mount();
   syntaxHighlighter.mount();
+  sourceViewer.init();
   mermaidRenderer.init();
   renderCoordinator.init();
 
@@ -22,6 +22,9 @@ function renderInlineMath(container: Element): void {
         element: element as HTMLElement,
         content: mathContent,
       });
+    } else {
+      // Mark empty expressions as rendered to skip in future
+      element.dataset.katexRendered = "true";
     }
   }
 
@@ -38,6 +41,8 @@ function renderInlineMath(container: Element): void {
     } catch (error) {
       console.error("Failed to render inline math:", error);
       element.style.color = "red";
+      // Mark as processed even on error
+      element.dataset.katexRendered = "error";
     }
   }
 
@@ -62,6 +67,9 @@ function renderDisplayMath(container: Element): void {
         element: element as HTMLElement,
         content: mathContent,
       });
+    } else {
+      // Mark empty expressions as rendered to skip in future
+      element.dataset.katexRendered = "true";
     }
   }
 
@@ -78,6 +86,8 @@ function renderDisplayMath(container: Element): void {
     } catch (error) {
       console.error("Failed to render display math:", error);
       element.style.color = "red";
+      // Mark as processed even on error
+      element.dataset.katexRendered = "error";
     }
   }
 
@@ -119,7 +129,7 @@ function renderBlockMath(container: Element): void {
     } catch (error) {
       console.error("Failed to render math block:", error);
       element.style.color = "red";
-      element.dataset.rendered = "true";
+      element.dataset.rendered = "error";
     }
   }
 
@@ -1,8 +1,20 @@
 import * as mathRenderer from "./math-renderer";
 import * as mermaidRenderer from "./mermaid-renderer";
 import * as syntaxHighlighter from "./syntax-highlighter";
+import * as sourceViewer from "./source-viewer";
 import * as codeCopy from "./code-copy";
 
+// Elements still waiting for a renderer
+const PENDING_SELECTOR = [
+  "pre.preprocessed-mermaid:not([data-rendered])",
+  "pre.preprocessed-math:not([data-rendered])",
+  "span.preprocessed-math-inline:not([data-katex-rendered])",
+  "div.preprocessed-math-display:not([data-katex-rendered])",
+  "pre code:not([data-highlighted])",
+].join(", ");
+
+const POLL_INTERVAL_MS = 50;
+
 class RenderCoordinator {
   #rafId: number | null = null;
   #isRendering = false;
@@ -47,6 +59,26 @@ class RenderCoordinator {
     });
   }
 
+  /**
+   * Wait until diagrams, math and code are rendered and laid out
+   *
+   * Gives up after `timeoutMs` so a renderer that never finishes does not block the caller.
+   */
+  async whenRendered(timeoutMs = 5000): Promise<void> {
+    const deadline = performance.now() + timeoutMs;
+    while (document.querySelector(`.markdown-body :is(${PENDING_SELECTOR})`)) {
+      if (performance.now() > deadline) {
+        console.warn("RenderCoordinator: Timed out waiting for rendering");
+        break;
+      }
+      await new Promise((resolve) => setTimeout(resolve, POLL_INTERVAL_MS));
+    }
+    await Promise.all(Array.from(document.images, (image) => image.decode().catch(() => {})));
+    await document.fonts.ready;
+    // Two frames so the last changes are laid out
+    await new Promise((resolve) => requestAnimationFrame(() => requestAnimationFrame(resolve)));
+  }
+
   forceRenderMermaid(): void {
     const markdownBodies = document.querySelectorAll(".markdown-body");
     if (markdownBodies.length === 0) {
@@ -113,6 +145,7 @@ class RenderCoordinator {
         Array.from(markdownBodies).map(async (markdownBody) => {
           mathRenderer.renderMath(markdownBody);
           syntaxHighlighter.highlightCodeBlocks(markdownBody);
+          sourceViewer.highlightSourceViewers(markdownBody);
           await mermaidRenderer.renderDiagrams(markdownBody);
           codeCopy.addCopyButtons(markdownBody);
         })
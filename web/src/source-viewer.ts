@@ -0,0 +1,132 @@
+import hljs from "highlight.js";
+
+// Skip highlighting for very large files to keep the viewer responsive
+const MAX_HIGHLIGHT_LENGTH = 1_000_000;
+
+// Line where the current selection started (for shift-click range selection)
+let selectionAnchorLine: number | null = null;
+
+export function init(): void {
+  // Click on a line number to select it, shift-click to extend the selection
+  document.addEventListener("click", (event) => {
+    const target = event.target as Element | null;
+    const cell = target?.closest(".source-line-number") as HTMLElement | null;
+    if (!cell) {
+      return;
+    }
+    const line = Number(cell.dataset.lineNumber);
+    if (!Number.isInteger(line)) {
+      return;
+    }
+
+    event.preventDefault();
+    if (event.shiftKey && selectionAnchorLine !== null) {
+      selectLines(selectionAnchorLine, line);
+    } else {
+      selectionAnchorLine = line;
+      selectLines(line, line);
+    }
+  });
+
+  // Select and reveal lines requested from Rust (e.g. `file.rs#L10-L20`)
+  document.addEventListener("arto:select-lines", ((event: CustomEvent) => {
+    const { start, end } = event.detail as { start: number; end: number };
+    revealLines(start, end);
+  }) as EventListener);
+}
+
+export function highlightSourceViewers(container: Element): void {
+  const viewers = container.querySelectorAll(".source-viewer:not([data-highlighted])");
+  viewers.forEach((viewer) => {
+    highlightSourceViewer(viewer as HTMLElement);
+  });
+}
+
+function highlightSourceViewer(viewer: HTMLElement): void {
+  viewer.dataset.highlighted = "yes";
+
+  const language = viewer.dataset.language;
+  if (!language || !hljs.getLanguage(language)) {
+    console.debug(`Source language not registered: ${language}`);
+    return;
+  }
+
+  const cells = Array.from(viewer.querySelectorAll<HTMLElement>(".source-line-code"));
+  const code = cells.map((cell) => cell.textContent ?? "").join("\n");
+  if (code.length > MAX_HIGHLIGHT_LENGTH) {
+    console.debug("Source is too large to highlight");
+    return;
+  }
+
+  try {
+    const { value } = hljs.highlight(code, { language, ignoreIllegals: true });
+    splitHighlightedLines(value).forEach((html, index) => {
+      const cell = cells[index];
+      if (cell) {
+        cell.innerHTML = html;
+      }
+    });
+    console.debug(`Highlighted source with language: ${language}`);
+  } catch (error) {
+    console.warn(`Failed to highlight source (${language}):`, error);
+  }
+}
+
+/**
+ * Split highlight.js output into lines, re-opening spans that cross line breaks
+ */
+function splitHighlightedLines(html: string): string[] {
+  const lines: string[] = [];
+  const openTags: string[] = [];
+  let current = "";
+
+  for (const token of html.match(/<span[^>]*>|<\/span>|\n|[^<\n]+/g) ?? []) {
+    if (token === "\n") {
+      lines.push(current + "</span>".repeat(openTags.length));
+      current = openTags.join("");
+    } else if (token === "</span>") {
+      openTags.pop();
+      current += token;
+    } else {
+      if (token.startsWith("<span")) {
+        openTags.push(token);
+      }
+      current += token;
+    }
+  }
+  lines.push(current);
+  return lines;
+}
+
+function selectLines(start: number, end: number): HTMLElement | null {
+  const [from, to] = start <= end ? [start, end] : [end, start];
+  document.querySelectorAll(".source-line.selected").forEach((row) => {
+    row.classList.remove("selected");
+  });
+
+  let first: HTMLElement | null = null;
+  for (let line = from; line <= to; line++) {
+    const row = document.getElementById(`L${line}`);
+    if (!row?.classList.contains("source-line")) {
+      break;
+    }
+    row.classList.add("selected");
+    first ??= row;
+  }
+  return first;
+}
+
+function revealLines(start: number, end: number): void {
+  // The source view may not be rendered yet right after navigation
+  let attempts = 60;
+  const reveal = () => {
+    const first = selectLines(start, end);
+    if (first) {
+      selectionAnchorLine = start;
+      first.scrollIntoView({ block: "center" });
+    } else if (attempts-- > 0) {
+      requestAnimationFrame(reveal);
+    }
+  };
+  reveal();
+}